@@ -1,9 +1,9 @@
-/// Oracle Aggregation Module
-///
-/// Implements homomorphic aggregation of provider predictions
-/// Key feature: All computation happens on encrypted data
+//! Oracle Aggregation Module
+//!
+//! Implements homomorphic aggregation of provider predictions
+//! Key feature: All computation happens on encrypted data
 
-use crate::fhe_module::{FheCiphertext, FheBackend};
+use crate::fhe_module::{FheCiphertext, FheBackend, ResultDecryptor};
 use std::collections::HashMap;
 
 /// Provider submission (encrypted)
@@ -12,6 +12,56 @@ pub struct ProviderSubmission {
     pub provider_id: String,
     pub quantized_value: i64,
     pub weight: i64,
+    pub timestamp: u64,             // Unix seconds when the observation was made
+}
+
+/// Event submission window [start, end) in Unix seconds
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubmissionWindow {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl SubmissionWindow {
+    pub fn new(start: u64, end: u64) -> Result<Self, String> {
+        if end <= start {
+            return Err(format!("Empty submission window [{}, {})", start, end));
+        }
+        Ok(SubmissionWindow { start, end })
+    }
+
+    pub fn length(&self) -> u64 {
+        self.end - self.start
+    }
+
+    pub fn contains(&self, timestamp: u64) -> bool {
+        (self.start..self.end).contains(&timestamp)
+    }
+}
+
+/// Encrypted time-weighted average price (TWAP) consensus
+#[derive(Clone, Debug)]
+pub struct TwapResult {
+    /// Encrypted Σ_p w_p · Σ_i Δt_{p,i} · x_{p,i}  (consensus × W × T)
+    pub consensus_ciphertext: FheCiphertext,
+    /// Per-provider encrypted Σ_i Δt_i · x_i  (provider TWAP × T)
+    pub provider_twaps: Vec<(String, FheCiphertext)>,
+    /// Public total provider weight W
+    pub total_weight: i64,
+    /// Window length T in seconds
+    pub window_length: i64,
+}
+
+impl TwapResult {
+    /// Public scale factor W·T separating the ciphertext from the average
+    pub fn scale(&self) -> Result<i64, String> {
+        self.total_weight.checked_mul(self.window_length).ok_or_else(|| {
+            format!(
+                "TWAP scale overflows i64: weight {} over a {}s window",
+                self.total_weight, self.window_length
+            )
+        })
+    }
 }
 
 /// Aggregation result
@@ -21,9 +71,43 @@ pub struct AggregationResult {
     pub threshold_ciphertext: FheCiphertext,
     pub diff_ciphertext: FheCiphertext,
     pub comparison_result_ciphertext: FheCiphertext,
+    pub dispersion: Option<DispersionResult>,
     pub metadata: HashMap<String, String>,
 }
 
+/// Largest submission value dispersion accepts (percentage markets, 0–10000)
+pub const MAX_DISPERSION_VALUE: i64 = 10_000;
+
+/// Event disclosure policy for dispersion statistics
+///
+/// Percentage markets only: squaring 0–10000 values stays well inside i64,
+/// squaring 8-decimal prices does not. `homomorphic_dispersion` rejects
+/// values outside [0, `MAX_DISPERSION_VALUE`].
+#[derive(Clone, Debug, Default)]
+pub struct DisclosurePolicy {
+    /// Compute the encrypted weighted variance next to the aggregate
+    pub compute_dispersion: bool,
+    /// Allow the committee to decrypt the dispersion value itself
+    pub reveal_dispersion: bool,
+    /// Refuse to settle when the std dev exceeds this (quantized units)
+    pub max_std_dev: Option<i64>,
+}
+
+/// Encrypted dispersion of provider submissions
+#[derive(Clone, Debug)]
+pub struct DispersionResult {
+    /// Encrypted W²·Var = W·Σ(wᵢ·xᵢ²) − (Σ wᵢ·xᵢ)²
+    pub scaled_variance_ciphertext: FheCiphertext,
+    /// Encrypted flag (1 = std dev above `max_std_dev`), if a limit is set
+    pub disagreement_ciphertext: Option<FheCiphertext>,
+    /// The flag's limit in the W²-scaled domain, `max_std_dev`²·W²
+    pub disagreement_limit: Option<i64>,
+    /// Public total weight W = Σ wᵢ
+    pub total_weight: i64,
+    /// Whether the policy allows revealing the dispersion value
+    pub reveal_allowed: bool,
+}
+
 /// Perform homomorphic aggregation
 ///
 /// Computes: aggregate = Σ (weight_i * value_i)
@@ -78,6 +162,290 @@ pub fn homomorphic_threshold_compare<B: FheBackend + ?Sized>(
     result
 }
 
+/// Perform homomorphic dispersion (weighted variance) computation
+///
+/// Computes: W²·Var = W·Σ(wᵢ·xᵢ²) − (Σ wᵢ·xᵢ)²
+/// Squares are ciphertext × ciphertext products; weights and W are public,
+/// so no encrypted division is needed. Dividing by W² happens after
+/// decryption, and only if the policy allows it.
+///
+/// Fails unless every term, at its largest, fits in i64: values must be
+/// percentages and weights non-negative, and (`MAX_DISPERSION_VALUE`·W)²
+/// and (`max_std_dev`·W)² must not overflow.
+pub fn homomorphic_dispersion<B: FheBackend + ?Sized>(
+    backend: &B,
+    submissions: &[ProviderSubmission],
+    policy: &DisclosurePolicy,
+) -> Result<DispersionResult, String> {
+    println!("FHE Dispersion: {} providers", submissions.len());
+
+    let total_weight = dispersion_total_weight(submissions)?;
+    let limit = match policy.max_std_dev {
+        Some(max_std_dev) => Some((max_std_dev, dispersion_limit(max_std_dev, total_weight)?)),
+        None => None,
+    };
+
+    let mut ct_weighted_sum = backend.encrypt(0);
+    let mut ct_weighted_sum_sq = backend.encrypt(0);
+
+    for submission in submissions {
+        let ct_value = backend.encrypt(submission.quantized_value);
+
+        // Homomorphic squaring: CT_x * CT_x (encrypted)
+        let ct_square = backend.mul(&ct_value, &ct_value);
+
+        ct_weighted_sum = backend.add(
+            &ct_weighted_sum,
+            &backend.scalar_mul(&ct_value, submission.weight),
+        );
+        ct_weighted_sum_sq = backend.add(
+            &ct_weighted_sum_sq,
+            &backend.scalar_mul(&ct_square, submission.weight),
+        );
+    }
+
+    // W·Σ(wᵢ·xᵢ²) − (Σ wᵢ·xᵢ)²
+    let ct_sum_squared = backend.mul(&ct_weighted_sum, &ct_weighted_sum);
+    let ct_scaled_variance = backend.sub(
+        &backend.scalar_mul(&ct_weighted_sum_sq, total_weight),
+        &ct_sum_squared,
+    );
+    println!("  ✓ Weighted variance computed (result still encrypted)");
+
+    // Compare against the limit in the same W²-scaled domain
+    let disagreement_ciphertext = limit.map(|(max_std_dev, limit)| {
+        println!("  ✓ Disagreement check: std dev > {} (encrypted)", max_std_dev);
+        homomorphic_threshold_compare(backend, &ct_scaled_variance, limit)
+    });
+
+    Ok(DispersionResult {
+        scaled_variance_ciphertext: ct_scaled_variance,
+        disagreement_ciphertext,
+        disagreement_limit: limit.map(|(_, limit)| limit),
+        total_weight,
+        reveal_allowed: policy.reveal_dispersion,
+    })
+}
+
+/// Total weight W, once every value is a percentage and every weight is
+/// non-negative, and W·Σ(wᵢ·xᵢ²) and (Σ wᵢ·xᵢ)², both at most
+/// (`MAX_DISPERSION_VALUE`·W)², fit in i64
+fn dispersion_total_weight(submissions: &[ProviderSubmission]) -> Result<i64, String> {
+    let mut total_weight = 0i64;
+    for submission in submissions {
+        if !(0..=MAX_DISPERSION_VALUE).contains(&submission.quantized_value) {
+            return Err(format!(
+                "Dispersion is only defined for percentage markets: value {} from {} outside [0, {}]",
+                submission.quantized_value, submission.provider_id, MAX_DISPERSION_VALUE
+            ));
+        }
+        if submission.weight < 0 {
+            return Err(format!("Negative weight {} for {}", submission.weight, submission.provider_id));
+        }
+        total_weight = total_weight
+            .checked_add(submission.weight)
+            .ok_or("Total weight overflows i64")?;
+    }
+    MAX_DISPERSION_VALUE
+        .checked_mul(total_weight)
+        .and_then(|bound| bound.checked_mul(bound))
+        .ok_or_else(|| format!("Total weight {} too large for encrypted dispersion", total_weight))?;
+    Ok(total_weight)
+}
+
+/// `max_std_dev`² · W², the disagreement limit in the W²-scaled domain
+fn dispersion_limit(max_std_dev: i64, total_weight: i64) -> Result<i64, String> {
+    if max_std_dev < 0 {
+        return Err(format!("Negative std dev limit {}", max_std_dev));
+    }
+    max_std_dev
+        .checked_mul(total_weight)
+        .and_then(|scaled| scaled.checked_mul(scaled))
+        .ok_or_else(|| format!("Std dev limit {} too large for total weight {}", max_std_dev, total_weight))
+}
+
+/// Time weights (seconds in effect) for one provider's observations
+///
+/// Each observation holds until the next one; the first also covers the gap
+/// back to the window start, so every provider's weights sum to T.
+/// Observations must lie inside the window with strictly increasing timestamps.
+pub fn twap_time_weights(
+    submissions: &[&ProviderSubmission],
+    window: &SubmissionWindow,
+) -> Result<Vec<i64>, String> {
+    if submissions.is_empty() {
+        return Err("No observations to average".to_string());
+    }
+
+    for pair in submissions.windows(2) {
+        if pair[1].timestamp <= pair[0].timestamp {
+            return Err(format!(
+                "Observations for {} not strictly increasing: {} then {}",
+                pair[1].provider_id, pair[0].timestamp, pair[1].timestamp
+            ));
+        }
+    }
+
+    let mut weights = Vec::with_capacity(submissions.len());
+    for (i, submission) in submissions.iter().enumerate() {
+        if !window.contains(submission.timestamp) {
+            return Err(format!(
+                "Observation at {} from {} outside window [{}, {})",
+                submission.timestamp, submission.provider_id, window.start, window.end
+            ));
+        }
+        let from = if i == 0 { window.start } else { submission.timestamp };
+        let until = submissions
+            .get(i + 1)
+            .map(|next| next.timestamp)
+            .unwrap_or(window.end);
+        weights.push((until - from) as i64);
+    }
+
+    Ok(weights)
+}
+
+/// Perform homomorphic TWAP for one provider
+///
+/// Computes: CT_twap_scaled = Σ Δt_i · CT_value_i
+/// Time weights are public (timestamps are), so only scalar multiplication
+/// is needed; the result is the TWAP scaled by the window length T.
+/// Fails if Σ |x_i|·Δt_i, which bounds every partial sum, overflows i64.
+pub fn homomorphic_twap<B: FheBackend + ?Sized>(
+    backend: &B,
+    observations: &[&ProviderSubmission],
+    window: &SubmissionWindow,
+) -> Result<FheCiphertext, String> {
+    let time_weights = twap_time_weights(observations, window)?;
+    twap_magnitude(observations, &time_weights).ok_or_else(|| {
+        format!(
+            "TWAP for {} overflows i64 over a {}s window",
+            observations[0].provider_id,
+            window.length()
+        )
+    })?;
+
+    let mut twap = backend.encrypt(0);
+    for (observation, dt) in observations.iter().zip(time_weights) {
+        // Encrypt the observation (would come from blockchain in practice)
+        let ct_value = backend.encrypt(observation.quantized_value);
+        twap = backend.add(&twap, &backend.scalar_mul(&ct_value, dt));
+    }
+
+    Ok(twap)
+}
+
+/// Σ |x_i|·Δt_i, or `None` if it overflows i64
+fn twap_magnitude(observations: &[&ProviderSubmission], time_weights: &[i64]) -> Option<i64> {
+    observations
+        .iter()
+        .zip(time_weights)
+        .try_fold(0i64, |sum, (observation, &dt)| {
+            sum.checked_add(observation.quantized_value.checked_abs()?.checked_mul(dt)?)
+        })
+}
+
+/// Perform homomorphic TWAP consensus across providers
+///
+/// Groups submissions by provider (in order of first appearance), computes
+/// each provider's encrypted TWAP, then the weighted sum across providers.
+/// A provider's weight must be the same on all of its observations.
+/// Fails if the consensus, W·T or any intermediate sum could overflow i64.
+pub fn homomorphic_twap_consensus<B: FheBackend + ?Sized>(
+    backend: &B,
+    submissions: &[ProviderSubmission],
+    window: &SubmissionWindow,
+) -> Result<TwapResult, String> {
+    println!("FHE TWAP Aggregation: {} observations", submissions.len());
+
+    let mut providers: Vec<(&str, Vec<&ProviderSubmission>)> = Vec::new();
+    for submission in submissions {
+        match providers.iter_mut().find(|(id, _)| *id == submission.provider_id) {
+            Some((_, observations)) => {
+                if observations[0].weight != submission.weight {
+                    return Err(format!(
+                        "Inconsistent weight for {}: {} vs {}",
+                        submission.provider_id, observations[0].weight, submission.weight
+                    ));
+                }
+                observations.push(submission);
+            }
+            None => providers.push((&submission.provider_id, vec![submission])),
+        }
+    }
+
+    let overflow = || format!("TWAP consensus overflows i64 over a {}s window", window.length());
+    let mut consensus = backend.encrypt(0);
+    let mut provider_twaps = Vec::with_capacity(providers.len());
+    let mut total_weight = 0i64;
+    let mut magnitude = 0i64;
+
+    for (provider_id, mut observations) in providers {
+        observations.sort_by_key(|s| s.timestamp);
+        let weight = observations[0].weight;
+
+        let ct_twap = homomorphic_twap(backend, &observations, window)?;
+        let time_weights = twap_time_weights(&observations, window)?;
+        magnitude = twap_magnitude(&observations, &time_weights)
+            .and_then(|twap| twap.checked_mul(weight.checked_abs()?))
+            .and_then(|weighted| magnitude.checked_add(weighted))
+            .ok_or_else(overflow)?;
+        consensus = backend.add(&consensus, &backend.scalar_mul(&ct_twap, weight));
+        total_weight = total_weight.checked_add(weight).ok_or_else(overflow)?;
+
+        println!(
+            "  ✓ Provider {} TWAP added: {} observations, weight={} (encrypted)",
+            provider_id,
+            observations.len(),
+            weight
+        );
+        provider_twaps.push((provider_id.to_string(), ct_twap));
+    }
+
+    let twap = TwapResult {
+        consensus_ciphertext: consensus,
+        provider_twaps,
+        total_weight,
+        window_length: i64::try_from(window.length()).map_err(|_| overflow())?,
+    };
+    twap.scale()?;
+
+    println!("  ✓ TWAP consensus complete (result still encrypted)");
+    Ok(twap)
+}
+
+/// Perform threshold comparison on a TWAP consensus
+///
+/// Computes: (consensus > threshold) as CT_scaled > threshold·W·T
+pub fn homomorphic_twap_threshold_compare<B: FheBackend + ?Sized>(
+    backend: &B,
+    twap: &TwapResult,
+    threshold: i64,
+) -> Result<FheCiphertext, String> {
+    let scaled_threshold = threshold
+        .checked_mul(twap.scale()?)
+        .ok_or_else(|| format!("Threshold {} overflows i64 at TWAP scale", threshold))?;
+    Ok(homomorphic_threshold_compare(backend, &twap.consensus_ciphertext, scaled_threshold))
+}
+
+/// Decrypt a TWAP consensus, rounding to the nearest quantized unit
+///
+/// Called by threshold decryption committee
+pub fn reveal_twap<D: ResultDecryptor + ?Sized>(
+    decryptor: &D,
+    twap: &TwapResult,
+) -> Result<i64, String> {
+    let scale = twap.scale()?;
+    if scale == 0 {
+        return Ok(0);
+    }
+    let scaled = decryptor.decrypt_result(&twap.consensus_ciphertext)?;
+    let rounded = scaled
+        .checked_add(scale / 2)
+        .ok_or("Decrypted TWAP out of range")?;
+    Ok(rounded.div_euclid(scale))
+}
+
 /// Full oracle aggregation workflow
 ///
 /// 1. Encrypt provider values
@@ -90,6 +458,26 @@ pub fn oracle_aggregation_workflow<B: FheBackend + ?Sized>(
     submissions: &[ProviderSubmission],
     threshold: i64,
 ) -> AggregationResult {
+    oracle_aggregation_workflow_with_policy(
+        backend,
+        submissions,
+        threshold,
+        &DisclosurePolicy::default(),
+    )
+    .expect("default policy computes no dispersion")
+}
+
+/// Full oracle aggregation workflow under an event disclosure policy
+///
+/// Same as `oracle_aggregation_workflow`, plus the encrypted dispersion
+/// when `policy.compute_dispersion` is set; fails if the submissions are
+/// outside the dispersion domain
+pub fn oracle_aggregation_workflow_with_policy<B: FheBackend + ?Sized>(
+    backend: &B,
+    submissions: &[ProviderSubmission],
+    threshold: i64,
+    policy: &DisclosurePolicy,
+) -> Result<AggregationResult, String> {
     println!("\n╔═══════════════════════════════════════════════════════════╗");
     println!("║  Oracle Aggregation Workflow                            ║");
     println!("╚═══════════════════════════════════════════════════════════╝\n");
@@ -114,6 +502,16 @@ pub fn oracle_aggregation_workflow<B: FheBackend + ?Sized>(
     println!("  ✓ Comparison complete (result still encrypted)");
     println!();
     
+    // Optional: Homomorphic dispersion
+    let dispersion = if policy.compute_dispersion {
+        println!("Step 3b: Homomorphic Dispersion (Confidence Interval)");
+        let dispersion = homomorphic_dispersion(backend, submissions, policy)?;
+        println!();
+        Some(dispersion)
+    } else {
+        None
+    };
+    
     // Step 4: Metadata
     let mut metadata = HashMap::new();
     metadata.insert("submissions_count".to_string(), submissions.len().to_string());
@@ -126,51 +524,95 @@ pub fn oracle_aggregation_workflow<B: FheBackend + ?Sized>(
     println!("  ✓ Ready for threshold decryption committee");
     println!();
     
-    AggregationResult {
+    Ok(AggregationResult {
         aggregate_ciphertext: ct_aggregate,
         threshold_ciphertext: ct_threshold,
         diff_ciphertext: ct_diff,
         comparison_result_ciphertext: ct_result,
+        dispersion,
         metadata,
-    }
+    })
 }
 
 /// Decrypt and reveal final oracle result
 ///
-/// Called by threshold decryption committee
-pub fn reveal_oracle_result<B: FheBackend + ?Sized>(
-    backend: &B,
+/// Called by threshold decryption committee; any backend also decrypts
+/// with its full key
+pub fn reveal_oracle_result<D: ResultDecryptor + ?Sized>(
+    decryptor: &D,
     result: &AggregationResult,
-) -> OracleDecision {
+) -> Result<OracleDecision, String> {
     println!("\n╔═══════════════════════════════════════════════════════════╗");
     println!("║  Oracle Result Decryption (by Threshold Committee)      ║");
     println!("╚═══════════════════════════════════════════════════════════╝\n");
     
     // Decrypt values
-    let aggregate_value = backend.decrypt(&result.aggregate_ciphertext);
-    let threshold_value = backend.decrypt(&result.threshold_ciphertext);
-    let diff_value = backend.decrypt(&result.diff_ciphertext);
-    let comparison_result = backend.decrypt(&result.comparison_result_ciphertext);
+    let aggregate_value = decryptor.decrypt_result(&result.aggregate_ciphertext)?;
+    let threshold_value = decryptor.decrypt_result(&result.threshold_ciphertext)?;
+    let diff_value = decryptor.decrypt_result(&result.diff_ciphertext)?;
+    let comparison_result = decryptor.decrypt_result(&result.comparison_result_ciphertext)?;
     
     println!("Decrypted Values:");
     println!("  Aggregate:      {}", aggregate_value);
     println!("  Threshold:      {}", threshold_value);
     println!("  Difference:     {} (aggregate - threshold)", diff_value);
     println!("  Comparison (1=YES, 0=NO): {}", comparison_result);
+    
+    // Dispersion: the disagreement flag is always decrypted, the value
+    // itself only if the disclosure policy allows it
+    let mut std_dev = None;
+    let mut providers_disagree = false;
+    if let Some(dispersion) = &result.dispersion {
+        if let Some(ct_flag) = &dispersion.disagreement_ciphertext {
+            providers_disagree = decryptor.decrypt_result(ct_flag)? != 0;
+            println!("  Providers disagree: {}", providers_disagree);
+        }
+        if dispersion.reveal_allowed {
+            let value = reveal_std_dev(decryptor, dispersion)?;
+            println!("  Std deviation:  {:.2}", value);
+            std_dev = Some(value);
+        } else {
+            println!("  Std deviation:  (withheld by disclosure policy)");
+        }
+    }
     println!();
     
-    let decision = if comparison_result != 0 { "YES" } else { "NO" };
+    let decision = if providers_disagree {
+        "UNRESOLVED"
+    } else if comparison_result != 0 {
+        "YES"
+    } else {
+        "NO"
+    };
     
     println!("╔═══════════════════════════════════════════════════════════╗");
     println!("║  ORACLE DECISION: {}                                   ║", decision);
     println!("╚═══════════════════════════════════════════════════════════╝\n");
     
-    OracleDecision {
+    Ok(OracleDecision {
         aggregate_value,
         threshold_value,
         is_above_threshold: comparison_result != 0,
+        std_dev,
+        providers_disagree,
         decision: decision.to_string(),
+    })
+}
+
+/// Decrypt the dispersion and convert to a standard deviation
+///
+/// std dev = √(W²·Var) / W
+pub fn reveal_std_dev<D: ResultDecryptor + ?Sized>(
+    decryptor: &D,
+    dispersion: &DispersionResult,
+) -> Result<f64, String> {
+    if dispersion.total_weight == 0 {
+        return Ok(0.0);
     }
+    let scaled_variance = decryptor
+        .decrypt_result(&dispersion.scaled_variance_ciphertext)?
+        .max(0);
+    Ok((scaled_variance as f64).sqrt() / dispersion.total_weight as f64)
 }
 
 #[derive(Debug)]
@@ -178,5 +620,250 @@ pub struct OracleDecision {
     pub aggregate_value: i64,
     pub threshold_value: i64,
     pub is_above_threshold: bool,
+    pub std_dev: Option<f64>,
+    pub providers_disagree: bool,
     pub decision: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fhe_module::MockFhe;
+
+    fn submission(id: &str, value: i64, weight: i64) -> ProviderSubmission {
+        observation(id, value, weight, 1729418400)
+    }
+
+    fn observation(id: &str, value: i64, weight: i64, timestamp: u64) -> ProviderSubmission {
+        ProviderSubmission {
+            provider_id: id.to_string(),
+            quantized_value: value,
+            weight,
+            timestamp,
+        }
+    }
+
+    fn dispersion_policy(reveal: bool, max_std_dev: Option<i64>) -> DisclosurePolicy {
+        DisclosurePolicy {
+            compute_dispersion: true,
+            reveal_dispersion: reveal,
+            max_std_dev,
+        }
+    }
+
+    #[test]
+    fn test_dispersion_zero_when_providers_agree() {
+        let backend = MockFhe;
+        let subs = vec![submission("A", 6000, 1), submission("B", 6000, 3)];
+        let dispersion = homomorphic_dispersion(&backend, &subs, &dispersion_policy(true, None)).unwrap();
+
+        assert_eq!(backend.decrypt(&dispersion.scaled_variance_ciphertext), 0);
+        assert_eq!(reveal_std_dev(&backend, &dispersion).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_dispersion_matches_plaintext_weighted_std_dev() {
+        let backend = MockFhe;
+        // Weighted mean = (4000·1 + 6000·1 + 8000·2) / 4 = 6500
+        // Var = (1·2500² + 1·500² + 2·1500²) / 4 = 2_750_000
+        let subs = vec![
+            submission("A", 4000, 1),
+            submission("B", 6000, 1),
+            submission("C", 8000, 2),
+        ];
+        let dispersion = homomorphic_dispersion(&backend, &subs, &dispersion_policy(true, None)).unwrap();
+
+        assert_eq!(dispersion.total_weight, 4);
+        assert_eq!(
+            backend.decrypt(&dispersion.scaled_variance_ciphertext),
+            2_750_000 * 16
+        );
+        let std_dev = reveal_std_dev(&backend, &dispersion).unwrap();
+        assert!((std_dev - 2_750_000f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dispersion_limit_flags_disagreement() {
+        let backend = MockFhe;
+        let subs = vec![submission("A", 1000, 1), submission("B", 9000, 1)];
+
+        // std dev = 4000
+        let loose = homomorphic_dispersion(&backend, &subs, &dispersion_policy(false, Some(4000))).unwrap();
+        let strict = homomorphic_dispersion(&backend, &subs, &dispersion_policy(false, Some(3999))).unwrap();
+
+        assert_eq!(backend.decrypt(loose.disagreement_ciphertext.as_ref().unwrap()), 0);
+        assert_eq!(backend.decrypt(strict.disagreement_ciphertext.as_ref().unwrap()), 1);
+    }
+
+    #[test]
+    fn test_reveal_refuses_settlement_on_disagreement() {
+        let backend = MockFhe;
+        let subs = vec![submission("A", 1000, 1), submission("B", 9000, 1)];
+        let policy = dispersion_policy(false, Some(500));
+
+        let result = oracle_aggregation_workflow_with_policy(&backend, &subs, 5000, &policy).unwrap();
+        let decision = reveal_oracle_result(&backend, &result).unwrap();
+
+        assert!(decision.providers_disagree);
+        assert_eq!(decision.decision, "UNRESOLVED");
+        // Policy withholds the dispersion value itself
+        assert_eq!(decision.std_dev, None);
+    }
+
+    #[test]
+    fn test_reveal_discloses_std_dev_when_policy_allows() {
+        let backend = MockFhe;
+        let subs = vec![submission("A", 5000, 1), submission("B", 5200, 1)];
+        let policy = dispersion_policy(true, Some(500));
+
+        let result = oracle_aggregation_workflow_with_policy(&backend, &subs, 9000, &policy).unwrap();
+        let decision = reveal_oracle_result(&backend, &result).unwrap();
+
+        assert!(!decision.providers_disagree);
+        assert_eq!(decision.decision, "YES");
+        assert_eq!(decision.std_dev, Some(100.0));
+    }
+
+    #[test]
+    fn test_dispersion_rejects_values_outside_percent_domain() {
+        let backend = MockFhe;
+        // Quantized $3250.50: its square alone is ~1e23
+        let prices = vec![submission("A", 325050000000, 1), submission("B", 325000000000, 1)];
+        let policy = dispersion_policy(false, Some(100));
+
+        assert!(homomorphic_dispersion(&backend, &prices, &policy).is_err());
+        assert!(oracle_aggregation_workflow_with_policy(&backend, &prices, 5000, &policy).is_err());
+
+        let negative = vec![submission("A", -1, 1)];
+        assert!(homomorphic_dispersion(&backend, &negative, &policy).is_err());
+    }
+
+    #[test]
+    fn test_dispersion_rejects_overflowing_weights_and_limits() {
+        let backend = MockFhe;
+        let heavy = vec![submission("A", 5000, 1 << 40)];
+        assert!(homomorphic_dispersion(&backend, &heavy, &dispersion_policy(false, None)).is_err());
+
+        let subs = vec![submission("A", 5000, 1), submission("B", 6000, 1)];
+        let huge_limit = dispersion_policy(false, Some(i64::MAX / 2));
+        assert!(homomorphic_dispersion(&backend, &subs, &huge_limit).is_err());
+        assert!(homomorphic_dispersion(&backend, &subs, &dispersion_policy(false, Some(-1))).is_err());
+    }
+
+    #[test]
+    fn test_default_policy_skips_dispersion() {
+        let backend = MockFhe;
+        let subs = vec![submission("A", 5000, 1)];
+
+        let result = oracle_aggregation_workflow(&backend, &subs, 4000);
+        assert!(result.dispersion.is_none());
+
+        let decision = reveal_oracle_result(&backend, &result).unwrap();
+        assert_eq!(decision.std_dev, None);
+        assert!(!decision.providers_disagree);
+    }
+
+    #[test]
+    fn test_twap_constant_price() {
+        let backend = MockFhe;
+        let window = SubmissionWindow::new(1000, 1600).unwrap();
+        let subs = vec![
+            observation("A", 325050000000, 1, 1000),
+            observation("A", 325050000000, 1, 1300),
+        ];
+
+        let twap = homomorphic_twap_consensus(&backend, &subs, &window).unwrap();
+        assert_eq!(reveal_twap(&backend, &twap).unwrap(), 325050000000);
+    }
+
+    #[test]
+    fn test_twap_weights_by_time_in_effect() {
+        let backend = MockFhe;
+        let window = SubmissionWindow::new(0, 100).unwrap();
+        // 100 from t=0..75, 200 from t=75..100 → (100·75 + 200·25) / 100 = 125
+        let subs = vec![observation("A", 200, 1, 75), observation("A", 100, 1, 0)];
+
+        let twap = homomorphic_twap_consensus(&backend, &subs, &window).unwrap();
+        assert_eq!(backend.decrypt(&twap.provider_twaps[0].1), 12_500);
+        assert_eq!(reveal_twap(&backend, &twap).unwrap(), 125);
+    }
+
+    #[test]
+    fn test_twap_first_observation_backfills_window_start() {
+        let window = SubmissionWindow::new(0, 100).unwrap();
+        let late = observation("A", 100, 1, 40);
+        let later = observation("A", 200, 1, 90);
+
+        let weights = twap_time_weights(&[&late, &later], &window).unwrap();
+        assert_eq!(weights, vec![90, 10]);
+    }
+
+    #[test]
+    fn test_twap_consensus_across_providers() {
+        let backend = MockFhe;
+        let window = SubmissionWindow::new(0, 10).unwrap();
+        // A: TWAP 100 (weight 3), B: (200·5 + 400·5)/10 = 300 (weight 1)
+        // Consensus = (3·100 + 1·300) / 4 = 150
+        let subs = vec![
+            observation("A", 100, 3, 0),
+            observation("B", 200, 1, 0),
+            observation("B", 400, 1, 5),
+        ];
+
+        let twap = homomorphic_twap_consensus(&backend, &subs, &window).unwrap();
+        assert_eq!(twap.total_weight, 4);
+        assert_eq!(twap.provider_twaps.len(), 2);
+        assert_eq!(reveal_twap(&backend, &twap).unwrap(), 150);
+
+        let above = homomorphic_twap_threshold_compare(&backend, &twap, 149).unwrap();
+        let below = homomorphic_twap_threshold_compare(&backend, &twap, 150).unwrap();
+        assert_eq!(backend.decrypt(&above), 1);
+        assert_eq!(backend.decrypt(&below), 0);
+    }
+
+    #[test]
+    fn test_twap_rejects_overflowing_window_and_weight() {
+        let backend = MockFhe;
+        // MAX_PRICE (92233720.36) quantized to 8 decimals, held for 600s at W=2
+        let max_price = 9_223_372_036_000_000;
+        let window = SubmissionWindow::new(0, 600).unwrap();
+        let subs = vec![observation("A", max_price, 2, 0)];
+        assert!(homomorphic_twap_consensus(&backend, &subs, &window).is_err());
+
+        // W=1 fits at 600s but not at 1200s
+        let single = observation("A", max_price, 1, 0);
+        assert!(homomorphic_twap(&backend, &[&single], &window).is_ok());
+        let longer = SubmissionWindow::new(0, 1200).unwrap();
+        assert!(homomorphic_twap(&backend, &[&single], &longer).is_err());
+
+        // Fits, but the threshold does not at scale W·T
+        let small = vec![observation("A", 100, 1, 0)];
+        let twap = homomorphic_twap_consensus(&backend, &small, &window).unwrap();
+        assert!(homomorphic_twap_threshold_compare(&backend, &twap, i64::MAX / 100).is_err());
+
+        let huge_scale = TwapResult {
+            total_weight: i64::MAX,
+            window_length: 2,
+            ..twap
+        };
+        assert!(huge_scale.scale().is_err());
+        assert!(reveal_twap(&backend, &huge_scale).is_err());
+    }
+
+    #[test]
+    fn test_twap_rejects_invalid_observations() {
+        let backend = MockFhe;
+        let window = SubmissionWindow::new(100, 200).unwrap();
+
+        let outside = vec![observation("A", 100, 1, 200)];
+        assert!(homomorphic_twap_consensus(&backend, &outside, &window).is_err());
+
+        let duplicate = vec![observation("A", 100, 1, 150), observation("A", 120, 1, 150)];
+        assert!(homomorphic_twap_consensus(&backend, &duplicate, &window).is_err());
+
+        let mixed_weights = vec![observation("A", 100, 1, 110), observation("A", 120, 2, 150)];
+        assert!(homomorphic_twap_consensus(&backend, &mixed_weights, &window).is_err());
+
+        assert!(SubmissionWindow::new(200, 200).is_err());
+    }
+}
@@ -0,0 +1,1180 @@
+//! Oracle Coordinator
+//!
+//! Drives an event from submissions to a committee-signed oracle result on
+//! the LWE stack of `crate::threshold_lwe`:
+//! 1. Collect provider submissions until submissions close,
+//!    `PROPOSAL_WINDOW` before the contract deadline. Providers encrypt
+//!    their quantized value with the event's `FheBackend` and sign it for
+//!    the event; weights are public.
+//! 2. Aggregate homomorphically, ∑ wᵢ·Enc(xᵢ), with the ciphertexts' public
+//!    linear operations. Nodes read the event state from the contract and
+//!    recompute the aggregate from the signed submissions themselves.
+//! 3. Fan out `POST /decrypt` to every node in parallel. Each node returns
+//!    a partial decryption for every quorum it belongs to; wait for k + 2f
+//!    valid responses.
+//! 4. Decode with every quorum (`combine_cross_checked`), setting aside up
+//!    to f decryptors whose partials are wrong, and send the result with its
+//!    evidence to the rest in `POST /sign`. Each checks the result itself and
+//!    returns a FROST signature share over the nonce commitment it sent with
+//!    its partials; the coordinator aggregates the shares into one signature
+//!    under the group key. The result can then be proposed on chain before
+//!    the deadline; the requests and responses travel with it as evidence.
+//!
+//! Plaintexts are signed and below ±2^57, so weighted sums of 8-decimal
+//! prices and negative differences decrypt. The coordinator knows only the
+//! public [`DkgCommitment`], so it cannot sign a result by itself.
+//!
+//! A decryption attempt ends when enough valid responses arrive or the
+//! round timeout expires. Nodes that failed, timed out or answered with an
+//! invalid signature are asked again in a fresh round after a backoff, up to
+//! `max_attempts` rounds; every failure is reported. Signing shares are
+//! asked for the same way. If they still do not all arrive, the stored
+//! responses are dropped, since their nonces may be spent, and the next
+//! call decrypts afresh.
+
+use crate::aggregation::ProviderSubmission;
+use crate::decryptor_service::{
+    post_decrypt, post_sign, signature_from_hex, DecryptRequest, DecryptResponse, ServiceError, SignRequest,
+};
+use crate::event_lifecycle::submissions_close;
+use crate::event_store::{EventState, EventStore, StoredEvent};
+use crate::fhe_module::FheBackend;
+use crate::share_storage::{from_hex, to_hex, DkgCommitment};
+use crate::threshold_lwe::{check_committee_size, combine_cross_checked, quorums, LweCiphertext, LweDecryption, LweParams, PartialDecryption};
+use crate::threshold_signature::{
+    aggregate, verify_oracle_result, verify_signature, OracleResult, Signature, SignatureShare, SigningKey,
+};
+use curve25519_dalek::ristretto::RistrettoPoint;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const SUBMISSION_LABEL: &[u8] = b"bsfo-submission-v1";
+
+/// A provider's encrypted, weighted observation, signed by the provider
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "SubmissionJson", into = "SubmissionJson")]
+pub struct EncryptedSubmission {
+    pub provider_id: String,
+    pub ciphertext: LweCiphertext,
+    pub weight: i64,
+    /// Unix seconds
+    pub timestamp: u64,
+    /// Provider's signature binding all of the above to one event
+    pub signature: Signature,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SubmissionJson {
+    provider_id: String,
+    ciphertext: String,
+    weight: i64,
+    timestamp: u64,
+    signature: String,
+}
+
+/// label ‖ len ‖ event id ‖ len ‖ provider id ‖ len ‖ ciphertext ‖ weight ‖
+/// timestamp
+fn submission_message(
+    event_id: &str,
+    provider_id: &str,
+    ciphertext: &LweCiphertext,
+    weight: i64,
+    timestamp: u64,
+) -> Vec<u8> {
+    let mut message = SUBMISSION_LABEL.to_vec();
+    for field in [event_id.as_bytes(), provider_id.as_bytes(), &ciphertext.to_bytes()] {
+        message.extend_from_slice(&(field.len() as u32).to_be_bytes());
+        message.extend_from_slice(field);
+    }
+    message.extend_from_slice(&weight.to_be_bytes());
+    message.extend_from_slice(&timestamp.to_be_bytes());
+    message
+}
+
+impl EncryptedSubmission {
+    /// Encrypt an observation with the event's backend and sign it for
+    /// `event_id` with the provider's key
+    pub fn encrypt<B: FheBackend + ?Sized, R: RngCore + CryptoRng>(
+        provider: &SigningKey,
+        event_id: &str,
+        observation: &ProviderSubmission,
+        backend: &B,
+        rng: &mut R,
+    ) -> Result<Self, String> {
+        let ciphertext = LweCiphertext::try_from(&backend.encrypt(observation.quantized_value))?;
+        let message = submission_message(
+            event_id,
+            &observation.provider_id,
+            &ciphertext,
+            observation.weight,
+            observation.timestamp,
+        );
+        Ok(EncryptedSubmission {
+            provider_id: observation.provider_id.clone(),
+            ciphertext,
+            weight: observation.weight,
+            timestamp: observation.timestamp,
+            signature: provider.sign(&message, rng),
+        })
+    }
+
+    /// Check the signature under the provider's registered key
+    pub fn verify(&self, event_id: &str, provider_key: &RistrettoPoint) -> bool {
+        let message = submission_message(
+            event_id,
+            &self.provider_id,
+            &self.ciphertext,
+            self.weight,
+            self.timestamp,
+        );
+        verify_signature(provider_key, &message, &self.signature)
+    }
+}
+
+impl From<EncryptedSubmission> for SubmissionJson {
+    fn from(submission: EncryptedSubmission) -> Self {
+        SubmissionJson {
+            ciphertext: to_hex(&submission.ciphertext.to_bytes()),
+            signature: to_hex(&submission.signature.to_bytes()),
+            provider_id: submission.provider_id,
+            weight: submission.weight,
+            timestamp: submission.timestamp,
+        }
+    }
+}
+
+impl TryFrom<SubmissionJson> for EncryptedSubmission {
+    type Error = String;
+
+    fn try_from(wire: SubmissionJson) -> Result<Self, String> {
+        Ok(EncryptedSubmission {
+            ciphertext: LweCiphertext::from_bytes(&from_hex(&wire.ciphertext)?)?,
+            signature: signature_from_hex(&wire.signature)?,
+            provider_id: wire.provider_id,
+            weight: wire.weight,
+            timestamp: wire.timestamp,
+        })
+    }
+}
+
+/// ∑ wᵢ·Enc(xᵢ) over an event's submissions, in provider id order: the
+/// ciphertext nodes are asked to decrypt, and recompute themselves first
+pub fn aggregate_submissions(submissions: &[EncryptedSubmission]) -> Result<LweCiphertext, String> {
+    let mut sorted: Vec<&EncryptedSubmission> = submissions.iter().collect();
+    sorted.sort_by(|a, b| a.provider_id.cmp(&b.provider_id));
+    sorted
+        .iter()
+        .map(|s| s.ciphertext.scalar_mul(s.weight))
+        .reduce(|a, b| a.add(&b))
+        .ok_or_else(|| "No submissions to aggregate".to_string())
+}
+
+/// "YES" when the aggregate exceeds the event threshold
+fn decide(aggregate_value: i64, threshold: i64) -> &'static str {
+    if aggregate_value > threshold {
+        "YES"
+    } else {
+        "NO"
+    }
+}
+
+/// A decryptor node the coordinator can reach
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecryptorEndpoint {
+    pub id: u32,
+    pub addr: SocketAddr,
+    /// Key the node signs its responses with
+    pub identity: RistrettoPoint,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CoordinatorConfig {
+    /// LWE key shares needed to decrypt (k)
+    pub threshold: u32,
+    /// Decryptors with wrong partials to tolerate (f); decrypting waits
+    /// for k + 2f responses
+    pub faults: u32,
+    pub params: LweParams,
+    /// How long one decryption attempt waits for responses
+    pub round_timeout: Duration,
+    /// Decryption attempts before giving up
+    pub max_attempts: u32,
+    /// Pause between attempts
+    pub retry_backoff: Duration,
+}
+
+impl CoordinatorConfig {
+    /// Valid responses a decryption needs, k + 2f
+    pub fn responses_needed(&self) -> usize {
+        (self.threshold + 2 * self.faults) as usize
+    }
+}
+
+impl Default for CoordinatorConfig {
+    fn default() -> Self {
+        CoordinatorConfig {
+            threshold: 3,
+            faults: 1,
+            params: LweParams::default(),
+            round_timeout: Duration::from_secs(10),
+            max_attempts: 3,
+            retry_backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+/// A decryptor that did not contribute in some attempt, and why
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoundFailure {
+    pub id: u32,
+    pub round: u64,
+    pub error: ServiceError,
+}
+
+/// Each quorum of k among the responders, with every member's partial for it
+fn quorum_partials(responses: &[&DecryptResponse], threshold: u32) -> Result<Vec<Vec<PartialDecryption>>, String> {
+    let mut ids: Vec<u32> = responses.iter().map(|r| r.id).collect();
+    ids.sort_unstable();
+    quorums(&ids, threshold)
+        .into_iter()
+        .map(|quorum| {
+            quorum
+                .iter()
+                .map(|id| {
+                    responses
+                        .iter()
+                        .find(|r| r.id == *id)
+                        .and_then(|r| r.partial_for(&quorum))
+                        .ok_or_else(|| format!("Decryptor {} sent no partial for quorum {:?}", id, quorum))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Decode the aggregate with every quorum of the responders
+fn cross_check(
+    config: &CoordinatorConfig,
+    ciphertext: &LweCiphertext,
+    evidence: &[(DecryptRequest, DecryptResponse)],
+) -> Result<LweDecryption, String> {
+    let responses: Vec<&DecryptResponse> = evidence.iter().map(|(_, r)| r).collect();
+    let partials = quorum_partials(&responses, config.threshold)?;
+    combine_cross_checked(&config.params, ciphertext, &partials, config.threshold, config.faults)
+}
+
+/// Check a result against its evidence and return the cross-checked
+/// decryption: every request is the coordinator's, for this event and over
+/// the aggregate of registered providers' signed submissions; every response
+/// is signed by its decryptor's identity key; and the partials decrypt to
+/// the aggregate value and the result. Decryptor nodes run the same check
+/// before they sign.
+pub fn check_evidence(
+    result: &OracleResult,
+    evidence: &[(DecryptRequest, DecryptResponse)],
+    coordinator: &RistrettoPoint,
+    identities: &HashMap<u32, RistrettoPoint>,
+    providers: &HashMap<String, RistrettoPoint>,
+    config: &CoordinatorConfig,
+) -> Result<LweDecryption, String> {
+    let (first, _) = evidence.first().ok_or("Result has no evidence")?;
+    let ciphertext = &first.ciphertext;
+    let mut ids = Vec::with_capacity(evidence.len());
+    for (request, response) in evidence {
+        if request.event_id != result.event_id {
+            return Err(format!(
+                "Evidence for event {} backs a result for {}",
+                request.event_id, result.event_id
+            ));
+        }
+        if !request.verify(coordinator) || &request.ciphertext != ciphertext {
+            return Err(format!("Request for round {} is not the coordinator's for this aggregate", request.round));
+        }
+        request.check_aggregate(providers)?;
+        let signed = identities
+            .get(&response.id)
+            .is_some_and(|identity| response.verify(request, identity));
+        if !signed {
+            return Err(format!("Response from decryptor {} does not verify", response.id));
+        }
+        ids.push(response.id);
+    }
+    ids.sort_unstable();
+    let mut claimed = result.decryptor_set.clone();
+    claimed.sort_unstable();
+    if ids != claimed {
+        return Err(format!("Evidence from {:?} does not match decryptors {:?}", ids, claimed));
+    }
+
+    let decryption = cross_check(config, ciphertext, evidence)?;
+    if decryption.value != result.aggregate_value {
+        return Err(format!(
+            "Partials decrypt to {}, result claims {}",
+            decryption.value, result.aggregate_value
+        ));
+    }
+    if result.result != decide(decryption.value, result.threshold) {
+        return Err(format!("Result {} does not follow from the aggregate", result.result));
+    }
+    Ok(decryption)
+}
+
+/// Committee-signed oracle result with the decryptions behind it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignedOracleResult {
+    pub result: OracleResult,
+    /// FROST signature under the committee's group key
+    pub signature: Signature,
+    /// Every valid response, each with the request it answered
+    pub evidence: Vec<(DecryptRequest, DecryptResponse)>,
+    /// Failures seen along the way, including ones later retried
+    pub failures: Vec<RoundFailure>,
+}
+
+impl SignedOracleResult {
+    /// Check the group signature, and the result against its evidence:
+    /// every request is the coordinator's, for this event and over the
+    /// aggregate of registered providers' signed submissions; every response
+    /// is signed by its decryptor; and cross-checking the partials yields
+    /// the aggregate value and the result
+    pub fn verify(
+        &self,
+        group_key: &RistrettoPoint,
+        coordinator: &RistrettoPoint,
+        endpoints: &[DecryptorEndpoint],
+        providers: &HashMap<String, RistrettoPoint>,
+        config: &CoordinatorConfig,
+    ) -> bool {
+        self.check(group_key, coordinator, endpoints, providers, config).is_ok()
+    }
+
+    fn check(
+        &self,
+        group_key: &RistrettoPoint,
+        coordinator: &RistrettoPoint,
+        endpoints: &[DecryptorEndpoint],
+        providers: &HashMap<String, RistrettoPoint>,
+        config: &CoordinatorConfig,
+    ) -> Result<(), String> {
+        if !verify_oracle_result(group_key, &self.result, &self.signature) {
+            return Err("Result is not signed by the committee".to_string());
+        }
+        let identities = endpoints.iter().map(|e| (e.id, e.identity)).collect();
+        check_evidence(&self.result, &self.evidence, coordinator, &identities, providers, config).map(|_| ())
+    }
+
+    /// Arguments for the contract's `proposeResult`: 1 for YES and 0 for NO,
+    /// and the SHA-256 of the canonical result bytes
+    pub fn proposal(&self) -> (u8, [u8; 32]) {
+        let result = u8::from(self.result.result == "YES");
+        (result, Sha256::digest(self.result.canonical_bytes()).into())
+    }
+}
+
+/// An event's public parameters
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OracleEvent {
+    pub event_id: String,
+    /// Result is "YES" when the aggregate exceeds this
+    pub threshold: i64,
+    /// The contract deadline, the last time a result can be proposed;
+    /// submissions close `PROPOSAL_WINDOW` earlier
+    pub deadline: u64,
+}
+
+/// Runs events against a committee of decryptor nodes
+pub struct Coordinator {
+    /// Authenticates decryption and signing requests; results are signed
+    /// by the decryptors under `group`
+    signing_key: SigningKey,
+    /// Public result of the decryptors' DKG; the coordinator holds no share
+    group: DkgCommitment,
+    endpoints: Vec<DecryptorEndpoint>,
+    /// Registered provider keys by provider id
+    providers: HashMap<String, RistrettoPoint>,
+    config: CoordinatorConfig,
+    store: EventStore,
+}
+
+impl Coordinator {
+    /// Coordinator that keeps its events in memory only
+    pub fn new(
+        signing_key: SigningKey,
+        group: DkgCommitment,
+        endpoints: Vec<DecryptorEndpoint>,
+        providers: HashMap<String, RistrettoPoint>,
+        config: CoordinatorConfig,
+    ) -> Result<Self, String> {
+        Self::with_store(signing_key, group, endpoints, providers, config, EventStore::in_memory())
+    }
+
+    /// Coordinator that records every step in `store` and continues the
+    /// events already in it
+    pub fn with_store(
+        signing_key: SigningKey,
+        group: DkgCommitment,
+        endpoints: Vec<DecryptorEndpoint>,
+        providers: HashMap<String, RistrettoPoint>,
+        config: CoordinatorConfig,
+        store: EventStore,
+    ) -> Result<Self, String> {
+        config.params.validate()?;
+        if config.threshold == 0 || endpoints.len() < config.responses_needed() {
+            return Err(format!(
+                "Need at least {} decryptor endpoints, have {}",
+                config.responses_needed().max(1),
+                endpoints.len()
+            ));
+        }
+        check_committee_size(endpoints.len(), config.threshold)?;
+        if config.max_attempts == 0 {
+            return Err("max_attempts must be at least 1".to_string());
+        }
+        Ok(Coordinator {
+            signing_key,
+            group,
+            endpoints,
+            providers,
+            config,
+            store,
+        })
+    }
+
+    pub fn public_key(&self) -> RistrettoPoint {
+        self.signing_key.public_key()
+    }
+
+    /// Group key results are signed under
+    pub fn group_key(&self) -> RistrettoPoint {
+        self.group.public_key().0
+    }
+
+    pub fn store(&self) -> &EventStore {
+        &self.store
+    }
+
+    pub fn open_event(&mut self, event: OracleEvent) -> Result<(), String> {
+        self.store.open_event(&event)
+    }
+
+    /// Accept a signed submission from a registered provider, received at
+    /// `now`, before submissions close
+    pub fn submit(&mut self, event_id: &str, submission: EncryptedSubmission, now: u64) -> Result<(), String> {
+        let stored = self.event(event_id)?;
+        if stored.state() != EventState::Collecting || now >= submissions_close(stored.event.deadline) {
+            return Err(format!("Submissions for event {} are closed", event_id));
+        }
+        if submission.weight <= 0 {
+            return Err(format!("Provider {} has no positive weight", submission.provider_id));
+        }
+        let signed = self
+            .providers
+            .get(&submission.provider_id)
+            .is_some_and(|key| submission.verify(event_id, key));
+        if !signed {
+            return Err(format!(
+                "Submission is not signed by registered provider {}",
+                submission.provider_id
+            ));
+        }
+        if stored.submissions.iter().any(|s| s.provider_id == submission.provider_id) {
+            return Err(format!("Provider {} already submitted", submission.provider_id));
+        }
+        self.store.add_submission(event_id, &submission)
+    }
+
+    /// Close submissions and aggregate them
+    pub fn close_event(&mut self, event_id: &str, now: u64) -> Result<LweCiphertext, String> {
+        let stored = self.event(event_id)?;
+        match (stored.state(), &stored.aggregate) {
+            (EventState::Collecting, _) => {}
+            (EventState::Aggregated, Some(aggregate)) => return Ok(aggregate.clone()),
+            _ => return Err(format!("Event {} is already decided", event_id)),
+        }
+        let close = submissions_close(stored.event.deadline);
+        if now < close {
+            return Err(format!("Event {} is open until {}, now {}", event_id, close, now));
+        }
+        if stored.submissions.is_empty() {
+            return Err(format!("Event {} has no submissions", event_id));
+        }
+        let aggregate = aggregate_submissions(&stored.submissions)?;
+        self.store.set_aggregate(event_id, &aggregate)?;
+        Ok(aggregate)
+    }
+
+    /// Collect k + 2f valid responses for the aggregate, cross-check them
+    /// and have the decryptors sign the result; responses already in the
+    /// store count, so this also resumes a round interrupted by a restart
+    pub fn decrypt_event<R: RngCore + CryptoRng>(
+        &mut self,
+        event_id: &str,
+        rng: &mut R,
+    ) -> Result<SignedOracleResult, String> {
+        let stored = self.event(event_id)?;
+        let aggregate = match (stored.state(), &stored.aggregate, &stored.result) {
+            (EventState::Decided, _, Some(signed)) => return Ok(signed.clone()),
+            (EventState::Aggregated, Some(aggregate), _) => aggregate.clone(),
+            _ => return Err(format!("Event {} is still collecting", event_id)),
+        };
+        let event = stored.event.clone();
+        let submissions = stored.submissions.clone();
+
+        let needed = self.config.responses_needed();
+        let mut attempts = 0;
+        while self.event(event_id)?.partials.len() < needed && attempts < self.config.max_attempts {
+            if attempts > 0 {
+                thread::sleep(self.config.retry_backoff);
+            }
+            attempts += 1;
+            // Persisted before sending, so a restart never reuses a round number
+            let round = self.store.start_round(event_id)?;
+            let request = DecryptRequest::new(
+                &self.signing_key,
+                event_id,
+                round,
+                aggregate.clone(),
+                submissions.clone(),
+                rng,
+            );
+
+            let partials = &self.event(event_id)?.partials;
+            let missing = needed - partials.len();
+            let pending: Vec<DecryptorEndpoint> = self
+                .endpoints
+                .iter()
+                .filter(|e| !partials.contains_key(&e.id))
+                .copied()
+                .collect();
+            let timeout = self.config.round_timeout;
+            let sent = request.clone();
+            let (responses, failed) = self.fan_out(
+                &pending,
+                round,
+                missing,
+                move |endpoint| post_decrypt(endpoint.addr, &sent, timeout),
+                |endpoint, response| self.valid_response(&request, endpoint, response),
+            );
+            for failure in &failed {
+                self.store.add_failure(event_id, failure)?;
+            }
+            for response in &responses {
+                self.store.add_partial(event_id, &request, response)?;
+            }
+        }
+
+        let stored = self.event(event_id)?;
+        if stored.partials.len() < needed {
+            return Err(format!(
+                "Event {}: {} of {} valid responses after {} attempts; failures: {}",
+                event_id,
+                stored.partials.len(),
+                needed,
+                attempts,
+                stored
+                    .failures
+                    .iter()
+                    .map(|f| format!("node {} round {}: {}", f.id, f.round, f.error))
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ));
+        }
+
+        let evidence: Vec<(DecryptRequest, DecryptResponse)> = stored.partials.values().cloned().collect();
+        let decryption = cross_check(&self.config, &aggregate, &evidence)?;
+        for (request, response) in &evidence {
+            if decryption.faulty_decryptors.contains(&response.id) {
+                let failure = RoundFailure {
+                    id: response.id,
+                    round: request.round,
+                    error: ServiceError::Transport("partials disagree with the other quorums".to_string()),
+                };
+                self.store.add_failure(event_id, &failure)?;
+            }
+        }
+
+        let decryptor_set: Vec<u32> = evidence.iter().map(|(_, r)| r.id).collect();
+        let result = OracleResult {
+            event_id: event.event_id,
+            aggregate_value: decryption.value,
+            threshold: event.threshold,
+            result: decide(decryption.value, event.threshold).to_string(),
+            decryptor_set: decryptor_set.clone(),
+        };
+        // Every decryptor whose partials agreed signs, with the commitment it sent
+        let commitments = evidence
+            .iter()
+            .map(|(_, r)| r.commitments)
+            .filter(|c| !decryption.faulty_decryptors.contains(&c.id))
+            .collect();
+        let request = SignRequest::new(&self.signing_key, result.clone(), commitments, evidence.clone(), rng);
+        let round = self.event(event_id)?.round;
+        let signature = match self.collect_signature(event_id, round, &request) {
+            Ok(signature) => signature,
+            Err(e) => {
+                self.store.discard_partials(event_id)?;
+                return Err(format!("Event {}: {}; decrypting afresh on the next attempt", event_id, e));
+            }
+        };
+        let signed = SignedOracleResult {
+            signature,
+            result,
+            evidence,
+            failures: self.event(event_id)?.failures.clone(),
+        };
+        self.store.set_result(event_id, &signed)?;
+        Ok(signed)
+    }
+
+    /// Ask every signer in `request` for its share, retrying the ones that
+    /// did not answer, and aggregate them under the group key
+    fn collect_signature(&mut self, event_id: &str, round: u64, request: &SignRequest) -> Result<Signature, String> {
+        let signers: Vec<DecryptorEndpoint> = self
+            .endpoints
+            .iter()
+            .filter(|e| request.commitments.iter().any(|c| c.id == e.id))
+            .copied()
+            .collect();
+        let mut shares: Vec<SignatureShare> = Vec::new();
+        let mut attempts = 0;
+        while shares.len() < signers.len() && attempts < self.config.max_attempts {
+            if attempts > 0 {
+                thread::sleep(self.config.retry_backoff);
+            }
+            attempts += 1;
+            let pending: Vec<DecryptorEndpoint> = signers
+                .iter()
+                .filter(|e| !shares.iter().any(|s| s.id == e.id))
+                .copied()
+                .collect();
+            let timeout = self.config.round_timeout;
+            let sent = request.clone();
+            let (answered, failed) = self.fan_out(
+                &pending,
+                round,
+                pending.len(),
+                move |endpoint| post_sign(endpoint.addr, &sent, timeout),
+                |endpoint, share| share.id == endpoint.id,
+            );
+            for failure in &failed {
+                self.store.add_failure(event_id, failure)?;
+            }
+            shares.extend(answered);
+        }
+        if shares.len() < signers.len() {
+            return Err(format!(
+                "{} of {} signature shares after {} attempts",
+                shares.len(),
+                signers.len(),
+                attempts
+            ));
+        }
+        let verification_shares = signers
+            .iter()
+            .map(|e| (e.id, self.group.commitment.share_commitment(e.id)))
+            .collect();
+        aggregate(
+            &self.group_key(),
+            &request.result.canonical_bytes(),
+            &request.commitments,
+            &shares,
+            &verification_shares,
+        )
+    }
+
+    /// Finish every event whose decryption was interrupted, e.g. by a restart
+    pub fn resume_rounds<R: RngCore + CryptoRng>(&mut self, rng: &mut R) -> Vec<(String, Result<SignedOracleResult, String>)> {
+        let pending: Vec<String> = self
+            .store
+            .rounds_to_resume()
+            .iter()
+            .map(|e| e.event.event_id.clone())
+            .collect();
+        pending
+            .into_iter()
+            .map(|event_id| {
+                let outcome = self.decrypt_event(&event_id, rng);
+                (event_id, outcome)
+            })
+            .collect()
+    }
+
+    /// Close, aggregate and decrypt in one go once submissions have closed
+    pub fn run_event<R: RngCore + CryptoRng>(
+        &mut self,
+        event_id: &str,
+        now: u64,
+        rng: &mut R,
+    ) -> Result<SignedOracleResult, String> {
+        self.close_event(event_id, now)?;
+        self.decrypt_event(event_id, rng)
+    }
+
+    /// A response is valid if its decryptor signed it and it has a partial
+    /// for every quorum of the committee the decryptor belongs to
+    fn valid_response(&self, request: &DecryptRequest, endpoint: &DecryptorEndpoint, response: &DecryptResponse) -> bool {
+        let mut committee: Vec<u32> = self.endpoints.iter().map(|e| e.id).collect();
+        committee.sort_unstable();
+        response.id == endpoint.id
+            && response.verify(request, &endpoint.identity)
+            && quorums(&committee, self.config.threshold)
+                .iter()
+                .filter(|q| q.contains(&endpoint.id))
+                .all(|q| response.partial_for(q).is_some())
+    }
+
+    /// Send to `endpoints` in parallel; return once `needed` valid answers
+    /// arrived, everyone answered or the round timed out
+    fn fan_out<T, F, V>(
+        &self,
+        endpoints: &[DecryptorEndpoint],
+        round: u64,
+        needed: usize,
+        send: F,
+        valid: V,
+    ) -> (Vec<T>, Vec<RoundFailure>)
+    where
+        T: Send + 'static,
+        F: Fn(&DecryptorEndpoint) -> Result<T, ServiceError> + Send + Sync + 'static,
+        V: Fn(&DecryptorEndpoint, &T) -> bool,
+    {
+        let deadline = Instant::now() + self.config.round_timeout;
+        let (tx, rx) = mpsc::channel();
+        let send = Arc::new(send);
+        for endpoint in endpoints {
+            let (tx, send, endpoint) = (tx.clone(), Arc::clone(&send), *endpoint);
+            // Detached: a straggler finishes in the background after we stop waiting
+            thread::spawn(move || {
+                let _ = tx.send((endpoint.id, send(&endpoint)));
+            });
+        }
+        drop(tx);
+
+        let by_id: HashMap<u32, &DecryptorEndpoint> = endpoints.iter().map(|e| (e.id, e)).collect();
+        let mut valid_answers = Vec::new();
+        let mut failures = Vec::new();
+        let mut answered = Vec::new();
+        while valid_answers.len() < needed && answered.len() < endpoints.len() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let Ok((id, outcome)) = rx.recv_timeout(remaining) else {
+                break;
+            };
+            answered.push(id);
+            let error = match outcome {
+                Ok(answer) if valid(by_id[&id], &answer) => {
+                    valid_answers.push(answer);
+                    continue;
+                }
+                Ok(_) => ServiceError::Transport("response does not verify".to_string()),
+                Err(e) => e,
+            };
+            failures.push(RoundFailure { id, round, error });
+        }
+        if valid_answers.len() < needed {
+            failures.extend(
+                endpoints
+                    .iter()
+                    .filter(|e| !answered.contains(&e.id))
+                    .map(|e| RoundFailure {
+                        id: e.id,
+                        round,
+                        error: ServiceError::Timeout,
+                    }),
+            );
+        }
+        (valid_answers, failures)
+    }
+
+    fn event(&self, event_id: &str) -> Result<&StoredEvent, String> {
+        self.store
+            .event(event_id)
+            .ok_or_else(|| format!("Unknown event {}", event_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decryptor_service::{serve, DecryptorNode, DecryptorServer, NodeConfig, RateLimit, SharedLedger};
+    use crate::dkg::{run_dkg, DkgOutput};
+    use crate::event_lifecycle::{OracleLedger, PROPOSAL_WINDOW};
+    use crate::field::{Mersenne127, PrimeField, Scalar25519};
+    use crate::secret::Secret;
+    use crate::threshold_lwe::{LweFhe, LweSecretKey};
+    use crate::threshold_signature::SigningCommittee;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use std::net::TcpListener;
+
+    const EVENT: &str = "eth-above-4000";
+    const DEADLINE: u64 = 1_729_418_400;
+    const CLOSE: u64 = DEADLINE - PROPOSAL_WINDOW;
+
+    const PARAMS: LweParams = LweParams {
+        dimension: 64,
+        noise_bound: 8,
+        smudging_bits: 62,
+    };
+
+    const CONFIG: CoordinatorConfig = CoordinatorConfig {
+        threshold: 3,
+        faults: 0,
+        params: PARAMS,
+        round_timeout: Duration::from_secs(5),
+        max_attempts: 3,
+        retry_backoff: Duration::from_millis(10),
+    };
+
+    struct Setup {
+        coordinator: Coordinator,
+        servers: Vec<DecryptorServer>,
+        /// Every node's real address, including the ones hidden from the coordinator
+        endpoints: Vec<DecryptorEndpoint>,
+        providers: HashMap<String, SigningKey>,
+        backend: LweFhe,
+        ledger: SharedLedger,
+        rng: ChaCha20Rng,
+    }
+
+    /// Deterministic, so a restarted coordinator signs with the same key
+    fn coordinator_key(seed: u64) -> SigningKey {
+        SigningKey::from_secret(Secret::new(Scalar25519::from(1000 + seed)))
+    }
+
+    /// The decryptors' signing DKG; deterministic too, so a restarted
+    /// coordinator expects the same group key
+    fn signing_dkg(seed: u64) -> HashMap<u32, DkgOutput> {
+        let mut rng = ChaCha20Rng::seed_from_u64(100 + seed);
+        run_dkg(&[1, 2, 3, 4, 5], 3, &HashMap::new(), &mut rng).unwrap()
+    }
+
+    /// All the coordinator learns from the DKG
+    fn group(seed: u64) -> DkgCommitment {
+        DkgCommitment::from_output(&signing_dkg(seed)[&1])
+    }
+
+    /// Five nodes on localhost; the coordinator gets an address nobody
+    /// listens on for the nodes in `offline`
+    fn setup(offline: &[u32], seed: u64, config: CoordinatorConfig, store: EventStore) -> Setup {
+        let mut rng = ChaCha20Rng::seed_from_u64(seed);
+        let key = LweSecretKey::generate(&PARAMS, &mut rng);
+        let shares = key.deal_shares(3, 5, &mut rng).unwrap();
+        let backend = LweFhe::new(PARAMS, key).unwrap();
+        let signing_key = coordinator_key(seed);
+        let providers: HashMap<String, SigningKey> = ["A", "B", "C", "D"]
+            .iter()
+            .map(|id| (id.to_string(), SigningKey::generate(&mut rng)))
+            .collect();
+
+        // The nodes only decrypt between submission close and the deadline
+        let ledger = SharedLedger::new(OracleLedger::new("admin"), CLOSE - 3_600);
+        ledger
+            .transact(|chain, now| chain.create_event("admin", EVENT, "ETH above $4000", DEADLINE, now))
+            .unwrap();
+        ledger.set_block_time(CLOSE);
+
+        let mut signing_shares = signing_dkg(seed);
+        let identities: Vec<SigningKey> = shares.iter().map(|_| SigningKey::generate(&mut rng)).collect();
+        let peers: HashMap<u32, RistrettoPoint> =
+            shares.iter().zip(&identities).map(|(share, key)| (share.id, key.public_key())).collect();
+        let mut servers = Vec::new();
+        let mut endpoints = Vec::new();
+        for (share, identity) in shares.into_iter().zip(identities) {
+            let id = share.id;
+            let node_config = NodeConfig {
+                coordinator: signing_key.public_key(),
+                params: PARAMS,
+                peers: peers.clone(),
+                threshold: config.threshold,
+                faults: config.faults,
+                providers: registry(&providers),
+                contract: Box::new(ledger.clone()),
+                limits: RateLimit::default(),
+            };
+            let node = DecryptorNode::new(share, signing_shares.remove(&id).unwrap(), identity, node_config).unwrap();
+            let server = serve(node, "127.0.0.1:0".parse().unwrap()).unwrap();
+            endpoints.push(DecryptorEndpoint {
+                id,
+                addr: server.local_addr(),
+                identity: peers[&id],
+            });
+            servers.push(server);
+        }
+        let reachable = endpoints
+            .iter()
+            .map(|e| DecryptorEndpoint {
+                // Bind and drop, so connections are refused
+                addr: if offline.contains(&e.id) {
+                    TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap()
+                } else {
+                    e.addr
+                },
+                ..*e
+            })
+            .collect();
+        let mut coordinator = Coordinator::with_store(
+            signing_key,
+            group(seed),
+            reachable,
+            registry(&providers),
+            config,
+            store,
+        )
+        .unwrap();
+        if coordinator.store().event(EVENT).is_none() {
+            coordinator
+                .open_event(OracleEvent {
+                    event_id: EVENT.to_string(),
+                    threshold: 40_000,
+                    deadline: DEADLINE,
+                })
+                .unwrap();
+        }
+        Setup {
+            coordinator,
+            servers,
+            endpoints,
+            providers,
+            backend,
+            ledger,
+            rng,
+        }
+    }
+
+    fn registry(providers: &HashMap<String, SigningKey>) -> HashMap<String, RistrettoPoint> {
+        providers.iter().map(|(id, key)| (id.clone(), key.public_key())).collect()
+    }
+
+    fn encrypt(s: &mut Setup, provider: &str, value: i64, weight: i64, timestamp: u64) -> EncryptedSubmission {
+        let observation = ProviderSubmission {
+            provider_id: provider.to_string(),
+            quantized_value: value,
+            weight,
+            timestamp,
+        };
+        EncryptedSubmission::encrypt(&s.providers[provider], EVENT, &observation, &s.backend, &mut s.rng).unwrap()
+    }
+
+    fn submit(s: &mut Setup, values: [(&str, i64, i64); 4]) {
+        for (provider, value, weight) in values {
+            let submission = encrypt(s, provider, value, weight, CLOSE - 60);
+            s.coordinator.submit(EVENT, submission, CLOSE - 30).unwrap();
+        }
+    }
+
+    fn submit_all(s: &mut Setup) {
+        submit(s, [("A", 5000, 2), ("B", 4900, 1), ("C", 5200, 3), ("D", 5050, 2)]);
+    }
+
+    fn verifies(s: &Setup, signed: &SignedOracleResult, config: &CoordinatorConfig) -> bool {
+        let providers = registry(&s.providers);
+        signed.verify(&s.coordinator.group_key(), &s.coordinator.public_key(), &s.endpoints, &providers, config)
+    }
+
+    #[test]
+    fn test_round_end_to_end() {
+        let mut s = setup(&[], 1, CONFIG, EventStore::in_memory());
+        submit_all(&mut s);
+        let signed = s.coordinator.run_event(EVENT, CLOSE, &mut s.rng).unwrap();
+
+        // 2·5000 + 4900 + 3·5200 + 2·5050
+        assert_eq!(signed.result.aggregate_value, 40_600);
+        assert_eq!(signed.result.result, "YES");
+        assert_eq!(signed.result.decryptor_set.len(), 3);
+        assert_eq!(s.coordinator.group_key(), signing_dkg(1)[&1].public_key);
+        assert!(verifies(&s, &signed, &CONFIG));
+
+        // Proposed on chain while the contract still accepts it
+        let (result, hash) = signed.proposal();
+        s.ledger
+            .transact(|chain, now| chain.propose_result("coordinator", EVENT, result, hash, now))
+            .unwrap();
+        assert_eq!(s.servers.len(), 5);
+    }
+
+    #[test]
+    fn test_verify_recomputes_the_result_from_its_evidence() {
+        let mut s = setup(&[], 2, CONFIG, EventStore::in_memory());
+        submit_all(&mut s);
+        let signed = s.coordinator.run_event(EVENT, CLOSE, &mut s.rng).unwrap();
+        // k decryptors colluding outside the protocol; the coordinator has no share
+        let committee = SigningCommittee::from_dkg(3, signing_dkg(2)).unwrap();
+        let resign = |signed: &SignedOracleResult, rng: &mut ChaCha20Rng| {
+            let mut forged = signed.clone();
+            forged.signature = committee.sign_oracle_result(&[1, 2, 3], &forged.result, rng).unwrap();
+            forged
+        };
+
+        let mut tampered = signed.clone();
+        tampered.result.result = "NO".to_string();
+        assert!(!verifies(&s, &tampered, &CONFIG), "signature no longer matches");
+
+        // Validly signed, but not what the evidence decrypts to
+        let mut inflated = signed.clone();
+        inflated.result.aggregate_value = 1;
+        inflated.result.result = "NO".to_string();
+        let inflated = resign(&inflated, &mut s.rng);
+        assert!(!verifies(&s, &inflated, &CONFIG));
+        let mut other_event = signed.clone();
+        other_event.result.event_id = "btc-above-100k".to_string();
+        let other_event = resign(&other_event, &mut s.rng);
+        assert!(!verifies(&s, &other_event, &CONFIG));
+        let mut flipped = signed.clone();
+        flipped.result.result = "NO".to_string();
+        let flipped = resign(&flipped, &mut s.rng);
+        assert!(!verifies(&s, &flipped, &CONFIG));
+
+        // Evidence that is not the coordinator's request over the event's aggregate
+        let mut swapped = signed.clone();
+        swapped.evidence[0].0.ciphertext = LweCiphertext::try_from(&s.backend.encrypt(1)).unwrap();
+        assert!(!verifies(&s, &swapped, &CONFIG));
+        let mut dropped = signed.clone();
+        dropped.evidence.pop();
+        assert!(!verifies(&s, &dropped, &CONFIG));
+        let mut forged_partial = signed.clone();
+        forged_partial.evidence[0].1.partials[0].partial.value = Mersenne127::from_u64(7);
+        assert!(!verifies(&s, &forged_partial, &CONFIG));
+
+        let unsigned = SigningKey::generate(&mut s.rng);
+        assert!(!signed.verify(
+            &unsigned.public_key(),
+            &s.coordinator.public_key(),
+            &s.endpoints,
+            &registry(&s.providers),
+            &CONFIG
+        ));
+    }
+
+    #[test]
+    fn test_price_scale_aggregate_is_cross_checked_by_every_quorum() {
+        let config = CoordinatorConfig { faults: 1, ..CONFIG };
+        let mut s = setup(&[], 3, config, EventStore::in_memory());
+        // 8-decimal prices and a negative difference
+        submit(
+            &mut s,
+            [
+                ("A", 325_012_345_678, 2),
+                ("B", 324_998_000_000, 1),
+                ("C", 325_100_000_000, 3),
+                ("D", -1_250_000_000, 2),
+            ],
+        );
+        let signed = s.coordinator.run_event(EVENT, CLOSE, &mut s.rng).unwrap();
+        assert_eq!(signed.result.aggregate_value, 1_947_822_691_356);
+        assert_eq!(signed.result.decryptor_set.len(), 5);
+        assert!(signed.failures.is_empty());
+        assert!(verifies(&s, &signed, &config));
+        // Five responses only decode under f = 1 if every quorum is present
+        let mut fewer = signed.clone();
+        fewer.evidence[0].1.partials.pop();
+        assert!(!verifies(&s, &fewer, &config));
+    }
+
+    #[test]
+    fn test_submissions_close_before_the_deadline() {
+        let mut s = setup(&[], 4, CONFIG, EventStore::in_memory());
+        let late = encrypt(&mut s, "A", 1, 1, CLOSE);
+        assert!(s.coordinator.submit(EVENT, late.clone(), CLOSE).is_err());
+
+        let mut forged = late.clone();
+        forged.provider_id = "B".to_string();
+        assert!(s.coordinator.submit(EVENT, forged, CLOSE - 1).is_err(), "signed by A");
+        let mut unregistered = late.clone();
+        unregistered.provider_id = "E".to_string();
+        assert!(s.coordinator.submit(EVENT, unregistered, CLOSE - 1).is_err(), "unknown provider");
+        let mut unweighted = encrypt(&mut s, "C", 1, 0, CLOSE - 1);
+        assert!(s.coordinator.submit(EVENT, unweighted.clone(), CLOSE - 1).is_err(), "no weight");
+        unweighted.weight = 1;
+        assert!(s.coordinator.submit(EVENT, unweighted, CLOSE - 1).is_err(), "weight is signed");
+        assert!(s.coordinator.close_event(EVENT, CLOSE).is_err(), "no submissions");
+
+        s.coordinator.submit(EVENT, late.clone(), CLOSE - 1).unwrap();
+        assert!(s.coordinator.submit(EVENT, late, CLOSE - 1).is_err(), "duplicate provider");
+        assert!(s.coordinator.close_event(EVENT, CLOSE - 1).is_err(), "still open");
+        assert!(s.coordinator.decrypt_event(EVENT, &mut s.rng).is_err(), "not closed");
+    }
+
+    #[test]
+    fn test_partial_failures_are_tolerated_and_reported() {
+        let mut s = setup(&[2, 4], 5, CONFIG, EventStore::in_memory());
+        submit_all(&mut s);
+        let signed = s.coordinator.run_event(EVENT, CLOSE, &mut s.rng).unwrap();
+        assert_eq!(signed.result.decryptor_set, vec![1, 3, 5]);
+        assert!(verifies(&s, &signed, &CONFIG));
+        let failed: Vec<u32> = signed.failures.iter().map(|f| f.id).collect();
+        assert!(failed.contains(&2) && failed.contains(&4));
+    }
+
+    #[test]
+    fn test_gives_up_without_quorum() {
+        let mut s = setup(&[1, 2, 3], 6, CONFIG, EventStore::in_memory());
+        submit_all(&mut s);
+        let err = s.coordinator.run_event(EVENT, CLOSE, &mut s.rng).unwrap_err();
+        assert!(err.contains("2 of 3 valid responses after 3 attempts"), "{}", err);
+        // Retries asked only the nodes that had not answered
+        assert!(err.contains("node 1 round 3"), "{}", err);
+        assert!(!err.contains("node 4 round 2"), "{}", err);
+    }
+
+    #[test]
+    fn test_restart_resumes_round_from_store() {
+        let path = std::env::temp_dir().join(format!("blocksense-coordinator-{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        // Nodes 3–5 unreachable: the first coordinator gets two responses and gives up
+        let mut s = setup(&[3, 4, 5], 7, CONFIG, EventStore::open(&path).unwrap());
+        submit_all(&mut s);
+        assert!(s.coordinator.run_event(EVENT, CLOSE, &mut s.rng).is_err());
+        let Setup {
+            coordinator,
+            servers: _servers,
+            endpoints,
+            providers,
+            mut rng,
+            ..
+        } = s;
+        drop(coordinator);
+
+        let store = EventStore::open(&path).unwrap();
+        let stored = store.rounds_to_resume()[0];
+        assert_eq!(stored.submissions.len(), 4);
+        assert_eq!(stored.partials.keys().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(stored.round, 3);
+
+        let mut restarted = Coordinator::with_store(
+            coordinator_key(7),
+            group(7),
+            endpoints.clone(),
+            registry(&providers),
+            CONFIG,
+            store,
+        )
+        .unwrap();
+        let resumed = restarted.resume_rounds(&mut rng);
+        let signed = resumed[0].1.as_ref().unwrap();
+        assert_eq!(signed.result.aggregate_value, 40_600);
+        // Responses from before the restart are kept; the third comes from
+        // a round after the ones already used
+        assert_eq!(signed.result.decryptor_set[..2], [1, 2]);
+        assert_eq!(signed.result.decryptor_set.len(), 3);
+        assert_eq!(signed.evidence[2].0.round, 4);
+        assert!(signed.verify(
+            &restarted.group_key(),
+            &restarted.public_key(),
+            &endpoints,
+            &registry(&providers),
+            &CONFIG
+        ));
+
+        let reopened = EventStore::open(&path).unwrap();
+        assert_eq!(reopened.list_events(EventState::Decided).len(), 1);
+        assert_eq!(reopened.event(EVENT).unwrap().result.as_ref(), Some(signed));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
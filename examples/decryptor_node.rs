@@ -0,0 +1,159 @@
+//! Decryptor Node Daemon
+//!
+//! Loads this decryptor's encrypted LWE key share and serves
+//! `POST /decrypt` and `POST /sign` on a loopback address until killed. The
+//! dealer seals each share from `LweSecretKey::deal_shares` with
+//! `save_record`; the node uses the default `LweParams` and answers for
+//! every quorum of `--threshold` among the `--peers` that it belongs to.
+//!
+//! Result signatures use the node's own DKG key share, sealed with
+//! `save_share` and checked against the published `DkgCommitment` JSON.
+//! Responses are signed with a separate identity key, sealed with
+//! `save_record` under the same passphrase. Peers come from a JSON map of
+//! decryptor id to hex identity key, provider keys from a JSON map of
+//! provider id to hex point; event state comes from the contract state file
+//! kept current by the chain watcher (`ContractStateFile`).
+//!
+//! ```text
+//! BSFO_SHARE_PASSPHRASE=... decryptor_node \
+//!     --listen 127.0.0.1:7001 \
+//!     --share node1.lwe.bsks \
+//!     --signing-share node1.dkg.bsks --dkg-commitment dkg_commitment.json \
+//!     --peers peers.json --threshold 3 [--faults 1] \
+//!     --identity node1.identity.bsks \
+//!     --coordinator-key <hex compressed Ristretto point> \
+//!     --providers providers.json \
+//!     --contract contract_state.json \
+//!     [--max-rounds 10] [--window-secs 60]
+//! ```
+
+use blocksense_examples::decryptor_service::{serve, ContractStateFile, DecryptorNode, NodeConfig, RateLimit};
+use blocksense_examples::share_storage::{load_record, load_share, DkgCommitment, ShareEncoding};
+use blocksense_examples::threshold_lwe::{check_committee_size, LweKeyShare, LweParams};
+use blocksense_examples::threshold_signature::SigningKey;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+use zeroize::Zeroizing;
+
+const PASSPHRASE_VAR: &str = "BSFO_SHARE_PASSPHRASE";
+
+fn parse_args() -> Result<HashMap<String, String>, String> {
+    let mut args = HashMap::new();
+    let mut iter = env::args().skip(1);
+    while let Some(flag) = iter.next() {
+        let name = flag
+            .strip_prefix("--")
+            .ok_or_else(|| format!("Unexpected argument {:?}", flag))?;
+        let value = iter.next().ok_or_else(|| format!("Missing value for --{}", name))?;
+        args.insert(name.to_string(), value);
+    }
+    Ok(args)
+}
+
+fn parse_point(name: &str, hex: &str) -> Result<RistrettoPoint, String> {
+    let hex = hex.trim_start_matches("0x");
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(format!("{} must be 64 hex digits", name));
+    }
+    let bytes = (0..64)
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| format!("{} is not hex", name))?;
+    CompressedRistretto::from_slice(&bytes)
+        .ok()
+        .and_then(|c| c.decompress())
+        .ok_or_else(|| format!("{} is not a valid point", name))
+}
+
+/// `{"1": "<hex point>", ...}`, decryptor ids to identity keys
+fn load_peers(path: &str) -> Result<HashMap<u32, RistrettoPoint>, String> {
+    let json = fs::read_to_string(path).map_err(|e| format!("Cannot read peers: {}", e))?;
+    let keys: HashMap<u32, String> = serde_json::from_str(&json).map_err(|e| format!("Invalid peers file: {}", e))?;
+    keys.into_iter()
+        .map(|(id, hex)| Ok((id, parse_point(&format!("Identity key of decryptor {}", id), &hex)?)))
+        .collect()
+}
+
+/// `{"provider id": "<hex point>", ...}`
+fn load_providers(path: &str) -> Result<HashMap<String, RistrettoPoint>, String> {
+    let json = fs::read_to_string(path).map_err(|e| format!("Cannot read providers: {}", e))?;
+    let keys: HashMap<String, String> =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid providers file: {}", e))?;
+    keys.into_iter()
+        .map(|(id, hex)| {
+            let key = parse_point(&format!("Key of provider {}", id), &hex)?;
+            Ok((id, key))
+        })
+        .collect()
+}
+
+fn run() -> Result<(), String> {
+    let args = parse_args()?;
+    let arg = |name: &str| {
+        args.get(name)
+            .map(String::as_str)
+            .ok_or_else(|| format!("Missing --{}", name))
+    };
+
+    let listen: SocketAddr = arg("listen")?
+        .parse()
+        .map_err(|e| format!("Invalid --listen: {}", e))?;
+    let peers = load_peers(arg("peers")?)?;
+    let threshold: u32 = arg("threshold")?.parse().map_err(|_| "Invalid --threshold")?;
+    let faults: u32 = match args.get("faults") {
+        Some(f) => f.parse().map_err(|_| "Invalid --faults")?,
+        None => 0,
+    };
+    let group_json = fs::read_to_string(arg("dkg-commitment")?).map_err(|e| format!("Cannot read DKG commitment: {}", e))?;
+    let group = DkgCommitment::from_json(&group_json)?;
+    let coordinator = parse_point("Coordinator key", arg("coordinator-key")?)?;
+    let providers = load_providers(arg("providers")?)?;
+    let limits = RateLimit {
+        max_rounds: match args.get("max-rounds") {
+            Some(n) => n.parse().map_err(|_| "Invalid --max-rounds")?,
+            None => RateLimit::default().max_rounds,
+        },
+        window: match args.get("window-secs") {
+            Some(s) => Duration::from_secs(s.parse().map_err(|_| "Invalid --window-secs")?),
+            None => RateLimit::default().window,
+        },
+    };
+
+    let passphrase = Zeroizing::new(env::var(PASSPHRASE_VAR).map_err(|_| format!("Set {}", PASSPHRASE_VAR))?);
+    let share: LweKeyShare = load_record(Path::new(arg("share")?), passphrase.as_bytes())?;
+    check_committee_size(peers.len(), threshold)?;
+    let stored = load_share(Path::new(arg("signing-share")?), passphrase.as_bytes(), &group.vss_commitment())?;
+    let signing_share = group.key_share(&stored)?;
+    let identity: SigningKey = load_record(Path::new(arg("identity")?), passphrase.as_bytes())?;
+    println!("Decryptor {} identity key: {:?}", share.id, identity.public_key().compress());
+    let config = NodeConfig {
+        coordinator,
+        params: LweParams::default(),
+        peers,
+        threshold,
+        faults,
+        providers,
+        contract: Box::new(ContractStateFile::new(arg("contract")?)),
+        limits,
+    };
+    let node = DecryptorNode::new(share, signing_share, identity, config)?;
+
+    let server = serve(node, listen)?;
+    println!("Listening on http://{}", server.local_addr());
+    server.wait();
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("decryptor_node: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,1609 @@
+//! Decryptor Node Service
+//!
+//! Serves the decryptor API from docs/ARCHITECTURE.md §7,
+//! `POST /decrypt { ciphertext, proof, round } → { share, signature }`,
+//! over plain HTTP/1.1 on a loopback socket. Bytes travel as hex in JSON.
+//!
+//! A request names the event it belongs to and carries the event's signed
+//! provider submissions. The node answers only if:
+//! 1. `proof` is the oracle coordinator's Schnorr signature over
+//!    (event id, round, ciphertext), so only the coordinator can ask;
+//! 2. the oracle contract, read through [`ContractView`], has the event
+//!    between submission close and its deadline with no result proposed
+//!    yet (`crate::event_lifecycle::PROPOSAL_WINDOW`);
+//! 3. every submission is signed by a registered provider for this event,
+//!    and the ciphertext is the weighted sum of them that the node computes
+//!    itself, so the node never decrypts a ciphertext of the coordinator's
+//!    choosing. Once answered, an event's aggregate is pinned;
+//! 4. the round is newer than any round already answered for the event and
+//!    the node has not answered too many rounds recently.
+//!
+//! The coordinator can still leave a submission out of the first request
+//! for an event, but it cannot add, alter or replay one.
+//!
+//! The aggregate is an LWE ciphertext (`crate::threshold_lwe`). The node
+//! answers with a partial decryption for every quorum of the committee it
+//! belongs to, so the coordinator can cross-check the quorums and set aside
+//! a decryptor whose partials are wrong. It signs the request digest,
+//! partials and a fresh FROST nonce commitment with a separate identity key.
+//!
+//! `POST /sign` is the second FROST round. The node holds only its own DKG
+//! signing share and returns its signature share once it has checked, from
+//! the coordinator-signed [`SignRequest`], that the result is what the
+//! evidence decrypts to and that every signer's commitment is the one that
+//! signer sent with its partials. Nonces are used for one signing package
+//! only; the same request again gets the same share.
+
+use crate::coordinator::{aggregate_submissions, check_evidence, CoordinatorConfig, EncryptedSubmission};
+use crate::dkg::DkgOutput;
+use crate::event_lifecycle::{submissions_close, LifecycleState, OracleLedger};
+use crate::share_storage::{decode_point, decode_scalar, from_hex, from_hex32, to_hex};
+use crate::threshold_lwe::{
+    field_from_bytes, field_to_bytes, quorums, LweCiphertext, LweKeyShare, LweParams, PartialDecryption,
+};
+use crate::threshold_signature::{
+    commit, sign_share, verify_signature, OracleResult, Signature, SignatureShare, SigningCommitments, SigningKey,
+    SigningNonces,
+};
+use curve25519_dalek::ristretto::RistrettoPoint;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Largest request body the server reads; a signing request carries every
+/// response behind the result
+pub const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Read and write timeout on accepted connections
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+const REQUEST_LABEL: &[u8] = b"bsfo-decrypt-request-v1";
+const RESPONSE_LABEL: &[u8] = b"bsfo-decrypt-response-v2";
+const SIGN_REQUEST_LABEL: &[u8] = b"bsfo-sign-request-v1";
+
+/// An event as the oracle contract records it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ContractEvent {
+    pub state: LifecycleState,
+    pub deadline: u64,
+}
+
+/// Read-only view of the oracle contract; the coordinator cannot write to it
+pub trait ContractView: Send + Sync {
+    fn event(&self, event_id: &str) -> Option<ContractEvent>;
+
+    /// Timestamp of the latest block, in Unix seconds
+    fn block_time(&self) -> u64;
+}
+
+/// In-process [`OracleLedger`] and block clock, standing in for a chain client
+#[derive(Clone, Debug)]
+pub struct SharedLedger(Arc<RwLock<(OracleLedger, u64)>>);
+
+impl SharedLedger {
+    pub fn new(ledger: OracleLedger, block_time: u64) -> Self {
+        SharedLedger(Arc::new(RwLock::new((ledger, block_time))))
+    }
+
+    /// Run a transaction against the ledger at the current block time
+    pub fn transact<T>(&self, f: impl FnOnce(&mut OracleLedger, u64) -> T) -> T {
+        let mut chain = self.0.write().unwrap();
+        let now = chain.1;
+        f(&mut chain.0, now)
+    }
+
+    pub fn set_block_time(&self, block_time: u64) {
+        self.0.write().unwrap().1 = block_time;
+    }
+}
+
+impl ContractView for SharedLedger {
+    fn event(&self, event_id: &str) -> Option<ContractEvent> {
+        self.0.read().unwrap().0.event(event_id).map(|event| ContractEvent {
+            state: event.state(),
+            deadline: event.deadline,
+        })
+    }
+
+    fn block_time(&self) -> u64 {
+        self.0.read().unwrap().1
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContractSnapshot {
+    block_time: u64,
+    events: BTreeMap<String, ContractEventJson>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContractEventJson {
+    state: String,
+    deadline: u64,
+}
+
+/// A JSON snapshot of the contract,
+/// `{ "block_time": .., "events": { "<event id>": { "state": "PENDING", "deadline": .. } } }`,
+/// kept current by a chain watcher and re-read on every lookup
+#[derive(Clone, Debug)]
+pub struct ContractStateFile {
+    path: PathBuf,
+}
+
+impl ContractStateFile {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        ContractStateFile { path: path.into() }
+    }
+
+    /// Block time and events
+    pub fn load(&self) -> Result<(u64, HashMap<String, ContractEvent>), String> {
+        let json = fs::read_to_string(&self.path).map_err(|e| format!("Cannot read {}: {}", self.path.display(), e))?;
+        let snapshot: ContractSnapshot =
+            serde_json::from_str(&json).map_err(|e| format!("Invalid contract state file: {}", e))?;
+        let events = snapshot
+            .events
+            .into_iter()
+            .map(|(event_id, event)| {
+                let state = event.state.parse()?;
+                Ok((
+                    event_id,
+                    ContractEvent {
+                        state,
+                        deadline: event.deadline,
+                    },
+                ))
+            })
+            .collect::<Result<_, String>>()?;
+        Ok((snapshot.block_time, events))
+    }
+
+    /// Write the ledger as seen at `block_time`, through a temporary file so
+    /// readers never see half of it
+    pub fn write(&self, ledger: &OracleLedger, block_time: u64) -> Result<(), String> {
+        let states = [
+            LifecycleState::Pending,
+            LifecycleState::Proposed,
+            LifecycleState::Finalized,
+            LifecycleState::Disputed,
+            LifecycleState::Resolved,
+        ];
+        let events = states
+            .iter()
+            .flat_map(|&state| ledger.events_in(state))
+            .map(|event_id| {
+                let event = ledger.event(event_id).expect("listed events exist");
+                let json = ContractEventJson {
+                    state: event.state().to_string(),
+                    deadline: event.deadline,
+                };
+                (event_id.to_string(), json)
+            })
+            .collect();
+        let snapshot = ContractSnapshot { block_time, events };
+        let json = serde_json::to_string_pretty(&snapshot).map_err(|e| e.to_string())?;
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, json)
+            .and_then(|_| fs::rename(&tmp, &self.path))
+            .map_err(|e| format!("Cannot write {}: {}", self.path.display(), e))
+    }
+}
+
+/// An unreadable file reads as no events at block time 0, so the node refuses
+impl ContractView for ContractStateFile {
+    fn event(&self, event_id: &str) -> Option<ContractEvent> {
+        self.load().ok()?.1.remove(event_id)
+    }
+
+    fn block_time(&self) -> u64 {
+        self.load().map_or(0, |(block_time, _)| block_time)
+    }
+}
+
+/// At most `max_rounds` answered rounds in any `window`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub max_rounds: usize,
+    pub window: Duration,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        RateLimit {
+            max_rounds: 10,
+            window: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Why a request was refused, with its HTTP status
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ServiceError {
+    /// Malformed request (400)
+    BadRequest(String),
+    /// Coordinator proof does not verify (401)
+    Unauthorized,
+    /// Event not open for decryption on chain, or the ciphertext is not the
+    /// aggregate of its signed submissions (403)
+    NotDecryptable(String),
+    NotFound,
+    MethodNotAllowed,
+    /// Round not newer than the last one answered for the event (409)
+    StaleRound(String),
+    PayloadTooLarge,
+    /// Too many rounds in the rate-limit window (429)
+    RateLimited,
+    /// Client side: the node did not answer in time
+    Timeout,
+    /// Client side: connection failure or unparseable response
+    Transport(String),
+}
+
+impl ServiceError {
+    pub fn status(&self) -> u16 {
+        match self {
+            ServiceError::BadRequest(_) => 400,
+            ServiceError::Unauthorized => 401,
+            ServiceError::NotDecryptable(_) => 403,
+            ServiceError::NotFound => 404,
+            ServiceError::MethodNotAllowed => 405,
+            ServiceError::StaleRound(_) => 409,
+            ServiceError::PayloadTooLarge => 413,
+            ServiceError::RateLimited => 429,
+            ServiceError::Transport(_) => 502,
+            ServiceError::Timeout => 504,
+        }
+    }
+
+    /// Rebuild an error from its status and message
+    pub fn from_status(status: u16, message: String) -> Self {
+        match status {
+            401 => ServiceError::Unauthorized,
+            403 => ServiceError::NotDecryptable(message),
+            404 => ServiceError::NotFound,
+            405 => ServiceError::MethodNotAllowed,
+            409 => ServiceError::StaleRound(message),
+            413 => ServiceError::PayloadTooLarge,
+            429 => ServiceError::RateLimited,
+            400 => ServiceError::BadRequest(message),
+            502 => ServiceError::Transport(message),
+            504 => ServiceError::Timeout,
+            _ => ServiceError::Transport(format!("HTTP {}: {}", status, message)),
+        }
+    }
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceError::Unauthorized => f.write_str("coordinator proof does not verify"),
+            ServiceError::BadRequest(reason)
+            | ServiceError::NotDecryptable(reason)
+            | ServiceError::StaleRound(reason)
+            | ServiceError::Transport(reason) => f.write_str(reason),
+            ServiceError::NotFound => f.write_str("not found"),
+            ServiceError::MethodNotAllowed => f.write_str("method not allowed"),
+            ServiceError::PayloadTooLarge => write!(f, "body exceeds {} bytes", MAX_BODY_BYTES),
+            ServiceError::RateLimited => f.write_str("too many rounds, retry later"),
+            ServiceError::Timeout => f.write_str("decryptor timed out"),
+        }
+    }
+}
+
+/// A coordinator's request for a partial decryption of an event's aggregate
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "DecryptRequestJson", into = "DecryptRequestJson")]
+pub struct DecryptRequest {
+    pub event_id: String,
+    pub round: u64,
+    pub ciphertext: LweCiphertext,
+    /// The signed submissions `ciphertext` aggregates
+    pub submissions: Vec<EncryptedSubmission>,
+    /// Coordinator's signature over [`DecryptRequest::message`]
+    pub proof: Signature,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DecryptRequestJson {
+    event_id: String,
+    round: u64,
+    ciphertext: String,
+    submissions: Vec<EncryptedSubmission>,
+    proof: String,
+}
+
+impl DecryptRequest {
+    /// Sign a request as the coordinator
+    pub fn new<R: rand::RngCore + rand::CryptoRng>(
+        coordinator: &SigningKey,
+        event_id: &str,
+        round: u64,
+        ciphertext: LweCiphertext,
+        submissions: Vec<EncryptedSubmission>,
+        rng: &mut R,
+    ) -> Self {
+        let proof = coordinator.sign(&Self::message(event_id, round, &ciphertext), rng);
+        DecryptRequest {
+            event_id: event_id.to_string(),
+            round,
+            ciphertext,
+            submissions,
+            proof,
+        }
+    }
+
+    /// label ‖ len ‖ event id ‖ round ‖ len ‖ ciphertext, the signed bytes
+    pub fn message(event_id: &str, round: u64, ciphertext: &LweCiphertext) -> Vec<u8> {
+        let ciphertext = ciphertext.to_bytes();
+        let mut message = REQUEST_LABEL.to_vec();
+        message.extend_from_slice(&(event_id.len() as u32).to_be_bytes());
+        message.extend_from_slice(event_id.as_bytes());
+        message.extend_from_slice(&round.to_be_bytes());
+        message.extend_from_slice(&(ciphertext.len() as u32).to_be_bytes());
+        message.extend_from_slice(&ciphertext);
+        message
+    }
+
+    pub fn digest(&self) -> [u8; 32] {
+        Sha256::digest(Self::message(&self.event_id, self.round, &self.ciphertext)).into()
+    }
+
+    pub fn verify(&self, coordinator: &RistrettoPoint) -> bool {
+        verify_signature(
+            coordinator,
+            &Self::message(&self.event_id, self.round, &self.ciphertext),
+            &self.proof,
+        )
+    }
+
+    /// Check every submission against the provider registry and the
+    /// ciphertext against their aggregate
+    pub fn check_aggregate(&self, providers: &HashMap<String, RistrettoPoint>) -> Result<(), String> {
+        let mut seen = HashSet::new();
+        for submission in &self.submissions {
+            if !seen.insert(submission.provider_id.as_str()) {
+                return Err(format!("provider {} submitted twice", submission.provider_id));
+            }
+            let signed = providers
+                .get(&submission.provider_id)
+                .is_some_and(|key| submission.verify(&self.event_id, key));
+            if !signed {
+                return Err(format!(
+                    "submission from {} is not signed by a registered provider for event {}",
+                    submission.provider_id, self.event_id
+                ));
+            }
+        }
+        if aggregate_submissions(&self.submissions)? != self.ciphertext {
+            return Err(format!(
+                "ciphertext is not the aggregate of the submissions to event {}",
+                self.event_id
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("request serializes")
+    }
+
+    pub fn from_json(json: &[u8]) -> Result<Self, String> {
+        serde_json::from_slice(json).map_err(|e| e.to_string())
+    }
+}
+
+impl From<DecryptRequest> for DecryptRequestJson {
+    fn from(request: DecryptRequest) -> Self {
+        DecryptRequestJson {
+            ciphertext: to_hex(&request.ciphertext.to_bytes()),
+            proof: to_hex(&request.proof.to_bytes()),
+            event_id: request.event_id,
+            round: request.round,
+            submissions: request.submissions,
+        }
+    }
+}
+
+impl TryFrom<DecryptRequestJson> for DecryptRequest {
+    type Error = String;
+
+    fn try_from(wire: DecryptRequestJson) -> Result<Self, String> {
+        Ok(DecryptRequest {
+            ciphertext: LweCiphertext::from_bytes(&from_hex(&wire.ciphertext)?)?,
+            proof: signature_from_hex(&wire.proof)?,
+            event_id: wire.event_id,
+            round: wire.round,
+            submissions: wire.submissions,
+        })
+    }
+}
+
+/// A decryptor's partial decryption for one quorum
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuorumPartial {
+    /// The quorum the Lagrange coefficient was taken for, in committee order
+    pub quorum: Vec<u32>,
+    pub partial: PartialDecryption,
+}
+
+/// A decryptor's answer: its partials for every quorum it belongs to and
+/// its round-1 FROST commitment, signed with its identity key
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "DecryptResponseJson", into = "DecryptResponseJson")]
+pub struct DecryptResponse {
+    pub id: u32,
+    pub partials: Vec<QuorumPartial>,
+    pub commitments: SigningCommitments,
+    pub signature: Signature,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DecryptResponseJson {
+    id: u32,
+    partials: Vec<QuorumPartialJson>,
+    commitments: CommitmentsJson,
+    signature: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CommitmentsJson {
+    id: u32,
+    hiding: String,
+    binding: String,
+}
+
+impl From<SigningCommitments> for CommitmentsJson {
+    fn from(commitments: SigningCommitments) -> Self {
+        CommitmentsJson {
+            id: commitments.id,
+            hiding: to_hex(commitments.hiding.compress().as_bytes()),
+            binding: to_hex(commitments.binding.compress().as_bytes()),
+        }
+    }
+}
+
+impl TryFrom<CommitmentsJson> for SigningCommitments {
+    type Error = String;
+
+    fn try_from(wire: CommitmentsJson) -> Result<Self, String> {
+        Ok(SigningCommitments {
+            id: wire.id,
+            hiding: decode_point(from_hex32(&wire.hiding)?)?,
+            binding: decode_point(from_hex32(&wire.binding)?)?,
+        })
+    }
+}
+
+/// id ‖ D ‖ E, compressed
+fn commitments_bytes(commitments: &SigningCommitments) -> Vec<u8> {
+    let mut bytes = commitments.id.to_be_bytes().to_vec();
+    bytes.extend_from_slice(commitments.hiding.compress().as_bytes());
+    bytes.extend_from_slice(commitments.binding.compress().as_bytes());
+    bytes
+}
+
+#[derive(Serialize, Deserialize)]
+struct QuorumPartialJson {
+    quorum: Vec<u32>,
+    value: String,
+}
+
+impl DecryptResponse {
+    /// label ‖ request digest ‖ id ‖ count ‖ per partial: len ‖ quorum ids ‖
+    /// value ‖ commitments, the signed bytes
+    fn message(
+        request_digest: &[u8; 32],
+        id: u32,
+        partials: &[QuorumPartial],
+        commitments: &SigningCommitments,
+    ) -> Vec<u8> {
+        let mut message = RESPONSE_LABEL.to_vec();
+        message.extend_from_slice(request_digest);
+        message.extend_from_slice(&id.to_be_bytes());
+        message.extend_from_slice(&(partials.len() as u32).to_be_bytes());
+        for entry in partials {
+            message.extend_from_slice(&(entry.quorum.len() as u32).to_be_bytes());
+            for member in &entry.quorum {
+                message.extend_from_slice(&member.to_be_bytes());
+            }
+            message.extend_from_slice(&field_to_bytes(entry.partial.value));
+        }
+        message.extend_from_slice(&commitments_bytes(commitments));
+        message
+    }
+
+    /// Check the signature under decryptor `id`'s identity key and that
+    /// every partial and the commitment are its own
+    ///
+    /// LWE partials carry no proof of correctness; wrong ones are caught by
+    /// cross-checking quorums when they are combined.
+    pub fn verify(&self, request: &DecryptRequest, identity: &RistrettoPoint) -> bool {
+        self.commitments.id == self.id
+            && self
+                .partials
+                .iter()
+                .all(|p| p.partial.id == self.id && p.quorum.contains(&self.id))
+            && verify_signature(
+                identity,
+                &Self::message(&request.digest(), self.id, &self.partials, &self.commitments),
+                &self.signature,
+            )
+    }
+
+    /// The partial for `quorum`, if the decryptor sent one
+    pub fn partial_for(&self, quorum: &[u32]) -> Option<PartialDecryption> {
+        self.partials.iter().find(|p| p.quorum == quorum).map(|p| p.partial)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("response serializes")
+    }
+
+    pub fn from_json(json: &[u8]) -> Result<Self, String> {
+        serde_json::from_slice(json).map_err(|e| e.to_string())
+    }
+}
+
+impl From<DecryptResponse> for DecryptResponseJson {
+    fn from(response: DecryptResponse) -> Self {
+        DecryptResponseJson {
+            id: response.id,
+            partials: response
+                .partials
+                .into_iter()
+                .map(|p| QuorumPartialJson {
+                    quorum: p.quorum,
+                    value: to_hex(&field_to_bytes(p.partial.value)),
+                })
+                .collect(),
+            commitments: response.commitments.into(),
+            signature: to_hex(&response.signature.to_bytes()),
+        }
+    }
+}
+
+impl TryFrom<DecryptResponseJson> for DecryptResponse {
+    type Error = String;
+
+    fn try_from(wire: DecryptResponseJson) -> Result<Self, String> {
+        let id = wire.id;
+        let partials = wire
+            .partials
+            .into_iter()
+            .map(|p| {
+                let bytes: [u8; 16] = from_hex(&p.value)?
+                    .try_into()
+                    .map_err(|_| "partial decryption must be 16 bytes".to_string())?;
+                Ok(QuorumPartial {
+                    quorum: p.quorum,
+                    partial: PartialDecryption {
+                        id,
+                        value: field_from_bytes(bytes)?,
+                    },
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(DecryptResponse {
+            id,
+            partials,
+            commitments: wire.commitments.try_into()?,
+            signature: signature_from_hex(&wire.signature)?,
+        })
+    }
+}
+
+/// The coordinator's request for signature shares over a result, with the
+/// commitments of the signers and the evidence the result follows from
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "SignRequestJson", into = "SignRequestJson")]
+pub struct SignRequest {
+    pub result: OracleResult,
+    /// One per signer, each from that signer's response in `evidence`
+    pub commitments: Vec<SigningCommitments>,
+    pub evidence: Vec<(DecryptRequest, DecryptResponse)>,
+    /// Coordinator's signature over the result and the commitments
+    pub proof: Signature,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SignRequestJson {
+    result: OracleResult,
+    commitments: Vec<CommitmentsJson>,
+    evidence: Vec<(DecryptRequest, DecryptResponse)>,
+    proof: String,
+}
+
+impl SignRequest {
+    pub fn new<R: rand::RngCore + rand::CryptoRng>(
+        coordinator: &SigningKey,
+        result: OracleResult,
+        commitments: Vec<SigningCommitments>,
+        evidence: Vec<(DecryptRequest, DecryptResponse)>,
+        rng: &mut R,
+    ) -> Self {
+        let proof = coordinator.sign(&Self::message(&result, &commitments), rng);
+        SignRequest {
+            result,
+            commitments,
+            evidence,
+            proof,
+        }
+    }
+
+    /// label ‖ len ‖ canonical result ‖ count ‖ commitments, the signed bytes
+    fn message(result: &OracleResult, commitments: &[SigningCommitments]) -> Vec<u8> {
+        let result = result.canonical_bytes();
+        let mut message = SIGN_REQUEST_LABEL.to_vec();
+        message.extend_from_slice(&(result.len() as u32).to_be_bytes());
+        message.extend_from_slice(&result);
+        message.extend_from_slice(&(commitments.len() as u32).to_be_bytes());
+        for c in commitments {
+            message.extend_from_slice(&commitments_bytes(c));
+        }
+        message
+    }
+
+    /// Identifies the signing package; a node signs one package per nonce
+    pub fn digest(&self) -> [u8; 32] {
+        Sha256::digest(Self::message(&self.result, &self.commitments)).into()
+    }
+
+    pub fn verify(&self, coordinator: &RistrettoPoint) -> bool {
+        verify_signature(coordinator, &Self::message(&self.result, &self.commitments), &self.proof)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("request serializes")
+    }
+
+    pub fn from_json(json: &[u8]) -> Result<Self, String> {
+        serde_json::from_slice(json).map_err(|e| e.to_string())
+    }
+}
+
+impl From<SignRequest> for SignRequestJson {
+    fn from(request: SignRequest) -> Self {
+        SignRequestJson {
+            result: request.result,
+            commitments: request.commitments.into_iter().map(CommitmentsJson::from).collect(),
+            evidence: request.evidence,
+            proof: to_hex(&request.proof.to_bytes()),
+        }
+    }
+}
+
+impl TryFrom<SignRequestJson> for SignRequest {
+    type Error = String;
+
+    fn try_from(wire: SignRequestJson) -> Result<Self, String> {
+        Ok(SignRequest {
+            result: wire.result,
+            commitments: wire
+                .commitments
+                .into_iter()
+                .map(SigningCommitments::try_from)
+                .collect::<Result<_, String>>()?,
+            evidence: wire.evidence,
+            proof: signature_from_hex(&wire.proof)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SignatureShareJson {
+    id: u32,
+    z: String,
+}
+
+fn share_to_json(share: &SignatureShare) -> String {
+    let wire = SignatureShareJson {
+        id: share.id,
+        z: to_hex(&share.z.to_bytes()),
+    };
+    serde_json::to_string(&wire).expect("share serializes")
+}
+
+fn share_from_json(json: &[u8]) -> Result<SignatureShare, String> {
+    let wire: SignatureShareJson = serde_json::from_slice(json).map_err(|e| e.to_string())?;
+    Ok(SignatureShare {
+        id: wire.id,
+        z: decode_scalar(from_hex32(&wire.z)?)?,
+    })
+}
+
+/// 64-byte Schnorr signature from hex
+pub(crate) fn signature_from_hex(hex: &str) -> Result<Signature, String> {
+    let bytes: [u8; 64] = from_hex(hex)?
+        .try_into()
+        .map_err(|_| "signature must be 64 bytes".to_string())?;
+    Signature::from_bytes(&bytes)
+}
+
+/// A node's FROST round for an event: nonces committed to in its latest
+/// response, or the share it gave for one signing package
+enum SigningRound {
+    Committed(SigningCommitments, SigningNonces),
+    Signed(SigningCommitments, [u8; 32], SignatureShare),
+}
+
+impl SigningRound {
+    /// The share already given for `package` under `own`, if that is what
+    /// this round did
+    fn answered(&self, own: &SigningCommitments, package: &[u8; 32]) -> Result<SignatureShare, ServiceError> {
+        match self {
+            SigningRound::Signed(commitments, signed, share) if commitments == own && signed == package => Ok(*share),
+            SigningRound::Signed(commitments, ..) if commitments == own => Err(ServiceError::NotDecryptable(
+                "commitment was already used for a different signing package".to_string(),
+            )),
+            _ => Err(ServiceError::NotDecryptable(
+                "commitment is not from this node's latest response".to_string(),
+            )),
+        }
+    }
+}
+
+/// Answered rounds: the latest per event, the aggregate each event was
+/// answered for, the recent answer times and the signing round per event
+#[derive(Default)]
+struct RoundState {
+    last_round: HashMap<String, u64>,
+    pinned: HashMap<String, [u8; 32]>,
+    answered: VecDeque<Instant>,
+    signing: HashMap<String, SigningRound>,
+}
+
+/// What a node checks requests against
+pub struct NodeConfig {
+    /// Coordinator identity key; only it may request decryptions
+    pub coordinator: RistrettoPoint,
+    pub params: LweParams,
+    /// Identity keys of every decryptor holding an LWE key share, by id
+    pub peers: HashMap<u32, RistrettoPoint>,
+    /// Key shares needed to decrypt (k)
+    pub threshold: u32,
+    /// Decryptors with wrong partials the cross-check tolerates (f)
+    pub faults: u32,
+    /// Registered provider keys by provider id
+    pub providers: HashMap<String, RistrettoPoint>,
+    /// The oracle contract, the source of event state
+    pub contract: Box<dyn ContractView>,
+    pub limits: RateLimit,
+}
+
+/// One decryptor: its LWE key share, its own FROST signing share, its
+/// identity key and the checks it applies to requests
+pub struct DecryptorNode {
+    pub id: u32,
+    key_share: LweKeyShare,
+    signing_share: DkgOutput,
+    identity: SigningKey,
+    /// Ids in `config.peers`, ascending, as the coordinator lists quorums
+    committee: Vec<u32>,
+    config: NodeConfig,
+    state: Mutex<RoundState>,
+}
+
+impl DecryptorNode {
+    pub fn new(
+        key_share: LweKeyShare,
+        signing_share: DkgOutput,
+        identity: SigningKey,
+        config: NodeConfig,
+    ) -> Result<Self, String> {
+        let id = key_share.id;
+        if signing_share.id != id {
+            return Err(format!("Signing share {} does not belong to decryptor {}", signing_share.id, id));
+        }
+        if config.peers.get(&id) != Some(&identity.public_key()) {
+            return Err(format!("Decryptor {} is not in the committee under its identity key", id));
+        }
+        let mut committee: Vec<u32> = config.peers.keys().copied().collect();
+        committee.sort_unstable();
+        Ok(DecryptorNode {
+            id,
+            key_share,
+            signing_share,
+            identity,
+            committee,
+            config,
+            state: Mutex::new(RoundState::default()),
+        })
+    }
+
+    /// Public identity key, under which responses are signed
+    pub fn identity_key(&self) -> RistrettoPoint {
+        self.identity.public_key()
+    }
+
+    /// Check a request and answer it with a signed partial decryption
+    pub fn handle(&self, request: &DecryptRequest) -> Result<DecryptResponse, ServiceError> {
+        if !request.verify(&self.config.coordinator) {
+            return Err(ServiceError::Unauthorized);
+        }
+        self.check_event(&request.event_id)?;
+        request
+            .check_aggregate(&self.config.providers)
+            .map_err(ServiceError::NotDecryptable)?;
+
+        {
+            let mut state = self.state.lock().unwrap();
+            let digest: [u8; 32] = Sha256::digest(request.ciphertext.to_bytes()).into();
+            if state.pinned.get(&request.event_id).is_some_and(|pinned| *pinned != digest) {
+                return Err(ServiceError::NotDecryptable(format!(
+                    "event {} was already decrypted with a different aggregate",
+                    request.event_id
+                )));
+            }
+            if let Some(&last) = state.last_round.get(&request.event_id) {
+                if request.round <= last {
+                    return Err(ServiceError::StaleRound(format!(
+                        "round {} of event {} is not after round {}",
+                        request.round, request.event_id, last
+                    )));
+                }
+            }
+            let now = Instant::now();
+            let window = self.config.limits.window;
+            while state.answered.front().is_some_and(|&t| now.duration_since(t) >= window) {
+                state.answered.pop_front();
+            }
+            if state.answered.len() >= self.config.limits.max_rounds {
+                return Err(ServiceError::RateLimited);
+            }
+            state.answered.push_back(now);
+            state.last_round.insert(request.event_id.clone(), request.round);
+            state.pinned.insert(request.event_id.clone(), digest);
+        }
+
+        let mut rng = rand::thread_rng();
+        let partials = quorums(&self.committee, self.config.threshold)
+            .into_iter()
+            .filter(|quorum| quorum.contains(&self.id))
+            .map(|quorum| {
+                let partial = self
+                    .key_share
+                    .partial_decrypt(&self.config.params, &request.ciphertext, &quorum, &mut rng)?;
+                Ok(QuorumPartial { quorum, partial })
+            })
+            .collect::<Result<Vec<_>, String>>()
+            .map_err(ServiceError::NotDecryptable)?;
+        // Fresh nonces per answer; an older commitment for the event is dropped
+        let (nonces, commitments) = commit(self.id, &mut rng);
+        let signature = self.identity.sign(
+            &DecryptResponse::message(&request.digest(), self.id, &partials, &commitments),
+            &mut rng,
+        );
+        self.state
+            .lock()
+            .unwrap()
+            .signing
+            .insert(request.event_id.clone(), SigningRound::Committed(commitments, nonces));
+        Ok(DecryptResponse {
+            id: self.id,
+            partials,
+            commitments,
+            signature,
+        })
+    }
+
+    /// Check a signing request and answer it with this node's signature
+    /// share, spending the nonces behind its commitment
+    pub fn handle_sign(&self, request: &SignRequest) -> Result<SignatureShare, ServiceError> {
+        if !request.verify(&self.config.coordinator) {
+            return Err(ServiceError::Unauthorized);
+        }
+        let event_id = &request.result.event_id;
+        self.check_event(event_id)?;
+        let own = request
+            .commitments
+            .iter()
+            .find(|c| c.id == self.id)
+            .copied()
+            .ok_or_else(|| ServiceError::NotDecryptable(format!("decryptor {} is not a signer", self.id)))?;
+        let package = request.digest();
+        match self.state.lock().unwrap().signing.get(event_id) {
+            Some(SigningRound::Committed(commitments, _)) if *commitments == own => {}
+            Some(round) => return round.answered(&own, &package),
+            None => {
+                return Err(ServiceError::NotDecryptable(format!(
+                    "no commitment outstanding for event {}",
+                    event_id
+                )))
+            }
+        }
+        self.check_signers(request).map_err(ServiceError::NotDecryptable)?;
+
+        let mut state = self.state.lock().unwrap();
+        match state.signing.remove(event_id) {
+            Some(SigningRound::Committed(commitments, nonces)) if commitments == own => {
+                let share = sign_share(
+                    &self.signing_share,
+                    nonces,
+                    &request.result.canonical_bytes(),
+                    &request.commitments,
+                )
+                .map_err(ServiceError::NotDecryptable)?;
+                state
+                    .signing
+                    .insert(event_id.clone(), SigningRound::Signed(own, package, share));
+                Ok(share)
+            }
+            Some(round) => {
+                let answered = round.answered(&own, &package);
+                state.signing.insert(event_id.clone(), round);
+                answered
+            }
+            None => Err(ServiceError::NotDecryptable(format!(
+                "no commitment outstanding for event {}",
+                event_id
+            ))),
+        }
+    }
+
+    /// The result must be what the evidence decrypts to, and the signers at
+    /// least k decryptors whose partials agreed, each with the commitment
+    /// from its own signed response
+    fn check_signers(&self, request: &SignRequest) -> Result<(), String> {
+        let config = CoordinatorConfig {
+            threshold: self.config.threshold,
+            faults: self.config.faults,
+            params: self.config.params,
+            ..CoordinatorConfig::default()
+        };
+        let decryption = check_evidence(
+            &request.result,
+            &request.evidence,
+            &self.config.coordinator,
+            &self.config.peers,
+            &self.config.providers,
+            &config,
+        )?;
+        let signers: HashSet<u32> = request.commitments.iter().map(|c| c.id).collect();
+        if signers.len() != request.commitments.len() || signers.len() < self.config.threshold as usize {
+            return Err(format!(
+                "Need {} distinct signers, got {:?}",
+                self.config.threshold,
+                request.commitments.iter().map(|c| c.id).collect::<Vec<_>>()
+            ));
+        }
+        for c in &request.commitments {
+            if decryption.faulty_decryptors.contains(&c.id) {
+                return Err(format!("Signer {} sent partials that disagree with the other quorums", c.id));
+            }
+            let sent = request.evidence.iter().find(|(_, r)| r.id == c.id).map(|(_, r)| r.commitments);
+            if sent != Some(*c) {
+                return Err(format!("Commitment of signer {} is not the one in its response", c.id));
+            }
+        }
+        Ok(())
+    }
+
+    /// The contract must have the event closed to submissions and still
+    /// open for a proposal
+    fn check_event(&self, event_id: &str) -> Result<(), ServiceError> {
+        let refuse = |reason: String| Err(ServiceError::NotDecryptable(reason));
+        let Some(event) = self.config.contract.event(event_id) else {
+            return refuse(format!("event {} does not exist on chain", event_id));
+        };
+        if event.state != LifecycleState::Pending {
+            return refuse(format!("event {} is already {} on chain", event_id, event.state));
+        }
+        let now = self.config.contract.block_time();
+        let close = submissions_close(event.deadline);
+        if now < close {
+            return refuse(format!(
+                "event {} takes submissions until {}, block time is {}",
+                event_id, close, now
+            ));
+        }
+        if now > event.deadline {
+            return refuse(format!(
+                "event {} can no longer take a result: deadline {}, block time is {}",
+                event_id, event.deadline, now
+            ));
+        }
+        Ok(())
+    }
+
+    /// Route one HTTP request to a status and JSON body
+    fn route(&self, method: &str, path: &str, body: &[u8]) -> (u16, String) {
+        let result = match (method, path) {
+            ("POST", "/decrypt") => DecryptRequest::from_json(body)
+                .map_err(ServiceError::BadRequest)
+                .and_then(|request| self.handle(&request))
+                .map(|response| response.to_json()),
+            ("POST", "/sign") => SignRequest::from_json(body)
+                .map_err(ServiceError::BadRequest)
+                .and_then(|request| self.handle_sign(&request))
+                .map(|share| share_to_json(&share)),
+            ("GET", "/health") => Ok(serde_json::json!({ "id": self.id, "status": "ok" }).to_string()),
+            (_, "/decrypt") | (_, "/sign") | (_, "/health") => Err(ServiceError::MethodNotAllowed),
+            _ => Err(ServiceError::NotFound),
+        };
+        match result {
+            Ok(json) => (200, json),
+            Err(e) => (e.status(), error_body(&e)),
+        }
+    }
+}
+
+impl fmt::Debug for DecryptorNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecryptorNode")
+            .field("id", &self.id)
+            .field("key_share", &self.key_share)
+            .field("signing_share", &self.signing_share)
+            .field("identity", &self.identity)
+            .field("coordinator", &self.config.coordinator.compress())
+            .field("committee", &self.committee)
+            .field("threshold", &self.config.threshold)
+            .field("faults", &self.config.faults)
+            .field("limits", &self.config.limits)
+            .finish()
+    }
+}
+
+fn error_body(error: &ServiceError) -> String {
+    serde_json::json!({ "error": error.to_string() }).to_string()
+}
+
+/// A running node; dropping it stops the server
+#[derive(Debug)]
+pub struct DecryptorServer {
+    addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl DecryptorServer {
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Block until the server stops
+    pub fn wait(mut self) {
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+
+    /// Stop accepting connections and join the accept loop
+    pub fn shutdown(self) {
+        drop(self);
+    }
+}
+
+impl Drop for DecryptorServer {
+    fn drop(&mut self) {
+        if let Some(thread) = self.thread.take() {
+            self.stop.store(true, Ordering::SeqCst);
+            // Wake the blocking accept so the loop sees the flag
+            let _ = TcpStream::connect_timeout(&self.addr, IO_TIMEOUT);
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Serve `node` on a loopback address; port 0 picks a free port
+pub fn serve(node: DecryptorNode, addr: SocketAddr) -> Result<DecryptorServer, String> {
+    if !addr.ip().is_loopback() {
+        return Err(format!("Refusing to listen on non-loopback address {}", addr));
+    }
+    let listener = TcpListener::bind(addr).map_err(|e| format!("Cannot bind {}: {}", addr, e))?;
+    let addr = listener.local_addr().map_err(|e| e.to_string())?;
+    let stop = Arc::new(AtomicBool::new(false));
+    let node = Arc::new(node);
+
+    let flag = Arc::clone(&stop);
+    let thread = thread::spawn(move || {
+        for stream in listener.incoming() {
+            if flag.load(Ordering::SeqCst) {
+                break;
+            }
+            let Ok(stream) = stream else { continue };
+            let node = Arc::clone(&node);
+            thread::spawn(move || {
+                let _ = handle_connection(&node, stream);
+            });
+        }
+    });
+
+    Ok(DecryptorServer {
+        addr,
+        stop,
+        thread: Some(thread),
+    })
+}
+
+fn handle_connection(node: &DecryptorNode, stream: TcpStream) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let (status, body) = match read_request(&mut reader) {
+        Ok((method, path, body)) => node.route(&method, &path, &body),
+        Err(e) => (e.status(), error_body(&e)),
+    };
+    write_response(&stream, status, &body)
+}
+
+/// Request line, headers and a Content-Length body
+fn read_request<R: BufRead>(reader: &mut R) -> Result<(String, String, Vec<u8>), ServiceError> {
+    let bad = |reason: &str| ServiceError::BadRequest(reason.to_string());
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|_| bad("unreadable request line"))?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(bad("malformed request line"));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut content_length = 0usize;
+    loop {
+        line.clear();
+        reader.read_line(&mut line).map_err(|_| bad("unreadable header"))?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().map_err(|_| bad("invalid Content-Length"))?;
+            }
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err(ServiceError::PayloadTooLarge);
+    }
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body).map_err(|_| bad("truncated body"))?;
+    Ok((method, path, body))
+}
+
+fn write_response(mut stream: &TcpStream, status: u16, body: &str) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        429 => "Too Many Requests",
+        _ => "Error",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+/// Send a request to the node at `addr` and parse its answer
+pub fn post_decrypt(addr: SocketAddr, request: &DecryptRequest, timeout: Duration) -> Result<DecryptResponse, ServiceError> {
+    let body = post(addr, "/decrypt", &request.to_json(), timeout)?;
+    DecryptResponse::from_json(body.as_bytes()).map_err(ServiceError::Transport)
+}
+
+/// Ask the node at `addr` for its signature share
+pub fn post_sign(addr: SocketAddr, request: &SignRequest, timeout: Duration) -> Result<SignatureShare, ServiceError> {
+    let body = post(addr, "/sign", &request.to_json(), timeout)?;
+    share_from_json(body.as_bytes()).map_err(ServiceError::Transport)
+}
+
+/// POST a JSON body and return the body of a 200 answer
+fn post(addr: SocketAddr, path: &str, body: &str, timeout: Duration) -> Result<String, ServiceError> {
+    let io_error = |e: std::io::Error| match e.kind() {
+        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut => ServiceError::Timeout,
+        _ => ServiceError::Transport(format!("{}: {}", addr, e)),
+    };
+    let stream = TcpStream::connect_timeout(&addr, timeout).map_err(io_error)?;
+    stream.set_read_timeout(Some(timeout)).map_err(io_error)?;
+    stream.set_write_timeout(Some(timeout)).map_err(io_error)?;
+
+    write!(
+        &stream,
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        path,
+        addr,
+        body.len(),
+        body
+    )
+    .map_err(io_error)?;
+
+    let mut response = Vec::new();
+    (&stream).read_to_end(&mut response).map_err(io_error)?;
+    let text = String::from_utf8_lossy(&response);
+    let (head, body) = text
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| ServiceError::Transport("malformed HTTP response".to_string()))?;
+    let status: u16 = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| ServiceError::Transport("malformed status line".to_string()))?;
+
+    if status == 200 {
+        Ok(body.to_string())
+    } else {
+        let message = serde_json::from_str::<serde_json::Value>(body)
+            .ok()
+            .and_then(|v| v["error"].as_str().map(str::to_string))
+            .unwrap_or_default();
+        Err(ServiceError::from_status(status, message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregation::ProviderSubmission;
+    use crate::dkg::run_dkg;
+    use crate::event_lifecycle::PROPOSAL_WINDOW;
+    use crate::fhe_module::FheBackend;
+    use crate::share_storage::DkgCommitment;
+    use crate::threshold_lwe::{combine_partial_decryptions, LweFhe, LweSecretKey};
+    use crate::threshold_signature::{aggregate, verify_oracle_result};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    const EVENT: &str = "eth-above-4000";
+    const DEADLINE: u64 = 10_000;
+    const CLOSE: u64 = DEADLINE - PROPOSAL_WINDOW;
+
+    fn params() -> LweParams {
+        LweParams {
+            dimension: 64,
+            ..LweParams::default()
+        }
+    }
+
+    struct Committee {
+        servers: Vec<DecryptorServer>,
+        identities: HashMap<u32, RistrettoPoint>,
+        /// Public result of the signing DKG; each node holds its own share
+        group: DkgCommitment,
+        coordinator: SigningKey,
+        backend: LweFhe,
+        ledger: SharedLedger,
+        submissions: Vec<EncryptedSubmission>,
+        ciphertext: LweCiphertext,
+        rng: ChaCha20Rng,
+    }
+
+    fn observation(provider_id: &str, value: i64, weight: i64) -> ProviderSubmission {
+        ProviderSubmission {
+            provider_id: provider_id.to_string(),
+            quantized_value: value,
+            weight,
+            timestamp: CLOSE - 10,
+        }
+    }
+
+    fn committee(limits: RateLimit, seed: u64) -> Committee {
+        let mut rng = ChaCha20Rng::seed_from_u64(seed);
+        let key = LweSecretKey::generate(&params(), &mut rng);
+        let shares = key.deal_shares(3, 5, &mut rng).unwrap();
+        let backend = LweFhe::new(params(), key).unwrap();
+        let coordinator = SigningKey::generate(&mut rng);
+
+        // 2·$3250 at 8 decimals and 3·(−1500): 649_999_995_500
+        let mut providers = HashMap::new();
+        let mut submissions = Vec::new();
+        for (provider_id, value, weight) in [("A", 325_000_000_000, 2), ("B", -1_500, 3)] {
+            let key = SigningKey::generate(&mut rng);
+            providers.insert(provider_id.to_string(), key.public_key());
+            let observation = observation(provider_id, value, weight);
+            submissions.push(EncryptedSubmission::encrypt(&key, EVENT, &observation, &backend, &mut rng).unwrap());
+        }
+        let ciphertext = aggregate_submissions(&submissions).unwrap();
+
+        let ledger = SharedLedger::new(OracleLedger::new("admin"), 0);
+        ledger
+            .transact(|chain, now| chain.create_event("admin", EVENT, "ETH above $4000", DEADLINE, now))
+            .unwrap();
+        ledger.set_block_time(CLOSE);
+
+        let mut signing_shares = run_dkg(&[1, 2, 3, 4, 5], 3, &HashMap::new(), &mut rng).unwrap();
+        let group = DkgCommitment::from_output(&signing_shares[&1]);
+        let keys: Vec<SigningKey> = shares.iter().map(|_| SigningKey::generate(&mut rng)).collect();
+        let identities: HashMap<u32, RistrettoPoint> =
+            shares.iter().zip(&keys).map(|(share, key)| (share.id, key.public_key())).collect();
+        let servers = shares
+            .into_iter()
+            .zip(keys)
+            .map(|(share, identity)| {
+                let config = NodeConfig {
+                    coordinator: coordinator.public_key(),
+                    params: params(),
+                    peers: identities.clone(),
+                    threshold: 3,
+                    faults: 0,
+                    providers: providers.clone(),
+                    contract: Box::new(ledger.clone()),
+                    limits,
+                };
+                let signing_share = signing_shares.remove(&share.id).unwrap();
+                let node = DecryptorNode::new(share, signing_share, identity, config).unwrap();
+                serve(node, "127.0.0.1:0".parse().unwrap()).unwrap()
+            })
+            .collect();
+        Committee {
+            servers,
+            identities,
+            group,
+            coordinator,
+            backend,
+            ledger,
+            submissions,
+            ciphertext,
+            rng,
+        }
+    }
+
+    fn request(c: &mut Committee, round: u64) -> DecryptRequest {
+        DecryptRequest::new(&c.coordinator, EVENT, round, c.ciphertext.clone(), c.submissions.clone(), &mut c.rng)
+    }
+
+    fn refused(result: Result<DecryptResponse, ServiceError>) -> bool {
+        matches!(result, Err(ServiceError::NotDecryptable(_)))
+    }
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn test_committee_decrypts_over_http() {
+        let mut c = committee(RateLimit::default(), 1);
+        let request = request(&mut c, 1);
+
+        let mut partials = Vec::new();
+        for server in &c.servers[..3] {
+            let response = post_decrypt(server.local_addr(), &request, TIMEOUT).unwrap();
+            let id = response.id;
+            assert!(response.verify(&request, &c.identities[&id]));
+            assert!(!response.verify(&request, &c.identities[&(id % 5 + 1)]));
+            // One partial per quorum of 3 among 5 containing the node
+            assert_eq!(response.partials.len(), 6);
+            partials.push(response.partial_for(&[1, 2, 3]).unwrap());
+        }
+        let value = combine_partial_decryptions(&params(), &c.ciphertext, &partials, 3).unwrap();
+        assert_eq!(value, 649_999_995_500);
+    }
+
+    #[test]
+    fn test_nodes_sign_only_results_their_evidence_backs() {
+        let mut c = committee(RateLimit::default(), 5);
+        let request = request(&mut c, 1);
+        let evidence: Vec<(DecryptRequest, DecryptResponse)> = c.servers[..3]
+            .iter()
+            .map(|server| (request.clone(), post_decrypt(server.local_addr(), &request, TIMEOUT).unwrap()))
+            .collect();
+        let commitments: Vec<SigningCommitments> = evidence.iter().map(|(_, r)| r.commitments).collect();
+        let result = OracleResult {
+            event_id: EVENT.to_string(),
+            aggregate_value: 649_999_995_500,
+            threshold: 0,
+            result: "YES".to_string(),
+            decryptor_set: evidence.iter().map(|(_, r)| r.id).collect(),
+        };
+        let sign = |c: &mut Committee, result: &OracleResult, commitments: &[SigningCommitments]| {
+            SignRequest::new(&c.coordinator, result.clone(), commitments.to_vec(), evidence.clone(), &mut c.rng)
+        };
+        let addr = c.servers[0].local_addr();
+
+        // Not the coordinator's, not what the partials decrypt to, or with a
+        // commitment the signer never sent: refused, and no nonce is spent
+        let impostor = SigningKey::generate(&mut c.rng);
+        let forged = SignRequest::new(&impostor, result.clone(), commitments.clone(), evidence.clone(), &mut c.rng);
+        assert_eq!(post_sign(addr, &forged, TIMEOUT), Err(ServiceError::Unauthorized));
+        let mut inflated = result.clone();
+        inflated.aggregate_value += 1;
+        let inflated = sign(&mut c, &inflated, &commitments);
+        assert!(matches!(post_sign(addr, &inflated, TIMEOUT), Err(ServiceError::NotDecryptable(_))));
+        let mut swapped = commitments.clone();
+        swapped[1] = commit(swapped[1].id, &mut c.rng).1;
+        let swapped = sign(&mut c, &result, &swapped);
+        assert!(matches!(post_sign(addr, &swapped, TIMEOUT), Err(ServiceError::NotDecryptable(_))));
+        let too_few = sign(&mut c, &result, &commitments[..2]);
+        assert!(matches!(post_sign(addr, &too_few, TIMEOUT), Err(ServiceError::NotDecryptable(_))));
+
+        let signing = sign(&mut c, &result, &commitments);
+        let shares: Vec<SignatureShare> = c.servers[..3]
+            .iter()
+            .map(|server| post_sign(server.local_addr(), &signing, TIMEOUT).unwrap())
+            .collect();
+        let verification_shares = commitments
+            .iter()
+            .map(|cm| (cm.id, c.group.commitment.share_commitment(cm.id)))
+            .collect();
+        let group_key = c.group.public_key().0;
+        let signature =
+            aggregate(&group_key, &result.canonical_bytes(), &commitments, &shares, &verification_shares).unwrap();
+        assert!(verify_oracle_result(&group_key, &result, &signature));
+
+        // The same package again gets the same share; another one never does
+        assert_eq!(post_sign(addr, &signing, TIMEOUT).unwrap(), shares[0]);
+        let mut restated = result.clone();
+        restated.threshold = 1;
+        let restated = sign(&mut c, &restated, &commitments);
+        assert!(matches!(post_sign(addr, &restated, TIMEOUT), Err(ServiceError::NotDecryptable(_))));
+    }
+
+    #[test]
+    fn test_rejects_requests_the_contract_and_submissions_do_not_back() {
+        let mut c = committee(RateLimit::default(), 2);
+        let addr = c.servers[0].local_addr();
+        let submissions = c.submissions.clone();
+        let ciphertext = c.ciphertext.clone();
+        let signed_request = |c: &mut Committee, event_id: &str, ciphertext: LweCiphertext, submissions| {
+            DecryptRequest::new(&c.coordinator, event_id, 1, ciphertext, submissions, &mut c.rng)
+        };
+
+        let impostor = SigningKey::generate(&mut c.rng);
+        let forged = DecryptRequest::new(&impostor, EVENT, 1, c.ciphertext.clone(), submissions.clone(), &mut c.rng);
+        assert_eq!(post_decrypt(addr, &forged, TIMEOUT), Err(ServiceError::Unauthorized));
+
+        let unknown = signed_request(&mut c, "unknown-event", ciphertext.clone(), submissions.clone());
+        assert!(refused(post_decrypt(addr, &unknown, TIMEOUT)));
+
+        // Before submissions close, and once no result can be proposed
+        let on_time = request(&mut c, 1);
+        for block_time in [CLOSE - 1, DEADLINE + 1] {
+            c.ledger.set_block_time(block_time);
+            assert!(refused(post_decrypt(addr, &on_time, TIMEOUT)));
+        }
+        c.ledger.set_block_time(DEADLINE);
+
+        // A ciphertext that is not the aggregate of the submissions
+        let other = LweCiphertext::try_from(&c.backend.encrypt(7)).unwrap();
+        let swapped = signed_request(&mut c, EVENT, other, submissions.clone());
+        assert!(refused(post_decrypt(addr, &swapped, TIMEOUT)));
+
+        // A consistent aggregate over a submission no registered provider signed
+        let mut injected = submissions.clone();
+        let unregistered = SigningKey::generate(&mut c.rng);
+        injected[0] =
+            EncryptedSubmission::encrypt(&unregistered, EVENT, &observation("A", 1_000, 2), &c.backend, &mut c.rng)
+                .unwrap();
+        let aggregate = aggregate_submissions(&injected).unwrap();
+        let unsigned = signed_request(&mut c, EVENT, aggregate, injected);
+        assert!(refused(post_decrypt(addr, &unsigned, TIMEOUT)));
+
+        // Submissions replayed from another event, or altered in transit
+        let replayed = signed_request(&mut c, "eth-below-3000", ciphertext.clone(), submissions.clone());
+        assert!(refused(post_decrypt(addr, &replayed, TIMEOUT)));
+        let mut altered = submissions.clone();
+        altered[1].weight = 30;
+        let aggregate = aggregate_submissions(&altered).unwrap();
+        let reweighted = signed_request(&mut c, EVENT, aggregate, altered);
+        assert!(refused(post_decrypt(addr, &reweighted, TIMEOUT)));
+
+        let mut doubled = submissions.clone();
+        doubled.push(submissions[0].clone());
+        let aggregate = aggregate_submissions(&doubled).unwrap();
+        let duplicate = signed_request(&mut c, EVENT, aggregate, doubled);
+        assert!(refused(post_decrypt(addr, &duplicate, TIMEOUT)));
+
+        // Once answered, the event's aggregate is pinned
+        let first_only = aggregate_submissions(&submissions[..1]).unwrap();
+        let partial = signed_request(&mut c, EVENT, first_only, submissions[..1].to_vec());
+        assert!(post_decrypt(addr, &partial, TIMEOUT).is_ok());
+        let full = request(&mut c, 2);
+        assert!(refused(post_decrypt(addr, &full, TIMEOUT)));
+        assert!(post_decrypt(c.servers[1].local_addr(), &full, TIMEOUT).is_ok());
+
+        // Nothing is decrypted once a result is on chain
+        c.ledger
+            .transact(|chain, now| chain.propose_result("coordinator", EVENT, 1, [0; 32], now))
+            .unwrap();
+        let late = request(&mut c, 3);
+        assert!(refused(post_decrypt(c.servers[2].local_addr(), &late, TIMEOUT)));
+    }
+
+    #[test]
+    fn test_rounds_are_fresh_and_rate_limited() {
+        let limits = RateLimit {
+            max_rounds: 2,
+            window: Duration::from_secs(60),
+        };
+        let mut c = committee(limits, 3);
+        let addr = c.servers[0].local_addr();
+
+        let first = request(&mut c, 5);
+        assert!(post_decrypt(addr, &first, TIMEOUT).is_ok());
+        assert!(matches!(post_decrypt(addr, &first, TIMEOUT), Err(ServiceError::StaleRound(_))));
+        assert!(matches!(post_decrypt(addr, &request(&mut c, 4), TIMEOUT), Err(ServiceError::StaleRound(_))));
+        assert!(post_decrypt(addr, &request(&mut c, 6), TIMEOUT).is_ok());
+        assert_eq!(post_decrypt(addr, &request(&mut c, 7), TIMEOUT), Err(ServiceError::RateLimited));
+    }
+
+    #[test]
+    fn test_contract_state_file_reflects_the_ledger() {
+        let path = std::env::temp_dir().join(format!("blocksense-contract-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let file = ContractStateFile::new(&path);
+        assert_eq!(file.event(EVENT), None);
+        assert_eq!(file.block_time(), 0);
+
+        let mut ledger = OracleLedger::new("admin");
+        ledger.create_event("admin", EVENT, "ETH above $4000", DEADLINE, 0).unwrap();
+        ledger.create_event("admin", "other", "BTC above $100k", 20_000, 0).unwrap();
+        ledger.propose_result("coordinator", "other", 0, [1; 32], 500).unwrap();
+        file.write(&ledger, CLOSE).unwrap();
+
+        assert_eq!(file.block_time(), CLOSE);
+        let pending = ContractEvent {
+            state: LifecycleState::Pending,
+            deadline: DEADLINE,
+        };
+        assert_eq!(file.event(EVENT), Some(pending));
+        assert_eq!(file.event("other").map(|e| e.state), Some(LifecycleState::Proposed));
+        assert_eq!(file.load().unwrap().1.len(), 2);
+
+        fs::write(&path, "{\"block_time\":5,\"events\":{\"x\":{\"state\":\"DONE\",\"deadline\":1}}}").unwrap();
+        assert!(file.load().unwrap_err().contains("DONE"));
+        assert_eq!(file.event("x"), None);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_http_routing_and_loopback_only() {
+        let mut c = committee(RateLimit::default(), 4);
+        let addr = c.servers[0].local_addr();
+        let send = |raw: &str| {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            stream.write_all(raw.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+        assert!(send("GET /health HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 200"));
+        assert!(send("GET /decrypt HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 405"));
+        assert!(send("GET /keys HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 404"));
+        assert!(send("POST /decrypt HTTP/1.1\r\nContent-Length: 2\r\n\r\n{}").starts_with("HTTP/1.1 400"));
+        assert!(send("POST /decrypt HTTP/1.1\r\nContent-Length: 9999999\r\n\r\n").starts_with("HTTP/1.1 413"));
+
+        let key = LweSecretKey::generate(&params(), &mut c.rng);
+        let mut shares = key.deal_shares(1, 1, &mut c.rng).unwrap();
+        shares.push(key.deal_shares(1, 1, &mut c.rng).unwrap().remove(0));
+        let mut signing_shares = run_dkg(&[1, 2], 1, &HashMap::new(), &mut c.rng).unwrap();
+        let identity = SigningKey::generate(&mut c.rng);
+        let identity_key = identity.public_key();
+        let config = |identity: RistrettoPoint| NodeConfig {
+            coordinator: c.coordinator.public_key(),
+            params: params(),
+            peers: HashMap::from([(1, identity)]),
+            threshold: 1,
+            faults: 0,
+            providers: HashMap::new(),
+            contract: Box::new(c.ledger.clone()),
+            limits: RateLimit::default(),
+        };
+        // The signing share and the identity key must be this decryptor's
+        let other = signing_shares.remove(&2).unwrap();
+        assert!(DecryptorNode::new(shares.remove(1), other, SigningKey::generate(&mut c.rng), config(identity_key)).is_err());
+        let own = signing_shares.remove(&1).unwrap();
+        let stranger = SigningKey::generate(&mut c.rng).public_key();
+        assert!(DecryptorNode::new(shares.remove(0), own, identity, config(stranger)).is_err());
+
+        let key = LweSecretKey::generate(&params(), &mut c.rng);
+        let share = key.deal_shares(1, 1, &mut c.rng).unwrap().remove(0);
+        let own = run_dkg(&[1], 1, &HashMap::new(), &mut c.rng).unwrap().remove(&1).unwrap();
+        let identity = SigningKey::generate(&mut c.rng);
+        let identity_key = identity.public_key();
+        let node = DecryptorNode::new(share, own, identity, config(identity_key)).unwrap();
+        assert!(serve(node, "0.0.0.0:0".parse().unwrap()).is_err());
+    }
+}
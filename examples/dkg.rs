@@ -0,0 +1,783 @@
+//! Distributed Key Generation among Decryptor Nodes
+//!
+//! Pedersen/GJKR-style DKG: no party ever learns the joint secret.
+//!
+//! 1. Every node deals a Pedersen VSS of its own random z_i.
+//! 2. Receivers verify their shares and broadcast complaints.
+//! 3. Accused dealers answer by revealing the disputed share.
+//! 4. Dealers with an unanswered or invalid answer, or with ≥ k complaints,
+//!    are disqualified; the rest form QUAL.
+//! 5. QUAL dealers publish Feldman commitments a_ik·G to extract the public key.
+//!    A node whose share does not match a dealer's Feldman commitments
+//!    complains and publishes that share. If the share passes the Pedersen
+//!    check and fails the Feldman check, the dealer's z_i is reconstructed in
+//!    public from the Pedersen-verified shares. A complaint that does not
+//!    hold up is dropped and its sender is disqualified.
+//!
+//! Node j ends with key share x_j = ∑_{i∈QUAL} s_ij of x = ∑ z_i and the
+//! public key Y = x·G. Messages travel over an in-memory bus so a full run can
+//! be tested in one process.
+
+use crate::field::{PrimeField, Scalar25519};
+use crate::robust::robust_reconstruct;
+use crate::secret::Secret;
+use crate::shamir::Polynomial;
+use crate::vss::{FeldmanCommitment, PedersenCommitment, VssShare};
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::traits::Identity;
+use rand::{CryptoRng, RngCore};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Protocol message between decryptor nodes
+#[derive(Debug, PartialEq)]
+pub enum DkgMessage {
+    /// Broadcast: Pedersen commitments to the sender's polynomials
+    Commitment(PedersenCommitment),
+    /// Private: the recipient's share of the sender's secret
+    Share(VssShare),
+    /// Broadcast: the sender received an invalid or no share from `dealer`
+    Complaint { dealer: u32 },
+    /// Broadcast: dealer reveals the share it sent to `accuser`
+    ComplaintAnswer { accuser: u32, share: VssShare },
+    /// Broadcast: Feldman commitments a_ik·G for public key extraction
+    Extraction(FeldmanCommitment),
+    /// Broadcast: `dealer`'s Feldman commitments do not match the sender's
+    /// share, published as evidence
+    ExtractionComplaint { dealer: u32, share: VssShare },
+    /// Broadcast: the sender's share of `dealer`, for public reconstruction
+    Reveal { dealer: u32, share: VssShare },
+}
+
+/// Broadcasting copies a message per recipient; the share-carrying
+/// variants are copied deliberately, since those shares are being published
+impl Clone for DkgMessage {
+    fn clone(&self) -> Self {
+        match self {
+            DkgMessage::Commitment(c) => DkgMessage::Commitment(c.clone()),
+            DkgMessage::Share(share) => DkgMessage::Share(share.clone_secret()),
+            DkgMessage::Complaint { dealer } => DkgMessage::Complaint { dealer: *dealer },
+            DkgMessage::ComplaintAnswer { accuser, share } => DkgMessage::ComplaintAnswer {
+                accuser: *accuser,
+                share: share.clone_secret(),
+            },
+            DkgMessage::Extraction(c) => DkgMessage::Extraction(c.clone()),
+            DkgMessage::ExtractionComplaint { dealer, share } => DkgMessage::ExtractionComplaint {
+                dealer: *dealer,
+                share: share.clone_secret(),
+            },
+            DkgMessage::Reveal { dealer, share } => DkgMessage::Reveal {
+                dealer: *dealer,
+                share: share.clone_secret(),
+            },
+        }
+    }
+}
+
+/// Message with routing information
+#[derive(Clone, Debug, PartialEq)]
+pub struct Envelope {
+    pub from: u32,
+    /// None for broadcast
+    pub to: Option<u32>,
+    pub message: DkgMessage,
+}
+
+/// In-memory message bus with one inbox per node
+///
+/// Delivery is round-based: messages sent during a round become visible
+/// only after [`InMemoryBus::deliver`], so every node in a round reads the
+/// same inputs regardless of the order nodes run in. Broadcasts go to every
+/// node, the sender included, so all honest nodes see the same transcript.
+#[derive(Debug, Default)]
+pub struct InMemoryBus {
+    inboxes: HashMap<u32, VecDeque<Envelope>>,
+    in_flight: Vec<(u32, Envelope)>,
+}
+
+impl InMemoryBus {
+    pub fn new(nodes: &[u32]) -> Self {
+        InMemoryBus {
+            inboxes: nodes.iter().map(|&id| (id, VecDeque::new())).collect(),
+            in_flight: Vec::new(),
+        }
+    }
+
+    pub fn send(&mut self, from: u32, to: u32, message: DkgMessage) {
+        if self.inboxes.contains_key(&to) {
+            self.in_flight.push((
+                to,
+                Envelope {
+                    from,
+                    to: Some(to),
+                    message,
+                },
+            ));
+        }
+    }
+
+    pub fn broadcast(&mut self, from: u32, message: DkgMessage) {
+        let mut recipients: Vec<u32> = self.inboxes.keys().copied().collect();
+        recipients.sort_unstable();
+        for to in recipients {
+            self.in_flight.push((
+                to,
+                Envelope {
+                    from,
+                    to: None,
+                    message: message.clone(),
+                },
+            ));
+        }
+    }
+
+    /// End the round: move every message sent so far into its inbox
+    pub fn deliver(&mut self) {
+        for (to, envelope) in self.in_flight.drain(..) {
+            if let Some(inbox) = self.inboxes.get_mut(&to) {
+                inbox.push_back(envelope);
+            }
+        }
+    }
+
+    /// Take every delivered message queued for `node`
+    pub fn drain(&mut self, node: u32) -> Vec<Envelope> {
+        self.inboxes
+            .get_mut(&node)
+            .map(|inbox| inbox.drain(..).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Misbehaviour injected into a node, for testing
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DkgFault {
+    /// Sends nothing at all during dealing
+    Silent,
+    /// Sends `recipient` a corrupted share; answers the complaint with the
+    /// correct share if `answer_honestly`, with the corrupted one otherwise
+    CorruptShare { recipient: u32, answer_honestly: bool },
+    /// Publishes Feldman commitments to a different secret
+    BadExtraction,
+    /// Complains about every other dealer's extraction, hoping the honest
+    /// nodes reveal their shares
+    FalseExtractionComplaints,
+    /// Sends every reveal twice
+    DuplicateReveals,
+}
+
+/// Result of a DKG run for one node
+#[derive(Debug, PartialEq)]
+pub struct DkgOutput {
+    pub id: u32,
+    /// x_j = ∑_{i∈QUAL} s_ij, wiped on drop
+    pub key_share: Secret<Scalar25519>,
+    /// Y = ∑_{i∈QUAL} z_i·G
+    pub public_key: RistrettoPoint,
+    /// ∑_{i∈QUAL} a_ik·G: commits to the joint polynomial, so every key
+    /// share x_j checks against it like a Feldman share
+    pub commitment: FeldmanCommitment,
+    /// Dealers whose secrets make up the joint key
+    pub qualified: Vec<u32>,
+}
+
+impl DkgOutput {
+    /// x_j·G, published so others can check this node's partial results
+    pub fn verification_share(&self) -> RistrettoPoint {
+        RISTRETTO_BASEPOINT_POINT * self.key_share.expose().scalar()
+    }
+}
+
+/// One decryptor's DKG state
+pub struct DkgNode {
+    pub id: u32,
+    threshold: u32,
+    participants: Vec<u32>,
+    fault: Option<DkgFault>,
+    secret: Option<Polynomial<Scalar25519>>,
+    blinding: Option<Polynomial<Scalar25519>>,
+    commitments: HashMap<u32, PedersenCommitment>,
+    received: HashMap<u32, VssShare>,
+    complaints: HashMap<u32, BTreeSet<u32>>,
+    qualified: BTreeSet<u32>,
+    extractions: HashMap<u32, FeldmanCommitment>,
+    disputed: BTreeSet<u32>,
+}
+
+impl DkgNode {
+    pub fn new(id: u32, threshold: u32, participants: &[u32]) -> Result<Self, String> {
+        let unique: HashSet<u32> = participants.iter().copied().collect();
+        if unique.len() != participants.len() {
+            return Err("Duplicate participant ids".to_string());
+        }
+        if participants.contains(&0) {
+            return Err("Participant id 0 is reserved for the secret".to_string());
+        }
+        if !unique.contains(&id) {
+            return Err(format!("Node {} is not a participant", id));
+        }
+        if threshold == 0 || threshold as usize > participants.len() {
+            return Err(format!(
+                "Invalid threshold {} for {} participants",
+                threshold,
+                participants.len()
+            ));
+        }
+        Ok(DkgNode {
+            id,
+            threshold,
+            participants: participants.to_vec(),
+            fault: None,
+            secret: None,
+            blinding: None,
+            commitments: HashMap::new(),
+            received: HashMap::new(),
+            complaints: HashMap::new(),
+            qualified: BTreeSet::new(),
+            extractions: HashMap::new(),
+            disputed: BTreeSet::new(),
+        })
+    }
+
+    pub fn with_fault(mut self, fault: DkgFault) -> Self {
+        self.fault = Some(fault);
+        self
+    }
+
+    fn share_for(&self, recipient: u32) -> Option<VssShare> {
+        let x = Scalar25519::from(recipient);
+        Some(VssShare::pedersen(
+            self.secret.as_ref()?.evaluate(x),
+            self.blinding.as_ref()?.evaluate(x),
+        ))
+    }
+
+    /// Phase 1: deal a Pedersen VSS of a fresh random secret
+    pub fn deal<R: RngCore + CryptoRng>(&mut self, bus: &mut InMemoryBus, rng: &mut R) -> Result<(), String> {
+        let degree = self.threshold as usize - 1;
+        let secret = Polynomial::random(Scalar25519::random(rng), degree, rng);
+        let blinding = Polynomial::random(Scalar25519::random(rng), degree, rng);
+        let commitment = PedersenCommitment::from_polynomials(&secret, &blinding)?;
+        self.secret = Some(secret);
+        self.blinding = Some(blinding);
+
+        if self.fault == Some(DkgFault::Silent) {
+            return Ok(());
+        }
+
+        bus.broadcast(self.id, DkgMessage::Commitment(commitment));
+        for &recipient in &self.participants {
+            if let Some(mut share) = self.share_for(recipient) {
+                if let Some(DkgFault::CorruptShare { recipient: target, .. }) = self.fault {
+                    if target == recipient {
+                        share.value += Scalar25519::ONE;
+                    }
+                }
+                bus.send(self.id, recipient, DkgMessage::Share(share));
+            }
+        }
+        Ok(())
+    }
+
+    /// Phase 2: verify received shares and complain about bad dealers
+    pub fn verify_shares(&mut self, bus: &mut InMemoryBus) {
+        for envelope in bus.drain(self.id) {
+            match envelope.message {
+                DkgMessage::Commitment(commitment) if envelope.to.is_none() => {
+                    self.commitments.insert(envelope.from, commitment);
+                }
+                DkgMessage::Share(share) if envelope.to == Some(self.id) => {
+                    self.received.insert(envelope.from, share);
+                }
+                _ => {}
+            }
+        }
+
+        for &dealer in &self.participants {
+            if !self.share_is_valid(dealer) {
+                self.received.remove(&dealer);
+                bus.broadcast(self.id, DkgMessage::Complaint { dealer });
+            }
+        }
+    }
+
+    fn share_is_valid(&self, dealer: u32) -> bool {
+        match (self.commitments.get(&dealer), self.received.get(&dealer)) {
+            (Some(commitment), Some(share)) => verify_pedersen(commitment, self.id, share),
+            _ => false,
+        }
+    }
+
+    /// Phase 3: answer complaints against this node by revealing the share
+    pub fn answer_complaints(&mut self, bus: &mut InMemoryBus) {
+        for envelope in bus.drain(self.id) {
+            if let DkgMessage::Complaint { dealer } = envelope.message {
+                self.complaints.entry(dealer).or_default().insert(envelope.from);
+            }
+        }
+
+        if self.fault == Some(DkgFault::Silent) {
+            return;
+        }
+        let accusers = self.complaints.get(&self.id).cloned().unwrap_or_default();
+        for accuser in accusers {
+            if let Some(mut share) = self.share_for(accuser) {
+                if let Some(DkgFault::CorruptShare {
+                    recipient,
+                    answer_honestly: false,
+                }) = self.fault
+                {
+                    if recipient == accuser {
+                        share.value += Scalar25519::ONE;
+                    }
+                }
+                bus.broadcast(self.id, DkgMessage::ComplaintAnswer { accuser, share });
+            }
+        }
+    }
+
+    /// Phase 4: settle complaints, fix QUAL and publish Feldman commitments
+    pub fn qualify_and_extract(&mut self, bus: &mut InMemoryBus) -> Result<(), String> {
+        let mut answers: HashMap<(u32, u32), VssShare> = HashMap::new();
+        for envelope in bus.drain(self.id) {
+            if let DkgMessage::ComplaintAnswer { accuser, share } = envelope.message {
+                answers.insert((envelope.from, accuser), share);
+            }
+        }
+
+        for &dealer in &self.participants {
+            let Some(commitment) = self.commitments.get(&dealer) else {
+                continue;
+            };
+            let accusers = self.complaints.get(&dealer).cloned().unwrap_or_default();
+            if accusers.len() >= self.threshold as usize {
+                continue;
+            }
+
+            let mut answered_all = true;
+            for &accuser in &accusers {
+                match answers.get(&(dealer, accuser)) {
+                    Some(share) if verify_pedersen(commitment, accuser, share) => {
+                        if accuser == self.id {
+                            self.received.insert(dealer, share.clone_secret());
+                        }
+                    }
+                    _ => answered_all = false,
+                }
+            }
+            if answered_all {
+                self.qualified.insert(dealer);
+            }
+        }
+
+        if self.qualified.len() < self.threshold as usize {
+            return Err(format!(
+                "Only {} qualified dealers, need {}",
+                self.qualified.len(),
+                self.threshold
+            ));
+        }
+
+        if self.qualified.contains(&self.id) {
+            if let Some(secret) = &self.secret {
+                let mut extraction = FeldmanCommitment::from_polynomial(secret);
+                if self.fault == Some(DkgFault::BadExtraction) {
+                    extraction.points[0] += RISTRETTO_BASEPOINT_POINT;
+                }
+                bus.broadcast(self.id, DkgMessage::Extraction(extraction));
+            }
+        }
+        // Polynomials are not needed past this point
+        self.secret = None;
+        self.blinding = None;
+        Ok(())
+    }
+
+    /// Phase 5: check Feldman commitments against the shares held
+    pub fn verify_extraction(&mut self, bus: &mut InMemoryBus) {
+        for envelope in bus.drain(self.id) {
+            if let DkgMessage::Extraction(commitment) = envelope.message {
+                if self.qualified.contains(&envelope.from) {
+                    self.extractions.insert(envelope.from, commitment);
+                }
+            }
+        }
+
+        let lying = self.fault == Some(DkgFault::FalseExtractionComplaints);
+        for &dealer in &self.qualified {
+            let Some(share) = self.received.get(&dealer) else {
+                continue;
+            };
+            if (lying && dealer != self.id) || !self.extraction_matches(dealer, self.id, share) {
+                let share = share.clone_secret();
+                bus.broadcast(self.id, DkgMessage::ExtractionComplaint { dealer, share });
+            }
+        }
+    }
+
+    fn extraction_matches(&self, dealer: u32, id: u32, share: &VssShare) -> bool {
+        self.extractions.get(&dealer).is_some_and(|extraction| {
+            extraction.threshold() == self.threshold as usize && extraction.verify_share(id, share.value)
+        })
+    }
+
+    /// Phase 6: settle extraction complaints and reveal shares of dealers
+    /// whose extraction was shown to be wrong
+    ///
+    /// A complaint counts only if its share passes the dealer's Pedersen
+    /// check and fails the Feldman one. Anyone sending a complaint that does
+    /// not hold up is disqualified; their own dealing leaves the joint key.
+    pub fn reveal_disputed(&mut self, bus: &mut InMemoryBus) -> Result<(), String> {
+        let mut false_accusers = BTreeSet::new();
+        for envelope in bus.drain(self.id) {
+            if let DkgMessage::ExtractionComplaint { dealer, share } = envelope.message {
+                if !self.qualified.contains(&dealer) {
+                    continue;
+                }
+                let justified = self
+                    .commitments
+                    .get(&dealer)
+                    .is_some_and(|commitment| verify_pedersen(commitment, envelope.from, &share))
+                    && !self.extraction_matches(dealer, envelope.from, &share);
+                if justified {
+                    self.disputed.insert(dealer);
+                } else {
+                    false_accusers.insert(envelope.from);
+                }
+            }
+        }
+        for accuser in false_accusers {
+            self.qualified.remove(&accuser);
+            self.disputed.remove(&accuser);
+        }
+        if self.qualified.len() < self.threshold as usize {
+            return Err(format!(
+                "Only {} qualified dealers after extraction complaints, need {}",
+                self.qualified.len(),
+                self.threshold
+            ));
+        }
+
+        for &dealer in &self.disputed {
+            if let Some(share) = self.received.get(&dealer) {
+                let copies = if self.fault == Some(DkgFault::DuplicateReveals) { 2 } else { 1 };
+                for _ in 0..copies {
+                    let share = share.clone_secret();
+                    bus.broadcast(self.id, DkgMessage::Reveal { dealer, share });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Phase 7: compute the key share and the public key
+    pub fn finalize(&mut self, bus: &mut InMemoryBus) -> Result<DkgOutput, String> {
+        // One point per (dealer, sender), so repeated reveals cannot stall interpolation
+        let mut revealed: HashMap<u32, BTreeMap<u32, Scalar25519>> = HashMap::new();
+        for envelope in bus.drain(self.id) {
+            if let DkgMessage::Reveal { dealer, share } = envelope.message {
+                // Only shares consistent with the dealer's Pedersen commitment count
+                if let Some(commitment) = self.commitments.get(&dealer) {
+                    if self.disputed.contains(&dealer) && verify_pedersen(commitment, envelope.from, &share) {
+                        revealed
+                            .entry(dealer)
+                            .or_default()
+                            .entry(envelope.from)
+                            .or_insert(share.value);
+                    }
+                }
+            }
+        }
+
+        let mut key_share = Secret::new(Scalar25519::ZERO);
+        let mut joint = vec![RistrettoPoint::identity(); self.threshold as usize];
+        for &dealer in &self.qualified {
+            let share = self
+                .received
+                .get(&dealer)
+                .ok_or_else(|| format!("Missing share from qualified dealer {}", dealer))?;
+            *key_share.expose_mut() += share.value;
+
+            let extraction = if self.disputed.contains(&dealer) {
+                // k Pedersen-verified shares fix the dealer's whole polynomial
+                let points: Vec<(u32, Scalar25519)> = revealed
+                    .get(&dealer)
+                    .into_iter()
+                    .flatten()
+                    .take(self.threshold as usize)
+                    .map(|(&id, &value)| (id, value))
+                    .collect();
+                if points.len() < self.threshold as usize {
+                    return Err(format!("Cannot reconstruct secret of dealer {}", dealer));
+                }
+                let polynomial = robust_reconstruct(&points, self.threshold as usize)?.polynomial;
+                &FeldmanCommitment {
+                    points: polynomial
+                        .iter()
+                        .map(|a| RISTRETTO_BASEPOINT_POINT * a.scalar())
+                        .collect(),
+                }
+            } else {
+                &self.extractions[&dealer]
+            };
+            for (sum, point) in joint.iter_mut().zip(&extraction.points) {
+                *sum += point;
+            }
+        }
+
+        let commitment = FeldmanCommitment { points: joint };
+        Ok(DkgOutput {
+            id: self.id,
+            key_share,
+            public_key: commitment.public_secret(),
+            commitment,
+            qualified: self.qualified.iter().copied().collect(),
+        })
+    }
+}
+
+fn verify_pedersen(commitment: &PedersenCommitment, id: u32, share: &VssShare) -> bool {
+    match share.blinding {
+        Some(blinding) => {
+            commitment.threshold() > 0 && commitment.verify_share(id, share.value, blinding)
+        }
+        None => false,
+    }
+}
+
+/// Run phases 1–6 for every node, ending each round on the bus; what is
+/// left in the inboxes is the input to [`DkgNode::finalize`]
+fn exchange<R: RngCore + CryptoRng>(nodes: &mut [DkgNode], bus: &mut InMemoryBus, rng: &mut R) -> Result<(), String> {
+    for node in nodes.iter_mut() {
+        node.deal(bus, rng)?;
+    }
+    bus.deliver();
+    for node in nodes.iter_mut() {
+        node.verify_shares(bus);
+    }
+    bus.deliver();
+    for node in nodes.iter_mut() {
+        node.answer_complaints(bus);
+    }
+    bus.deliver();
+    for node in nodes.iter_mut() {
+        node.qualify_and_extract(bus)?;
+    }
+    bus.deliver();
+    for node in nodes.iter_mut() {
+        node.verify_extraction(bus);
+    }
+    bus.deliver();
+    for node in nodes.iter_mut() {
+        node.reveal_disputed(bus)?;
+    }
+    bus.deliver();
+    Ok(())
+}
+
+/// Run the whole protocol among `participants` over an in-memory bus
+///
+/// Returns every node's output; honest nodes must agree on QUAL and the
+/// public key, otherwise the run fails.
+pub fn run_dkg<R: RngCore + CryptoRng>(
+    participants: &[u32],
+    threshold: u32,
+    faults: &HashMap<u32, DkgFault>,
+    rng: &mut R,
+) -> Result<HashMap<u32, DkgOutput>, String> {
+    let mut bus = InMemoryBus::new(participants);
+    let mut nodes = participants
+        .iter()
+        .map(|&id| {
+            let node = DkgNode::new(id, threshold, participants)?;
+            Ok(match faults.get(&id) {
+                Some(&fault) => node.with_fault(fault),
+                None => node,
+            })
+        })
+        .collect::<Result<Vec<DkgNode>, String>>()?;
+
+    exchange(&mut nodes, &mut bus, rng)?;
+    let outputs = nodes
+        .iter_mut()
+        .map(|node| Ok((node.id, node.finalize(&mut bus)?)))
+        .collect::<Result<HashMap<u32, DkgOutput>, String>>()?;
+
+    let mut honest = outputs.values().filter(|o| !faults.contains_key(&o.id));
+    if let Some(first) = honest.next() {
+        if honest.any(|o| o.commitment != first.commitment || o.qualified != first.qualified) {
+            return Err("Honest nodes disagree on the DKG result".to_string());
+        }
+    }
+    Ok(outputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::interpolate_at;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    const NODES: [u32; 5] = [1, 2, 3, 4, 5];
+
+    fn run(faults: &[(u32, DkgFault)]) -> Result<HashMap<u32, DkgOutput>, String> {
+        let faults: HashMap<u32, DkgFault> = faults.iter().copied().collect();
+        run_dkg(&NODES, 3, &faults, &mut ChaCha20Rng::seed_from_u64(36))
+    }
+
+    /// Interpolate the joint secret from the given nodes' key shares
+    fn joint_secret(outputs: &HashMap<u32, DkgOutput>, ids: &[u32]) -> Scalar25519 {
+        let points: Vec<_> = ids
+            .iter()
+            .map(|id| (Scalar25519::from(*id), *outputs[id].key_share.expose()))
+            .collect();
+        interpolate_at(&points, Scalar25519::ZERO).unwrap()
+    }
+
+    fn assert_consistent(outputs: &HashMap<u32, DkgOutput>, honest: &[u32]) {
+        let public_key = outputs[&honest[0]].public_key;
+        for subset in [[honest[0], honest[1], honest[2]], [honest[2], honest[1], honest[0]]] {
+            let x = joint_secret(outputs, &subset);
+            assert_eq!(RISTRETTO_BASEPOINT_POINT * x.scalar(), public_key);
+        }
+        // Every key share checks against the same joint commitment
+        let commitment = &outputs[&honest[0]].commitment;
+        assert_eq!(commitment.public_secret(), public_key);
+        for id in honest {
+            assert_eq!(&outputs[id].commitment, commitment);
+            assert!(commitment.verify_share(*id, *outputs[id].key_share.expose()));
+            assert_eq!(commitment.share_commitment(*id), outputs[id].verification_share());
+        }
+    }
+
+    #[test]
+    fn test_honest_dkg_produces_consistent_key_shares() {
+        let outputs = run(&[]).unwrap();
+
+        assert_eq!(outputs.len(), 5);
+        for output in outputs.values() {
+            assert_eq!(output.qualified, NODES.to_vec());
+        }
+        assert_consistent(&outputs, &[1, 2, 3, 4, 5]);
+        assert_eq!(joint_secret(&outputs, &[1, 2, 3]), joint_secret(&outputs, &[2, 4, 5]));
+    }
+
+    #[test]
+    fn test_answered_complaint_keeps_dealer_qualified() {
+        let outputs = run(&[(2, DkgFault::CorruptShare { recipient: 4, answer_honestly: true })]).unwrap();
+
+        assert_eq!(outputs[&4].qualified, NODES.to_vec());
+        // Node 4 adopted the share revealed in the answer
+        assert_consistent(&outputs, &[1, 3, 4, 5]);
+        assert_eq!(joint_secret(&outputs, &[1, 3, 4]), joint_secret(&outputs, &[3, 4, 5]));
+    }
+
+    #[test]
+    fn test_bad_answer_disqualifies_dealer() {
+        let outputs = run(&[(2, DkgFault::CorruptShare { recipient: 4, answer_honestly: false })]).unwrap();
+
+        for id in [1, 3, 4, 5] {
+            assert_eq!(outputs[&id].qualified, vec![1, 3, 4, 5]);
+        }
+        assert_consistent(&outputs, &[1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_silent_dealer_is_disqualified() {
+        let outputs = run(&[(5, DkgFault::Silent)]).unwrap();
+
+        assert_eq!(outputs[&1].qualified, vec![1, 2, 3, 4]);
+        assert_consistent(&outputs, &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_bad_extraction_is_reconstructed_publicly() {
+        let outputs = run(&[(3, DkgFault::BadExtraction)]).unwrap();
+
+        // Dealer 3 stays in QUAL; its public value comes from revealed shares
+        assert_eq!(outputs[&1].qualified, NODES.to_vec());
+        assert_consistent(&outputs, &[1, 2, 4, 5]);
+    }
+
+    #[test]
+    fn test_duplicate_reveals_count_once() {
+        // Node 1 reveals its share of dealer 3 twice; it must not add a
+        // second point at x = 1
+        let outputs = run(&[(3, DkgFault::BadExtraction), (1, DkgFault::DuplicateReveals)]).unwrap();
+
+        assert_eq!(outputs[&2].qualified, NODES.to_vec());
+        assert_consistent(&outputs, &[2, 4, 5]);
+        assert_eq!(outputs[&2].public_key, outputs[&1].public_key);
+    }
+
+    #[test]
+    fn test_false_extraction_complaints_reveal_nothing() {
+        let mut bus = InMemoryBus::new(&NODES);
+        let mut nodes: Vec<DkgNode> = NODES
+            .iter()
+            .map(|&id| {
+                let node = DkgNode::new(id, 3, &NODES).unwrap();
+                match id {
+                    5 => node.with_fault(DkgFault::FalseExtractionComplaints),
+                    _ => node,
+                }
+            })
+            .collect();
+        exchange(&mut nodes, &mut bus, &mut ChaCha20Rng::seed_from_u64(36)).unwrap();
+
+        // Node 5 complained against every honest dealer; nobody revealed a share
+        let inbox = bus.drain(5);
+        assert!(inbox.iter().all(|e| !matches!(e.message, DkgMessage::Reveal { .. })));
+
+        let outputs: HashMap<u32, DkgOutput> = nodes
+            .iter_mut()
+            .filter(|node| node.id != 5)
+            .map(|node| (node.id, node.finalize(&mut bus).unwrap()))
+            .collect();
+        for output in outputs.values() {
+            assert_eq!(output.qualified, vec![1, 2, 3, 4]);
+        }
+        assert_consistent(&outputs, &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_false_complainer_is_disqualified_in_full_run() {
+        let outputs = run(&[(2, DkgFault::FalseExtractionComplaints)]).unwrap();
+
+        assert_eq!(outputs[&1].qualified, vec![1, 3, 4, 5]);
+        assert_consistent(&outputs, &[1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_dkg_fails_without_enough_qualified_dealers() {
+        let faults = [(1, DkgFault::Silent), (2, DkgFault::Silent), (3, DkgFault::Silent)];
+        assert!(run(&faults).is_err());
+    }
+
+    #[test]
+    fn test_node_rejects_invalid_parameters() {
+        assert!(DkgNode::new(1, 0, &NODES).is_err());
+        assert!(DkgNode::new(1, 6, &NODES).is_err());
+        assert!(DkgNode::new(9, 3, &NODES).is_err());
+        assert!(DkgNode::new(1, 3, &[1, 1, 2]).is_err());
+        assert!(DkgNode::new(1, 2, &[0, 1, 2]).is_err());
+    }
+
+    #[test]
+    fn test_bus_routes_private_and_broadcast_messages() {
+        let mut bus = InMemoryBus::new(&[1, 2]);
+        bus.send(1, 2, DkgMessage::Complaint { dealer: 7 });
+        bus.broadcast(2, DkgMessage::Commitment(PedersenCommitment { points: Vec::new() }));
+
+        // Nothing is visible until the round ends
+        assert!(bus.drain(2).is_empty());
+        bus.deliver();
+
+        assert_eq!(bus.drain(1).len(), 1);
+        let inbox = bus.drain(2);
+        assert_eq!(inbox.len(), 2);
+        assert_eq!(inbox[0].to, Some(2));
+        assert!(bus.drain(2).is_empty());
+    }
+}
@@ -0,0 +1,200 @@
+//! Dudect-style Timing Leakage Tests
+//!
+//! Times an operation on two classes of inputs — a fixed input and random
+//! inputs — in random interleaved order, then applies Welch's t-test to the
+//! two timing distributions (Reparaz, Balasch and Verbauwhede, "Dude, is my
+//! code constant time?"). A |t| above 10 is strong evidence that the running
+//! time depends on the input.
+//!
+//! Timings are noisy on shared machines, so these tests are ignored by
+//! default. Run them in release mode on an otherwise idle machine:
+//!
+//! ```text
+//! cargo test -p blocksense-examples --release --lib dudect -- --ignored --test-threads=1
+//! ```
+
+use crate::field::{interpolate_at, FieldElement, Goldilocks, PrimeField, Scalar25519};
+use crate::vss::feldman_deal;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::hint::black_box;
+use std::time::Instant;
+
+/// |t| above this means the two classes are distinguishable
+const LEAK_THRESHOLD: f64 = 10.0;
+
+/// A full-width exponent so every square-and-multiply step does work
+const FIELD_EXPONENT: u64 = 0xdead_beef_cafe_f00d;
+
+/// Percentiles at which slow outliers are cropped before testing
+const CROP_PERCENTILES: [f64; 4] = [0.5, 0.75, 0.9, 0.99];
+
+/// Running mean and variance per class (Welford), combined by Welch's t
+#[derive(Default)]
+struct WelchTest {
+    count: [f64; 2],
+    mean: [f64; 2],
+    m2: [f64; 2],
+}
+
+impl WelchTest {
+    fn push(&mut self, class: usize, x: f64) {
+        self.count[class] += 1.0;
+        let delta = x - self.mean[class];
+        self.mean[class] += delta / self.count[class];
+        self.m2[class] += delta * (x - self.mean[class]);
+    }
+
+    fn t(&self) -> f64 {
+        let var = |c: usize| self.m2[c] / (self.count[c] - 1.0);
+        let se = (var(0) / self.count[0] + var(1) / self.count[1]).sqrt();
+        if se == 0.0 {
+            0.0
+        } else {
+            (self.mean[0] - self.mean[1]) / se
+        }
+    }
+}
+
+/// Largest |t| over the raw timings and each cropped subset
+///
+/// `input` builds an input for class 0 (fixed) or 1 (random); each
+/// measurement times `batch` back-to-back calls of `op`.
+fn max_t<I, G, F>(samples: usize, batch: usize, mut input: G, mut op: F) -> f64
+where
+    G: FnMut(usize, &mut ChaCha20Rng) -> I,
+    F: FnMut(&I),
+{
+    let mut rng = ChaCha20Rng::seed_from_u64(0xd0d3c7);
+    let inputs: Vec<(usize, I)> = (0..samples)
+        .map(|_| {
+            let class = rng.gen_range(0..2);
+            (class, input(class, &mut rng))
+        })
+        .collect();
+
+    let timings: Vec<(usize, f64)> = inputs
+        .iter()
+        .map(|(class, input)| {
+            let start = Instant::now();
+            for _ in 0..batch {
+                op(black_box(input));
+            }
+            (*class, start.elapsed().as_nanos() as f64)
+        })
+        .collect();
+
+    let mut sorted: Vec<f64> = timings.iter().map(|&(_, t)| t).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let cutoffs = CROP_PERCENTILES
+        .iter()
+        .map(|p| sorted[((sorted.len() - 1) as f64 * p) as usize])
+        .chain([f64::INFINITY]);
+
+    cutoffs
+        .map(|cutoff| {
+            let mut test = WelchTest::default();
+            for &(class, t) in timings.iter().filter(|&&(_, t)| t <= cutoff) {
+                test.push(class, t);
+            }
+            test.t().abs()
+        })
+        .fold(0.0, f64::max)
+}
+
+fn assert_constant_time(name: &str, t: f64) {
+    println!("{}: max |t| = {:.2}", name, t);
+    assert!(t < LEAK_THRESHOLD, "{} leaks timing: |t| = {:.2}", name, t);
+}
+
+#[test]
+#[ignore = "timing test; run locally, see module docs"]
+fn test_harness_detects_early_exit_comparison() {
+    let secret = [0x5au8; 512];
+    let t = max_t(
+        20_000,
+        16,
+        |class, rng| {
+            let mut guess = secret;
+            if class == 1 {
+                rng.fill_bytes(&mut guess);
+            }
+            guess
+        },
+        |guess| {
+            black_box(guess.iter().zip(&secret).all(|(a, b)| a == b));
+        },
+    );
+    println!("early-exit comparison: max |t| = {:.2}", t);
+    assert!(t > LEAK_THRESHOLD, "harness missed an obvious leak: |t| = {:.2}", t);
+}
+
+#[test]
+#[ignore = "timing test; run locally, see module docs"]
+fn test_field_arithmetic_is_constant_time() {
+    let fixed = Goldilocks::from_u64(0x1234_5678_9abc_def0);
+    let t = max_t(
+        50_000,
+        64,
+        |class, rng| if class == 0 { Goldilocks::ZERO } else { Goldilocks::random(rng) },
+        |&x| {
+            black_box((x * fixed + x) - fixed);
+        },
+    );
+    assert_constant_time("Goldilocks mul/add/sub", t);
+
+    let t = max_t(
+        50_000,
+        64,
+        |class, rng| if class == 0 { 1i64 } else { rng.next_u64() as i64 },
+        |&x| {
+            black_box(FieldElement::from_i64(x));
+        },
+    );
+    assert_constant_time("FieldElement reduction", t);
+
+    let t = max_t(
+        20_000,
+        4,
+        |class, rng| if class == 0 { FieldElement::ONE } else { FieldElement::random(rng) },
+        |&x| {
+            black_box(x.pow(FIELD_EXPONENT));
+        },
+    );
+    assert_constant_time("FieldElement pow", t);
+}
+
+#[test]
+#[ignore = "timing test; run locally, see module docs"]
+fn test_shamir_reconstruction_is_constant_time() {
+    let xs = [FieldElement::from(1u32), FieldElement::from(2u32), FieldElement::from(3u32)];
+    let t = max_t(
+        30_000,
+        8,
+        |class, rng| {
+            // Class 0: the all-zero sharing of secret 0
+            xs.map(|x| (x, if class == 0 { FieldElement::ZERO } else { FieldElement::random(rng) }))
+        },
+        |points| {
+            black_box(interpolate_at(points, FieldElement::ZERO).unwrap());
+        },
+    );
+    assert_constant_time("Shamir reconstruction", t);
+}
+
+#[test]
+#[ignore = "timing test; run locally, see module docs"]
+fn test_share_verification_is_constant_time() {
+    let mut rng = ChaCha20Rng::seed_from_u64(1);
+    let (commitment, shares) = feldman_deal(Scalar25519::from(42u64), 3, 5, &mut rng).unwrap();
+    let valid = shares[&1];
+    let t = max_t(
+        5_000,
+        1,
+        |class, rng| if class == 0 { valid } else { Scalar25519::random(rng) },
+        |&share| {
+            black_box(commitment.verify_share(1, share));
+        },
+    );
+    assert_constant_time("Feldman share verification", t);
+}
@@ -0,0 +1,435 @@
+//! Event Lifecycle State Machine
+//!
+//! Off-chain mirror of `contracts/contracts/FHEOracle.sol`. Each method
+//! applies the same `require` checks as the contract function it is named
+//! after, in the same order, and fails with the contract's revert string,
+//! so a node can predict whether a transaction will succeed and track the
+//! event state without drifting from the chain.
+//!
+//! ```text
+//! Pending ──propose──→ Proposed ──finalize (after window)──→ Finalized
+//!                         │
+//!                  dispute (in window)
+//!                         ↓
+//!                      Disputed ──resolve (after window)──→ Resolved
+//! ```
+//!
+//! This follows the contract, which differs from the diagram in
+//! docs/ARCHITECTURE.md §6.2: a disputed result ends Resolved, not
+//! Finalized. Times are Unix seconds standing in for `block.timestamp`,
+//! and callers are addresses as strings standing in for `msg.sender`.
+//! Like the contract, `resolve_dispute` does not check the jury vote count
+//! against `jury_threshold`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
+/// `DISPUTE_WINDOW = 1 days`
+pub const DISPUTE_WINDOW: u64 = 24 * 60 * 60;
+
+/// Initial `juryThreshold`
+pub const JURY_THRESHOLD: u32 = 3;
+
+/// Largest result `proposeResult` accepts
+pub const MAX_RESULT: u8 = 100;
+
+/// Not in the contract: submissions close this long before the deadline,
+/// leaving time to decrypt, sign and `proposeResult`, which only accepts
+/// proposals up to the deadline
+pub const PROPOSAL_WINDOW: u64 = 60 * 60;
+
+/// When an event with this deadline stops taking submissions
+pub fn submissions_close(deadline: u64) -> u64 {
+    deadline.saturating_sub(PROPOSAL_WINDOW)
+}
+
+/// The contract's `ResultStatus`, in declaration order
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResultStatus {
+    Proposed = 0,
+    Finalized = 1,
+    Disputed = 2,
+    Resolved = 3,
+}
+
+/// Lifecycle state; `Pending` is an event with no proposed result yet
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LifecycleState {
+    Pending,
+    Proposed,
+    Finalized,
+    Disputed,
+    Resolved,
+}
+
+impl LifecycleState {
+    /// Edges the contract allows
+    pub fn can_transition_to(self, next: LifecycleState) -> bool {
+        matches!(
+            (self, next),
+            (LifecycleState::Pending, LifecycleState::Proposed)
+                | (LifecycleState::Proposed, LifecycleState::Finalized)
+                | (LifecycleState::Proposed, LifecycleState::Disputed)
+                | (LifecycleState::Disputed, LifecycleState::Resolved)
+        )
+    }
+
+    pub fn is_terminal(self) -> bool {
+        matches!(self, LifecycleState::Finalized | LifecycleState::Resolved)
+    }
+}
+
+impl From<ResultStatus> for LifecycleState {
+    fn from(status: ResultStatus) -> Self {
+        match status {
+            ResultStatus::Proposed => LifecycleState::Proposed,
+            ResultStatus::Finalized => LifecycleState::Finalized,
+            ResultStatus::Disputed => LifecycleState::Disputed,
+            ResultStatus::Resolved => LifecycleState::Resolved,
+        }
+    }
+}
+
+impl fmt::Display for LifecycleState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LifecycleState::Pending => "PENDING",
+            LifecycleState::Proposed => "PROPOSED",
+            LifecycleState::Finalized => "FINALIZED",
+            LifecycleState::Disputed => "DISPUTED",
+            LifecycleState::Resolved => "RESOLVED",
+        })
+    }
+}
+
+impl FromStr for LifecycleState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "PENDING" => Ok(LifecycleState::Pending),
+            "PROPOSED" => Ok(LifecycleState::Proposed),
+            "FINALIZED" => Ok(LifecycleState::Finalized),
+            "DISPUTED" => Ok(LifecycleState::Disputed),
+            "RESOLVED" => Ok(LifecycleState::Resolved),
+            other => Err(format!("Unknown lifecycle state {:?}", other)),
+        }
+    }
+}
+
+/// `EventResult`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProposedResult {
+    pub status: ResultStatus,
+    pub result: u8,
+    pub proposal_time: u64,
+    pub finalize_time: Option<u64>,
+    pub result_hash: [u8; 32],
+    pub proposer: String,
+}
+
+impl ProposedResult {
+    /// Last second at which the result can be disputed or voted on
+    pub fn dispute_deadline(&self) -> u64 {
+        self.proposal_time.saturating_add(DISPUTE_WINDOW)
+    }
+}
+
+/// `Dispute`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dispute {
+    pub challenger: String,
+    pub reason: String,
+    pub challenge_time: u64,
+}
+
+/// `Event` plus its jury votes
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EventLifecycle {
+    pub description: String,
+    pub creation_time: u64,
+    pub deadline: u64,
+    pub proposed_result: Option<ProposedResult>,
+    pub dispute: Option<Dispute>,
+    /// Jurors who voted, in favour or not
+    pub jury_votes: BTreeSet<String>,
+    /// Votes in favour (`juryVoteCount`)
+    pub jury_vote_count: u32,
+}
+
+impl EventLifecycle {
+    pub fn state(&self) -> LifecycleState {
+        self.proposed_result
+            .as_ref()
+            .map_or(LifecycleState::Pending, |r| r.status.into())
+    }
+}
+
+/// The contract's storage: admin, jury threshold and events by id
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OracleLedger {
+    pub admin: String,
+    pub jury_threshold: u32,
+    events: BTreeMap<String, EventLifecycle>,
+}
+
+impl OracleLedger {
+    /// `constructor`: the deployer becomes admin
+    pub fn new(admin: &str) -> Self {
+        OracleLedger {
+            admin: admin.to_string(),
+            jury_threshold: JURY_THRESHOLD,
+            events: BTreeMap::new(),
+        }
+    }
+
+    pub fn event(&self, event_id: &str) -> Option<&EventLifecycle> {
+        self.events.get(event_id)
+    }
+
+    pub fn state(&self, event_id: &str) -> Option<LifecycleState> {
+        self.event(event_id).map(EventLifecycle::state)
+    }
+
+    /// Ids of events in `state`
+    pub fn events_in(&self, state: LifecycleState) -> Vec<&str> {
+        self.events
+            .iter()
+            .filter(|(_, e)| e.state() == state)
+            .map(|(id, _)| id.as_str())
+            .collect()
+    }
+
+    /// `createEvent`
+    pub fn create_event(
+        &mut self,
+        caller: &str,
+        event_id: &str,
+        description: &str,
+        deadline: u64,
+        now: u64,
+    ) -> Result<(), String> {
+        self.only_admin(caller)?;
+        require(!self.events.contains_key(event_id), "Event already exists")?;
+        require(deadline > now, "Deadline must be in future")?;
+        self.events.insert(
+            event_id.to_string(),
+            EventLifecycle {
+                description: description.to_string(),
+                creation_time: now,
+                deadline,
+                proposed_result: None,
+                dispute: None,
+                jury_votes: BTreeSet::new(),
+                jury_vote_count: 0,
+            },
+        );
+        Ok(())
+    }
+
+    /// `proposeResult`; note the contract only accepts proposals up to the deadline
+    pub fn propose_result(
+        &mut self,
+        caller: &str,
+        event_id: &str,
+        result: u8,
+        result_hash: [u8; 32],
+        now: u64,
+    ) -> Result<(), String> {
+        let event = self.event_mut(event_id)?;
+        require(result <= MAX_RESULT, "Result must be between 0 and 100")?;
+        require(event.proposed_result.is_none(), "Result already proposed")?;
+        require(now <= event.deadline, "Event deadline passed")?;
+        event.proposed_result = Some(ProposedResult {
+            status: ResultStatus::Proposed,
+            result,
+            proposal_time: now,
+            finalize_time: None,
+            result_hash,
+            proposer: caller.to_string(),
+        });
+        Ok(())
+    }
+
+    /// `disputeResult`
+    pub fn dispute_result(&mut self, caller: &str, event_id: &str, reason: &str, now: u64) -> Result<(), String> {
+        let event = self.event_mut(event_id)?;
+        let proposed = event.proposed_result.as_mut().ok_or("No result to dispute")?;
+        require(
+            proposed.status == ResultStatus::Proposed,
+            "Can only dispute proposed results",
+        )?;
+        require(now <= proposed.dispute_deadline(), "Dispute window closed")?;
+        proposed.status = ResultStatus::Disputed;
+        event.dispute = Some(Dispute {
+            challenger: caller.to_string(),
+            reason: reason.to_string(),
+            challenge_time: now,
+        });
+        Ok(())
+    }
+
+    /// `finalizeResult`
+    pub fn finalize_result(&mut self, caller: &str, event_id: &str, now: u64) -> Result<(), String> {
+        self.only_admin(caller)?;
+        let event = self.event_mut(event_id)?;
+        let proposed = event.proposed_result.as_mut().ok_or("No result to finalize")?;
+        require(proposed.status == ResultStatus::Proposed, "Result not in proposed state")?;
+        require(now > proposed.dispute_deadline(), "Dispute window still open")?;
+        proposed.status = ResultStatus::Finalized;
+        proposed.finalize_time = Some(now);
+        Ok(())
+    }
+
+    /// `voteOnDispute`
+    pub fn vote_on_dispute(&mut self, caller: &str, event_id: &str, in_favor: bool, now: u64) -> Result<(), String> {
+        let event = self.event_mut(event_id)?;
+        // The contract reads the default status (Proposed) when nothing was proposed
+        let disputed = event
+            .proposed_result
+            .as_ref()
+            .filter(|r| r.status == ResultStatus::Disputed)
+            .ok_or("Not in dispute phase")?;
+        require(now <= disputed.dispute_deadline(), "Dispute window closed")?;
+        require(!event.jury_votes.contains(caller), "Already voted")?;
+        event.jury_votes.insert(caller.to_string());
+        if in_favor {
+            event.jury_vote_count += 1;
+        }
+        Ok(())
+    }
+
+    /// `resolveDispute`
+    pub fn resolve_dispute(&mut self, caller: &str, event_id: &str, now: u64) -> Result<(), String> {
+        self.only_admin(caller)?;
+        let event = self.event_mut(event_id)?;
+        let disputed = event
+            .proposed_result
+            .as_mut()
+            .filter(|r| r.status == ResultStatus::Disputed)
+            .ok_or("Not in dispute phase")?;
+        require(now > disputed.dispute_deadline(), "Dispute window still open")?;
+        disputed.status = ResultStatus::Resolved;
+        Ok(())
+    }
+
+    fn only_admin(&self, caller: &str) -> Result<(), String> {
+        require(caller == self.admin, "Only admin can call this")
+    }
+
+    /// `eventExists`
+    fn event_mut(&mut self, event_id: &str) -> Result<&mut EventLifecycle, String> {
+        self.events
+            .get_mut(event_id)
+            .ok_or_else(|| "Event does not exist".to_string())
+    }
+}
+
+fn require(condition: bool, reason: &str) -> Result<(), String> {
+    if condition {
+        Ok(())
+    } else {
+        Err(reason.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADMIN: &str = "0xadmin";
+    const PROPOSER: &str = "0xoracle";
+    const CHALLENGER: &str = "0xchallenger";
+    const T0: u64 = 1_729_418_400;
+    const DEADLINE: u64 = T0 + 3600;
+
+    fn proposed() -> OracleLedger {
+        let mut ledger = OracleLedger::new(ADMIN);
+        ledger.create_event(ADMIN, "e1", "ETH > 4000", DEADLINE, T0).unwrap();
+        ledger.propose_result(PROPOSER, "e1", 1, [1; 32], T0 + 60).unwrap();
+        ledger
+    }
+
+    #[test]
+    fn test_create_and_propose_follow_contract_checks() {
+        let mut ledger = OracleLedger::new(ADMIN);
+        let err = |r: Result<(), String>| r.unwrap_err();
+        assert_eq!(err(ledger.create_event(PROPOSER, "e1", "", DEADLINE, T0)), "Only admin can call this");
+        assert_eq!(err(ledger.create_event(ADMIN, "e1", "", T0, T0)), "Deadline must be in future");
+        ledger.create_event(ADMIN, "e1", "ETH > 4000", DEADLINE, T0).unwrap();
+        assert_eq!(err(ledger.create_event(ADMIN, "e1", "", DEADLINE, T0)), "Event already exists");
+        assert_eq!(ledger.state("e1"), Some(LifecycleState::Pending));
+
+        assert_eq!(err(ledger.propose_result(PROPOSER, "nope", 1, [0; 32], T0)), "Event does not exist");
+        assert_eq!(err(ledger.propose_result(PROPOSER, "e1", 101, [0; 32], T0)), "Result must be between 0 and 100");
+        assert_eq!(err(ledger.propose_result(PROPOSER, "e1", 1, [0; 32], DEADLINE + 1)), "Event deadline passed");
+        ledger.propose_result(PROPOSER, "e1", 1, [0; 32], DEADLINE).unwrap();
+        assert_eq!(err(ledger.propose_result(PROPOSER, "e1", 0, [0; 32], T0)), "Result already proposed");
+        assert_eq!(ledger.state("e1"), Some(LifecycleState::Proposed));
+        assert_eq!(ledger.event("e1").unwrap().proposed_result.as_ref().unwrap().proposer, PROPOSER);
+    }
+
+    #[test]
+    fn test_finalize_only_after_dispute_window() {
+        let mut ledger = proposed();
+        let window_end = T0 + 60 + DISPUTE_WINDOW;
+        assert_eq!(ledger.finalize_result(PROPOSER, "e1", window_end + 1).unwrap_err(), "Only admin can call this");
+        assert_eq!(ledger.finalize_result(ADMIN, "e1", window_end).unwrap_err(), "Dispute window still open");
+        ledger.finalize_result(ADMIN, "e1", window_end + 1).unwrap();
+        assert_eq!(ledger.state("e1"), Some(LifecycleState::Finalized));
+
+        assert_eq!(
+            ledger.dispute_result(CHALLENGER, "e1", "late", window_end + 2).unwrap_err(),
+            "Can only dispute proposed results"
+        );
+        assert_eq!(
+            ledger.finalize_result(ADMIN, "e1", window_end + 2).unwrap_err(),
+            "Result not in proposed state"
+        );
+    }
+
+    #[test]
+    fn test_dispute_vote_and_resolve() {
+        let mut ledger = proposed();
+        let window_end = T0 + 60 + DISPUTE_WINDOW;
+        assert_eq!(ledger.vote_on_dispute("0xj1", "e1", true, T0 + 100).unwrap_err(), "Not in dispute phase");
+        assert_eq!(
+            ledger.dispute_result(CHALLENGER, "e1", "wrong", window_end + 1).unwrap_err(),
+            "Dispute window closed"
+        );
+        ledger.dispute_result(CHALLENGER, "e1", "wrong", window_end).unwrap();
+        assert_eq!(ledger.events_in(LifecycleState::Disputed), vec!["e1"]);
+        assert_eq!(
+            ledger.finalize_result(ADMIN, "e1", window_end + 1).unwrap_err(),
+            "Result not in proposed state"
+        );
+
+        ledger.vote_on_dispute("0xj1", "e1", true, window_end).unwrap();
+        ledger.vote_on_dispute("0xj2", "e1", false, window_end).unwrap();
+        assert_eq!(ledger.vote_on_dispute("0xj1", "e1", false, window_end).unwrap_err(), "Already voted");
+        assert_eq!(ledger.vote_on_dispute("0xj3", "e1", true, window_end + 1).unwrap_err(), "Dispute window closed");
+        assert_eq!(ledger.event("e1").unwrap().jury_vote_count, 1);
+
+        assert_eq!(ledger.resolve_dispute(ADMIN, "e1", window_end).unwrap_err(), "Dispute window still open");
+        ledger.resolve_dispute(ADMIN, "e1", window_end + 1).unwrap();
+        assert_eq!(ledger.state("e1"), Some(LifecycleState::Resolved));
+        assert_eq!(ledger.resolve_dispute(ADMIN, "e1", window_end + 2).unwrap_err(), "Not in dispute phase");
+    }
+
+    #[test]
+    fn test_transition_table_matches_contract() {
+        use LifecycleState::*;
+        let all = [Pending, Proposed, Finalized, Disputed, Resolved];
+        let allowed: Vec<(LifecycleState, LifecycleState)> = all
+            .iter()
+            .flat_map(|&a| all.iter().map(move |&b| (a, b)))
+            .filter(|&(a, b)| a.can_transition_to(b))
+            .collect();
+        assert_eq!(
+            allowed,
+            vec![(Pending, Proposed), (Proposed, Finalized), (Proposed, Disputed), (Disputed, Resolved)]
+        );
+        assert!(all.iter().filter(|s| s.is_terminal()).all(|s| all.iter().all(|&n| !s.can_transition_to(n))));
+    }
+}
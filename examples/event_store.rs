@@ -0,0 +1,610 @@
+//! Persistent Event Store
+//!
+//! Append-only JSON-lines log of everything the coordinator does to an
+//! event: opening it, accepted submissions, the aggregate ciphertext, each
+//! decryption round, every valid partial decryption and failure, and the
+//! signed result. The in-memory view is rebuilt by replaying the log, so a
+//! coordinator that restarts mid-round picks up where it stopped: it keeps
+//! the partial decryptions it already has and continues with a round number
+//! the nodes have not seen yet. Responses whose signing nonces may have been
+//! spent in a failed signing round are discarded, so they are not reused.
+//!
+//! The first line is a format header. Each record is written and synced
+//! before the coordinator acts on it. A crash mid-write leaves a torn last
+//! line without a newline; opening the log drops it. Any other unreadable
+//! line is an error.
+
+use crate::coordinator::{EncryptedSubmission, OracleEvent, RoundFailure, SignedOracleResult};
+use crate::decryptor_service::{signature_from_hex, DecryptRequest, DecryptResponse, ServiceError};
+use crate::share_storage::{from_hex, to_hex};
+use crate::threshold_signature::OracleResult;
+use crate::threshold_lwe::LweCiphertext;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+const LOG_FORMAT: &str = "bsfo-event-log";
+const LOG_VERSION: u8 = 3;
+
+/// Where an event is in the coordinator's pipeline
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EventState {
+    /// Accepting submissions
+    Collecting,
+    /// Aggregate published; decryption pending or in progress
+    Aggregated,
+    /// Signed result produced
+    Decided,
+}
+
+impl fmt::Display for EventState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            EventState::Collecting => "collecting",
+            EventState::Aggregated => "aggregated",
+            EventState::Decided => "decided",
+        })
+    }
+}
+
+/// Everything recorded about one event
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoredEvent {
+    pub event: OracleEvent,
+    pub submissions: Vec<EncryptedSubmission>,
+    pub aggregate: Option<LweCiphertext>,
+    /// Last decryption round number used; rounds only move forward
+    pub round: u64,
+    /// Valid responses so far, by decryptor id, with the request answered
+    pub partials: BTreeMap<u32, (DecryptRequest, DecryptResponse)>,
+    pub failures: Vec<RoundFailure>,
+    pub result: Option<SignedOracleResult>,
+}
+
+impl StoredEvent {
+    pub fn state(&self) -> EventState {
+        match (&self.aggregate, &self.result) {
+            (_, Some(_)) => EventState::Decided,
+            (Some(_), None) => EventState::Aggregated,
+            (None, None) => EventState::Collecting,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct LogHeader {
+    format: String,
+    version: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "record", rename_all = "snake_case")]
+enum Record {
+    EventOpened {
+        event_id: String,
+        threshold: i64,
+        deadline: u64,
+    },
+    SubmissionAccepted {
+        event_id: String,
+        provider_id: String,
+        ciphertext: String,
+        weight: i64,
+        timestamp: u64,
+        signature: String,
+    },
+    Aggregated {
+        event_id: String,
+        aggregate: String,
+    },
+    RoundStarted {
+        event_id: String,
+        round: u64,
+    },
+    PartialAccepted {
+        event_id: String,
+        request: Box<DecryptRequest>,
+        response: Box<DecryptResponse>,
+    },
+    RoundFailed {
+        event_id: String,
+        decryptor: u32,
+        round: u64,
+        status: u16,
+        error: String,
+    },
+    PartialsDiscarded {
+        event_id: String,
+    },
+    ResultSigned {
+        event_id: String,
+        aggregate_value: i64,
+        result: String,
+        decryptor_set: Vec<u32>,
+        signature: String,
+    },
+}
+
+impl Record {
+    fn event_id(&self) -> &str {
+        match self {
+            Record::EventOpened { event_id, .. }
+            | Record::SubmissionAccepted { event_id, .. }
+            | Record::Aggregated { event_id, .. }
+            | Record::RoundStarted { event_id, .. }
+            | Record::PartialAccepted { event_id, .. }
+            | Record::RoundFailed { event_id, .. }
+            | Record::PartialsDiscarded { event_id }
+            | Record::ResultSigned { event_id, .. } => event_id,
+        }
+    }
+}
+
+fn ciphertext_from_hex(hex: &str) -> Result<LweCiphertext, String> {
+    LweCiphertext::from_bytes(&from_hex(hex)?)
+}
+
+/// Events by id, backed by an append-only log or kept in memory only
+#[derive(Debug, Default)]
+pub struct EventStore {
+    log: Option<File>,
+    events: BTreeMap<String, StoredEvent>,
+}
+
+impl EventStore {
+    /// A store that forgets everything when dropped
+    pub fn in_memory() -> Self {
+        EventStore::default()
+    }
+
+    /// Open or create the log at `path` and replay it
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let io_error = |e: std::io::Error| format!("Cannot open {}: {}", path.display(), e);
+        let mut store = EventStore::default();
+        if path.exists() {
+            let contents = fs::read(path).map_err(io_error)?;
+            // Keep complete lines only; a torn tail is a write the crash interrupted
+            let complete = contents.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+            if complete < contents.len() {
+                OpenOptions::new()
+                    .write(true)
+                    .open(path)
+                    .and_then(|file| file.set_len(complete as u64))
+                    .map_err(io_error)?;
+            }
+            let text = std::str::from_utf8(&contents[..complete]).map_err(|_| "Event log is not UTF-8".to_string())?;
+            let mut lines = text.lines().enumerate();
+            if let Some((_, header)) = lines.next() {
+                check_header(header)?;
+            }
+            for (number, line) in lines {
+                let record: Record = serde_json::from_str(line)
+                    .map_err(|e| format!("Event log line {} is unreadable: {}", number + 1, e))?;
+                store
+                    .apply(record)
+                    .map_err(|e| format!("Event log line {}: {}", number + 1, e))?;
+            }
+        }
+
+        let mut log = OpenOptions::new().create(true).append(true).open(path).map_err(io_error)?;
+        if log.metadata().map_err(io_error)?.len() == 0 {
+            let header = LogHeader {
+                format: LOG_FORMAT.to_string(),
+                version: LOG_VERSION,
+            };
+            let line = serde_json::to_string(&header).map_err(|e| e.to_string())?;
+            writeln!(log, "{}", line).and_then(|_| log.sync_data()).map_err(io_error)?;
+        }
+        store.log = Some(log);
+        Ok(store)
+    }
+
+    pub fn event(&self, event_id: &str) -> Option<&StoredEvent> {
+        self.events.get(event_id)
+    }
+
+    /// Events in `state`, by id
+    pub fn list_events(&self, state: EventState) -> Vec<&StoredEvent> {
+        self.events.values().filter(|e| e.state() == state).collect()
+    }
+
+    /// Events whose decryption round has to be resumed
+    pub fn rounds_to_resume(&self) -> Vec<&StoredEvent> {
+        self.list_events(EventState::Aggregated)
+    }
+
+    pub(crate) fn open_event(&mut self, event: &OracleEvent) -> Result<(), String> {
+        self.commit(Record::EventOpened {
+            event_id: event.event_id.clone(),
+            threshold: event.threshold,
+            deadline: event.deadline,
+        })
+    }
+
+    pub(crate) fn add_submission(&mut self, event_id: &str, submission: &EncryptedSubmission) -> Result<(), String> {
+        self.commit(Record::SubmissionAccepted {
+            event_id: event_id.to_string(),
+            provider_id: submission.provider_id.clone(),
+            ciphertext: to_hex(&submission.ciphertext.to_bytes()),
+            weight: submission.weight,
+            timestamp: submission.timestamp,
+            signature: to_hex(&submission.signature.to_bytes()),
+        })
+    }
+
+    pub(crate) fn set_aggregate(&mut self, event_id: &str, aggregate: &LweCiphertext) -> Result<(), String> {
+        self.commit(Record::Aggregated {
+            event_id: event_id.to_string(),
+            aggregate: to_hex(&aggregate.to_bytes()),
+        })
+    }
+
+    /// Record and return the next round number for the event
+    pub(crate) fn start_round(&mut self, event_id: &str) -> Result<u64, String> {
+        let round = self.get(event_id)?.round + 1;
+        self.commit(Record::RoundStarted {
+            event_id: event_id.to_string(),
+            round,
+        })?;
+        Ok(round)
+    }
+
+    pub(crate) fn add_partial(
+        &mut self,
+        event_id: &str,
+        request: &DecryptRequest,
+        response: &DecryptResponse,
+    ) -> Result<(), String> {
+        self.commit(Record::PartialAccepted {
+            event_id: event_id.to_string(),
+            request: Box::new(request.clone()),
+            response: Box::new(response.clone()),
+        })
+    }
+
+    pub(crate) fn add_failure(&mut self, event_id: &str, failure: &RoundFailure) -> Result<(), String> {
+        self.commit(Record::RoundFailed {
+            event_id: event_id.to_string(),
+            decryptor: failure.id,
+            round: failure.round,
+            status: failure.error.status(),
+            error: failure.error.to_string(),
+        })
+    }
+
+    /// Drop every response kept so far; the next round asks all nodes again
+    pub(crate) fn discard_partials(&mut self, event_id: &str) -> Result<(), String> {
+        self.commit(Record::PartialsDiscarded {
+            event_id: event_id.to_string(),
+        })
+    }
+
+    pub(crate) fn set_result(&mut self, event_id: &str, signed: &SignedOracleResult) -> Result<(), String> {
+        self.commit(Record::ResultSigned {
+            event_id: event_id.to_string(),
+            aggregate_value: signed.result.aggregate_value,
+            result: signed.result.result.clone(),
+            decryptor_set: signed.result.decryptor_set.clone(),
+            signature: to_hex(&signed.signature.to_bytes()),
+        })
+    }
+
+    fn get(&self, event_id: &str) -> Result<&StoredEvent, String> {
+        self.events
+            .get(event_id)
+            .ok_or_else(|| format!("Unknown event {}", event_id))
+    }
+
+    fn get_mut(&mut self, event_id: &str) -> Result<&mut StoredEvent, String> {
+        self.events
+            .get_mut(event_id)
+            .ok_or_else(|| format!("Unknown event {}", event_id))
+    }
+
+    /// Validate, write and sync the record, then apply it
+    fn commit(&mut self, record: Record) -> Result<(), String> {
+        self.check(&record)?;
+        if let Some(log) = &mut self.log {
+            let line = serde_json::to_string(&record).map_err(|e| e.to_string())?;
+            writeln!(log, "{}", line)
+                .and_then(|_| log.sync_data())
+                .map_err(|e| format!("Cannot append to event log: {}", e))?;
+        }
+        self.apply(record)
+    }
+
+    fn check(&self, record: &Record) -> Result<(), String> {
+        let exists = self.events.contains_key(record.event_id());
+        match record {
+            Record::EventOpened { event_id, .. } if exists => Err(format!("Event {} already exists", event_id)),
+            Record::EventOpened { .. } => Ok(()),
+            _ => self.get(record.event_id()).map(|_| ()),
+        }
+    }
+
+    fn apply(&mut self, record: Record) -> Result<(), String> {
+        self.check(&record)?;
+        match record {
+            Record::EventOpened {
+                event_id,
+                threshold,
+                deadline,
+            } => {
+                self.events.insert(
+                    event_id.clone(),
+                    StoredEvent {
+                        event: OracleEvent {
+                            event_id,
+                            threshold,
+                            deadline,
+                        },
+                        submissions: Vec::new(),
+                        aggregate: None,
+                        round: 0,
+                        partials: BTreeMap::new(),
+                        failures: Vec::new(),
+                        result: None,
+                    },
+                );
+            }
+            Record::SubmissionAccepted {
+                event_id,
+                provider_id,
+                ciphertext,
+                weight,
+                timestamp,
+                signature,
+            } => {
+                let ciphertext = ciphertext_from_hex(&ciphertext)?;
+                let signature = signature_from_hex(&signature)?;
+                self.get_mut(&event_id)?.submissions.push(EncryptedSubmission {
+                    provider_id,
+                    ciphertext,
+                    weight,
+                    timestamp,
+                    signature,
+                });
+            }
+            Record::Aggregated { event_id, aggregate } => {
+                self.get_mut(&event_id)?.aggregate = Some(ciphertext_from_hex(&aggregate)?);
+            }
+            Record::RoundStarted { event_id, round } => {
+                let stored = self.get_mut(&event_id)?;
+                stored.round = stored.round.max(round);
+            }
+            Record::PartialAccepted {
+                event_id,
+                request,
+                response,
+            } => {
+                self.get_mut(&event_id)?
+                    .partials
+                    .insert(response.id, (*request, *response));
+            }
+            Record::RoundFailed {
+                event_id,
+                decryptor,
+                round,
+                status,
+                error,
+            } => {
+                self.get_mut(&event_id)?.failures.push(RoundFailure {
+                    id: decryptor,
+                    round,
+                    error: ServiceError::from_status(status, error),
+                });
+            }
+            Record::PartialsDiscarded { event_id } => {
+                self.get_mut(&event_id)?.partials.clear();
+            }
+            Record::ResultSigned {
+                event_id,
+                aggregate_value,
+                result,
+                decryptor_set,
+                signature,
+            } => {
+                let signature = signature_from_hex(&signature)?;
+                let stored = self.get_mut(&event_id)?;
+                let evidence = decryptor_set
+                    .iter()
+                    .map(|id| {
+                        stored
+                            .partials
+                            .get(id)
+                            .cloned()
+                            .ok_or_else(|| format!("Result cites decryptor {} without a partial", id))
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+                stored.result = Some(SignedOracleResult {
+                    result: OracleResult {
+                        event_id,
+                        aggregate_value,
+                        threshold: stored.event.threshold,
+                        result,
+                        decryptor_set,
+                    },
+                    signature,
+                    evidence,
+                    failures: stored.failures.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_header(line: &str) -> Result<(), String> {
+    let header: LogHeader = serde_json::from_str(line).map_err(|_| "Missing event log header".to_string())?;
+    if header.format != LOG_FORMAT {
+        return Err(format!("Not an event log: {:?}", header.format));
+    }
+    if header.version != LOG_VERSION {
+        return Err(format!("Unsupported event log version {}", header.version));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregation::ProviderSubmission;
+    use crate::threshold_lwe::{LweFhe, LweParams, LweSecretKey, PartialDecryption};
+    use crate::decryptor_service::QuorumPartial;
+    use crate::field::{Mersenne127, PrimeField};
+    use crate::threshold_signature::{commit, SigningKey};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use std::path::PathBuf;
+
+    fn temp_log(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("blocksense-{}-{}.log", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn event(id: &str) -> OracleEvent {
+        OracleEvent {
+            event_id: id.to_string(),
+            threshold: 100,
+            deadline: 1_000,
+        }
+    }
+
+    fn submission(provider: &str, rng: &mut ChaCha20Rng) -> EncryptedSubmission {
+        let params = LweParams {
+            dimension: 16,
+            ..LweParams::default()
+        };
+        let backend = LweFhe::new(params, LweSecretKey::generate(&params, rng)).unwrap();
+        let observation = ProviderSubmission {
+            provider_id: provider.to_string(),
+            quantized_value: -60,
+            weight: 2,
+            timestamp: 900,
+        };
+        let key = SigningKey::generate(rng);
+        EncryptedSubmission::encrypt(&key, "e1", &observation, &backend, rng).unwrap()
+    }
+
+    #[test]
+    fn test_log_replays_after_reopen() {
+        let path = temp_log("events-replay");
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let a = submission("A", &mut rng);
+        {
+            let mut store = EventStore::open(&path).unwrap();
+            store.open_event(&event("e1")).unwrap();
+            store.open_event(&event("e2")).unwrap();
+            store.add_submission("e1", &a).unwrap();
+            store.set_aggregate("e1", &a.ciphertext.scalar_mul(2)).unwrap();
+            assert_eq!(store.start_round("e1").unwrap(), 1);
+            assert_eq!(store.start_round("e1").unwrap(), 2);
+            store
+                .add_failure(
+                    "e1",
+                    &RoundFailure {
+                        id: 4,
+                        round: 2,
+                        error: ServiceError::RateLimited,
+                    },
+                )
+                .unwrap();
+            assert!(store.open_event(&event("e1")).is_err());
+            assert!(store.add_submission("missing", &a).is_err());
+        }
+
+        let store = EventStore::open(&path).unwrap();
+        let e1 = store.event("e1").unwrap();
+        assert_eq!(e1.submissions, vec![a.clone()]);
+        assert_eq!(e1.aggregate, Some(a.ciphertext.scalar_mul(2)));
+        assert_eq!(e1.round, 2);
+        assert_eq!(e1.failures[0].error, ServiceError::RateLimited);
+        assert_eq!(store.rounds_to_resume().len(), 1);
+        let collecting: Vec<&str> = store
+            .list_events(EventState::Collecting)
+            .iter()
+            .map(|e| e.event.event_id.as_str())
+            .collect();
+        assert_eq!(collecting, vec!["e2"]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_signed_result_round_trips() {
+        let path = temp_log("events-result");
+        let mut rng = ChaCha20Rng::seed_from_u64(2);
+        let coordinator = SigningKey::generate(&mut rng);
+        let a = submission("A", &mut rng);
+        let ct = a.ciphertext.scalar_mul(2);
+        let request = DecryptRequest::new(&coordinator, "e1", 1, ct.clone(), vec![a], &mut rng);
+        let node = SigningKey::generate(&mut rng);
+        let response = DecryptResponse {
+            id: 7,
+            partials: vec![QuorumPartial {
+                quorum: vec![7],
+                partial: PartialDecryption {
+                    id: 7,
+                    value: Mersenne127::from_u64(5),
+                },
+            }],
+            commitments: commit(7, &mut rng).1,
+            signature: node.sign(b"response", &mut rng),
+        };
+        let result = OracleResult {
+            event_id: "e1".to_string(),
+            aggregate_value: 5,
+            threshold: 100,
+            result: "NO".to_string(),
+            decryptor_set: vec![7],
+        };
+        let signed = SignedOracleResult {
+            signature: coordinator.sign(&result.canonical_bytes(), &mut rng),
+            result,
+            evidence: vec![(request.clone(), response.clone())],
+            failures: Vec::new(),
+        };
+        {
+            let mut store = EventStore::open(&path).unwrap();
+            store.open_event(&event("e1")).unwrap();
+            store.set_aggregate("e1", &ct).unwrap();
+            store.add_partial("e1", &request, &response).unwrap();
+            // Responses from a failed signing round are not kept
+            store.discard_partials("e1").unwrap();
+            assert!(store.event("e1").unwrap().partials.is_empty());
+            store.add_partial("e1", &request, &response).unwrap();
+            store.set_result("e1", &signed).unwrap();
+        }
+        let store = EventStore::open(&path).unwrap();
+        assert_eq!(store.event("e1").unwrap().result.as_ref(), Some(&signed));
+        assert_eq!(store.list_events(EventState::Decided).len(), 1);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_torn_tail_is_dropped_and_corruption_rejected() {
+        let path = temp_log("events-torn");
+        {
+            let mut store = EventStore::open(&path).unwrap();
+            store.open_event(&event("e1")).unwrap();
+        }
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"record":"event_opened","event_id":"e2","thr"#).unwrap();
+        drop(file);
+
+        let mut store = EventStore::open(&path).unwrap();
+        assert!(store.event("e2").is_none());
+        store.open_event(&event("e2")).unwrap();
+        drop(store);
+        assert_eq!(EventStore::open(&path).unwrap().list_events(EventState::Collecting).len(), 2);
+
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"garbage\n").unwrap();
+        drop(file);
+        assert!(EventStore::open(&path).unwrap_err().contains("line 4"));
+        fs::remove_file(&path).unwrap();
+    }
+}
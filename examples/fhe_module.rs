@@ -1,7 +1,7 @@
-/// TFHE FHE Module
-/// 
-/// Provides abstraction for homomorphic encryption operations
-/// Can be swapped between Mock and Real TFHE implementations
+//! TFHE FHE Module
+//! 
+//! Provides abstraction for homomorphic encryption operations
+//! Can be swapped between Mock and Real TFHE implementations
 
 use std::fmt;
 
@@ -30,6 +30,9 @@ pub trait FheBackend: Send + Sync {
     /// Homomorphic scalar multiplication: CT * scalar (stays encrypted)
     fn scalar_mul(&self, ct: &FheCiphertext, scalar: i64) -> FheCiphertext;
     
+    /// Homomorphic multiplication: CT_a * CT_b (stays encrypted)
+    fn mul(&self, ct_a: &FheCiphertext, ct_b: &FheCiphertext) -> FheCiphertext;
+    
     /// Homomorphic subtraction: CT_a - CT_b (stays encrypted)
     fn sub(&self, ct_a: &FheCiphertext, ct_b: &FheCiphertext) -> FheCiphertext;
     
@@ -37,6 +40,15 @@ pub trait FheBackend: Send + Sync {
     fn gt(&self, ct_a: &FheCiphertext, ct_b: &FheCiphertext) -> FheCiphertext;
 }
 
+/// Decrypts results for the reveal step
+///
+/// Implemented by a threshold committee that combines partial decryptions
+/// (see `crate::threshold_lwe`), never by a backend holding the full key.
+/// `MockFhe` is the one exception: its ciphertexts are plaintext anyway.
+pub trait ResultDecryptor {
+    fn decrypt_result(&self, ct: &FheCiphertext) -> Result<i64, String>;
+}
+
 /// Mock FHE Implementation (for testing without real TFHE)
 pub struct MockFhe;
 
@@ -64,6 +76,12 @@ impl FheBackend for MockFhe {
         self.encrypt(pt * scalar)
     }
     
+    fn mul(&self, ct_a: &FheCiphertext, ct_b: &FheCiphertext) -> FheCiphertext {
+        let a = self.decrypt(ct_a);
+        let b = self.decrypt(ct_b);
+        self.encrypt(a * b)
+    }
+    
     fn sub(&self, ct_a: &FheCiphertext, ct_b: &FheCiphertext) -> FheCiphertext {
         let a = self.decrypt(ct_a);
         let b = self.decrypt(ct_b);
@@ -78,6 +96,12 @@ impl FheBackend for MockFhe {
     }
 }
 
+impl ResultDecryptor for MockFhe {
+    fn decrypt_result(&self, ct: &FheCiphertext) -> Result<i64, String> {
+        Ok(self.decrypt(ct))
+    }
+}
+
 /// Real TFHE Implementation (using TFHE-rs library)
 pub struct RealTfhe {
     // Client key would be stored here
@@ -111,6 +135,12 @@ impl FheBackend for RealTfhe {
         mock.scalar_mul(ct, scalar)
     }
     
+    fn mul(&self, ct_a: &FheCiphertext, ct_b: &FheCiphertext) -> FheCiphertext {
+        // Real implementation would use TFHE-rs
+        let mock = MockFhe;
+        mock.mul(ct_a, ct_b)
+    }
+    
     fn sub(&self, ct_a: &FheCiphertext, ct_b: &FheCiphertext) -> FheCiphertext {
         // Real implementation would use TFHE-rs
         let mock = MockFhe;
@@ -146,6 +176,11 @@ impl FheBackend for RealTfhe {
         mock.scalar_mul(ct, scalar)
     }
     
+    fn mul(&self, ct_a: &FheCiphertext, ct_b: &FheCiphertext) -> FheCiphertext {
+        let mock = MockFhe;
+        mock.mul(ct_a, ct_b)
+    }
+    
     fn sub(&self, ct_a: &FheCiphertext, ct_b: &FheCiphertext) -> FheCiphertext {
         let mock = MockFhe;
         mock.sub(ct_a, ct_b)
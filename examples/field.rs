@@ -0,0 +1,882 @@
+//! Prime Field Arithmetic
+//!
+//! Field-element types used by every secret-sharing scheme: operator
+//! overloads, inversion, exponentiation and batch Lagrange interpolation at
+//! arbitrary points. Sharing code is generic over [`PrimeField`]:
+//!
+//! - [`FieldElement`]: p = 10^9 + 7, the original demo field
+//! - [`Goldilocks`]: p = 2^64 − 2^32 + 1, holds TFHE secret-key coefficients
+//! - [`Mersenne127`]: p = 2^127 − 1, holds 64-bit aggregates and 126-bit keys
+//! - [`Scalar25519`]: the Ristretto255 group order (~2^252), same size class
+//!   as 2^255 − 19 and usable with group commitments
+//!
+//! Arithmetic is constant time: reduction, carries and signs are handled
+//! with masks instead of branches or hardware division, so share values and
+//! secrets do not leak through timing. Only `inverse` reveals whether its
+//! input was zero.
+
+use curve25519_dalek::Scalar;
+use rand::{CryptoRng, RngCore};
+use std::fmt;
+use std::hash::Hash;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+use zeroize::Zeroize;
+
+/// Prime modulus for field arithmetic (common in cryptography)
+pub const FIELD_PRIME: u64 = 1_000_000_007;
+
+/// Goldilocks prime 2^64 − 2^32 + 1
+pub const GOLDILOCKS_PRIME: u64 = 0xffff_ffff_0000_0001;
+
+/// Mersenne prime 2^127 − 1
+pub const MERSENNE_127_PRIME: u128 = (1 << 127) - 1;
+
+/// Arithmetic shared by every field a secret can be shared over
+pub trait PrimeField:
+    Copy
+    + fmt::Debug
+    + fmt::Display
+    + Eq
+    + Hash
+    + Send
+    + Sync
+    + 'static
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + Sum
+    + Product
+    + Zeroize
+    + ConstantTimeEq
+    + ConditionallySelectable
+{
+    const ZERO: Self;
+    const ONE: Self;
+    /// Bit length of the modulus
+    const MODULUS_BITS: u32;
+
+    fn from_u64(value: u64) -> Self;
+
+    /// Multiplicative inverse; None for zero
+    fn inverse(self) -> Option<Self>;
+
+    /// Uniformly random element
+    fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self;
+
+    /// Signed integers map to p − |value| when negative
+    fn from_i64(value: i64) -> Self {
+        let magnitude = Self::from_u64(value.unsigned_abs());
+        let negative = Choice::from((value as u64 >> 63) as u8);
+        Self::conditional_select(&magnitude, &-magnitude, negative)
+    }
+
+    fn is_zero(self) -> bool {
+        self.ct_eq(&Self::ZERO).into()
+    }
+
+    /// Square-and-multiply over all 64 exponent bits, so the running time
+    /// does not depend on the exponent either
+    fn pow(self, exp: u64) -> Self {
+        let mut base = self;
+        let mut result = Self::ONE;
+        for bit in 0..u64::BITS {
+            let product = result * base;
+            result = Self::conditional_select(&result, &product, Choice::from(((exp >> bit) & 1) as u8));
+            base *= base;
+        }
+        result
+    }
+}
+
+/// All-ones when `flag` is set, zero otherwise
+///
+/// Goes through `Choice`, whose optimisation barrier stops the compiler
+/// from turning the masked selects back into branches.
+#[inline]
+fn mask_u128(flag: bool) -> u128 {
+    u128::conditional_select(&0, &u128::MAX, Choice::from(flag as u8))
+}
+
+/// High 128 bits of the 256-bit product a·b
+#[inline]
+fn mul_hi_u128(a: u128, b: u128) -> u128 {
+    let mask = u64::MAX as u128;
+    let (a0, a1) = (a & mask, a >> 64);
+    let (b0, b1) = (b & mask, b >> 64);
+
+    let (lo, mid_a, mid_b) = (a0 * b0, a1 * b0, a0 * b1);
+    let carry = ((lo >> 64) + (mid_a & mask) + (mid_b & mask)) >> 64;
+    a1 * b1 + (mid_a >> 64) + (mid_b >> 64) + carry
+}
+
+/// Implements the operator traits shared by all field types in terms of
+/// inherent `add_elem`/`sub_elem`/`mul_elem`/`neg_elem`
+macro_rules! impl_field_ops {
+    ($ty:ty $(, const $p:ident: $pt:ty)?) => {
+        impl$(<const $p: $pt>)? Add for $ty {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                self.add_elem(rhs)
+            }
+        }
+
+        impl$(<const $p: $pt>)? Sub for $ty {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                self.sub_elem(rhs)
+            }
+        }
+
+        impl$(<const $p: $pt>)? Mul for $ty {
+            type Output = Self;
+            fn mul(self, rhs: Self) -> Self {
+                self.mul_elem(rhs)
+            }
+        }
+
+        impl$(<const $p: $pt>)? Div for $ty {
+            type Output = Self;
+            /// Panics on division by zero, like integer division
+            fn div(self, rhs: Self) -> Self {
+                self.mul_elem(
+                    PrimeField::inverse(rhs).expect("division by zero in prime field"),
+                )
+            }
+        }
+
+        impl$(<const $p: $pt>)? Neg for $ty {
+            type Output = Self;
+            fn neg(self) -> Self {
+                self.neg_elem()
+            }
+        }
+
+        impl$(<const $p: $pt>)? AddAssign for $ty {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl$(<const $p: $pt>)? SubAssign for $ty {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl$(<const $p: $pt>)? MulAssign for $ty {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl$(<const $p: $pt>)? Sum for $ty {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(<Self as PrimeField>::ZERO, |acc, x| acc + x)
+            }
+        }
+
+        impl$(<const $p: $pt>)? Product for $ty {
+            fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(<Self as PrimeField>::ONE, |acc, x| acc * x)
+            }
+        }
+
+        impl$(<const $p: $pt>)? From<u64> for $ty {
+            fn from(value: u64) -> Self {
+                <Self as PrimeField>::from_u64(value)
+            }
+        }
+
+        impl$(<const $p: $pt>)? From<u32> for $ty {
+            fn from(value: u32) -> Self {
+                <Self as PrimeField>::from_u64(value as u64)
+            }
+        }
+
+        impl$(<const $p: $pt>)? From<i64> for $ty {
+            fn from(value: i64) -> Self {
+                <Self as PrimeField>::from_i64(value)
+            }
+        }
+    };
+}
+
+/// Element of GF(P) for a prime P < 2^64, kept in canonical form [0, P)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Fp64<const P: u64>(u64);
+
+/// Element of GF(FIELD_PRIME)
+pub type FieldElement = Fp64<FIELD_PRIME>;
+
+/// Element of the Goldilocks field; every 32-bit value and every binary or
+/// small-signed TFHE key coefficient embeds without wrap-around
+pub type Goldilocks = Fp64<GOLDILOCKS_PRIME>;
+
+impl<const P: u64> Fp64<P> {
+    pub const ZERO: Self = Fp64(0);
+    pub const ONE: Self = Fp64(1);
+
+    /// ⌊2^128 / P⌋ for Barrett reduction (P is odd, so no rounding issue)
+    const BARRETT: u128 = u128::MAX / P as u128;
+
+    /// Reduce an unsigned integer into the field
+    pub fn new(value: u64) -> Self {
+        Fp64(Self::reduce(value as u128))
+    }
+
+    /// Reduce a signed integer into the field (-1 ↦ p - 1)
+    pub fn from_i64(value: i64) -> Self {
+        PrimeField::from_i64(value)
+    }
+
+    /// x mod P without division: the Barrett quotient is at most one short,
+    /// so one masked subtraction finishes the reduction
+    fn reduce(x: u128) -> u64 {
+        let quotient = mul_hi_u128(x, Self::BARRETT);
+        let r = x - quotient * P as u128; // r < 2P
+        let (reduced, borrow) = r.overflowing_sub(P as u128);
+        let keep = mask_u128(borrow);
+        ((r & keep) | (reduced & !keep)) as u64
+    }
+
+    /// Canonical representative in [0, p)
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Square-and-multiply exponentiation
+    pub fn pow(self, exp: u64) -> Self {
+        PrimeField::pow(self, exp)
+    }
+
+    /// Multiplicative inverse via Fermat's little theorem: a^(p-2)
+    /// Returns None for zero
+    pub fn inverse(self) -> Option<Self> {
+        PrimeField::inverse(self)
+    }
+
+    fn add_elem(self, rhs: Self) -> Self {
+        // a + b < 2p may exceed u64 for p > 2^63, so work in u128
+        let sum = self.0 as u128 + rhs.0 as u128;
+        let (reduced, borrow) = sum.overflowing_sub(P as u128);
+        let keep = mask_u128(borrow);
+        Fp64(((sum & keep) | (reduced & !keep)) as u64)
+    }
+
+    fn sub_elem(self, rhs: Self) -> Self {
+        let (diff, borrow) = self.0.overflowing_sub(rhs.0);
+        Fp64(diff.wrapping_add(P & mask_u128(borrow) as u64))
+    }
+
+    fn mul_elem(self, rhs: Self) -> Self {
+        Fp64(Self::reduce(self.0 as u128 * rhs.0 as u128))
+    }
+
+    fn neg_elem(self) -> Self {
+        Self::ZERO.sub_elem(self)
+    }
+}
+
+impl<const P: u64> PrimeField for Fp64<P> {
+    const ZERO: Self = Fp64(0);
+    const ONE: Self = Fp64(1);
+    const MODULUS_BITS: u32 = u64::BITS - P.leading_zeros();
+
+    fn from_u64(value: u64) -> Self {
+        Fp64::new(value)
+    }
+
+    fn inverse(self) -> Option<Self> {
+        let inverse = PrimeField::pow(self, P - 2);
+        (!PrimeField::is_zero(self)).then_some(inverse)
+    }
+
+    /// Rejection sampling on MODULUS_BITS-bit candidates
+    fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        loop {
+            let candidate = rng.next_u64() >> (u64::BITS - Self::MODULUS_BITS);
+            if candidate < P {
+                return Fp64(candidate);
+            }
+        }
+    }
+}
+
+impl<const P: u64> ConstantTimeEq for Fp64<P> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl<const P: u64> ConditionallySelectable for Fp64<P> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Fp64(u64::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl<const P: u64> Zeroize for Fp64<P> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl_field_ops!(Fp64<P>, const P: u64);
+
+impl<const P: u64> fmt::Display for Fp64<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Element of GF(2^127 − 1), kept in canonical form [0, p)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Mersenne127(u128);
+
+impl Mersenne127 {
+    /// Reduce an unsigned 128-bit integer into the field
+    pub fn new(value: u128) -> Self {
+        Mersenne127(Self::reduce(value))
+    }
+
+    pub fn value(&self) -> u128 {
+        self.0
+    }
+
+    /// x mod (2^127 − 1) using 2^127 ≡ 1
+    fn reduce(x: u128) -> u128 {
+        let folded = (x & MERSENNE_127_PRIME) + (x >> 127);
+        let (reduced, borrow) = folded.overflowing_sub(MERSENNE_127_PRIME);
+        let keep = mask_u128(borrow);
+        (folded & keep) | (reduced & !keep)
+    }
+
+    fn add_elem(self, rhs: Self) -> Self {
+        Mersenne127(Self::reduce(self.0 + rhs.0))
+    }
+
+    fn sub_elem(self, rhs: Self) -> Self {
+        let (diff, borrow) = self.0.overflowing_sub(rhs.0);
+        Mersenne127(diff.wrapping_add(MERSENNE_127_PRIME & mask_u128(borrow)))
+    }
+
+    fn mul_elem(self, rhs: Self) -> Self {
+        // 254-bit product from 64-bit limbs, then fold with 2^128 ≡ 2
+        let mask = u64::MAX as u128;
+        let (a0, a1) = (self.0 & mask, self.0 >> 64);
+        let (b0, b1) = (rhs.0 & mask, rhs.0 >> 64);
+
+        let mid = a0 * b1 + a1 * b0; // < 2^128 since a1, b1 < 2^63
+        let (lo, carry) = (a0 * b0).overflowing_add(mid << 64);
+        let hi = a1 * b1 + (mid >> 64) + carry as u128;
+
+        Mersenne127(Self::reduce(Self::reduce(lo) + 2 * hi))
+    }
+
+    fn neg_elem(self) -> Self {
+        Mersenne127(0).sub_elem(self)
+    }
+
+    /// Fixed 128-step square-and-multiply
+    fn pow_u128(self, exp: u128) -> Self {
+        let mut base = self;
+        let mut result = Mersenne127(1);
+        for bit in 0..u128::BITS {
+            let product = result.mul_elem(base);
+            result = Self::conditional_select(&result, &product, Choice::from(((exp >> bit) & 1) as u8));
+            base = base.mul_elem(base);
+        }
+        result
+    }
+}
+
+impl PrimeField for Mersenne127 {
+    const ZERO: Self = Mersenne127(0);
+    const ONE: Self = Mersenne127(1);
+    const MODULUS_BITS: u32 = 127;
+
+    fn from_u64(value: u64) -> Self {
+        Mersenne127(value as u128)
+    }
+
+    fn inverse(self) -> Option<Self> {
+        let inverse = self.pow_u128(MERSENNE_127_PRIME - 2);
+        (!PrimeField::is_zero(self)).then_some(inverse)
+    }
+
+    fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        loop {
+            let candidate = (((rng.next_u64() as u128) << 64) | rng.next_u64() as u128) >> 1;
+            if candidate < MERSENNE_127_PRIME {
+                return Mersenne127(candidate);
+            }
+        }
+    }
+}
+
+impl ConstantTimeEq for Mersenne127 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl ConditionallySelectable for Mersenne127 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Mersenne127(u128::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl Zeroize for Mersenne127 {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl_field_ops!(Mersenne127);
+
+impl From<u128> for Mersenne127 {
+    fn from(value: u128) -> Self {
+        Mersenne127::new(value)
+    }
+}
+
+impl fmt::Display for Mersenne127 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Element of the Ristretto255 scalar field GF(ℓ), ℓ = 2^252 + 2774…8493
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Scalar25519(Scalar);
+
+impl Scalar25519 {
+    pub fn new(scalar: Scalar) -> Self {
+        Scalar25519(scalar)
+    }
+
+    /// Reduce 32 little-endian bytes into the field
+    pub fn from_bytes_mod_order(bytes: [u8; 32]) -> Self {
+        Scalar25519(Scalar::from_bytes_mod_order(bytes))
+    }
+
+    pub fn scalar(&self) -> Scalar {
+        self.0
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes()
+    }
+
+    fn add_elem(self, rhs: Self) -> Self {
+        Scalar25519(self.0 + rhs.0)
+    }
+
+    fn sub_elem(self, rhs: Self) -> Self {
+        Scalar25519(self.0 - rhs.0)
+    }
+
+    fn mul_elem(self, rhs: Self) -> Self {
+        Scalar25519(self.0 * rhs.0)
+    }
+
+    fn neg_elem(self) -> Self {
+        Scalar25519(-self.0)
+    }
+}
+
+impl PrimeField for Scalar25519 {
+    const ZERO: Self = Scalar25519(Scalar::ZERO);
+    const ONE: Self = Scalar25519(Scalar::ONE);
+    const MODULUS_BITS: u32 = 253;
+
+    fn from_u64(value: u64) -> Self {
+        Scalar25519(Scalar::from(value))
+    }
+
+    fn inverse(self) -> Option<Self> {
+        let inverse = Scalar25519(self.0.invert());
+        (!PrimeField::is_zero(self)).then_some(inverse)
+    }
+
+    /// 512 random bits reduced mod ℓ; bias is below 2^-250
+    fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        let mut wide = [0u8; 64];
+        rng.fill_bytes(&mut wide);
+        let scalar = Scalar::from_bytes_mod_order_wide(&wide);
+        wide.zeroize();
+        Scalar25519(scalar)
+    }
+}
+
+impl ConstantTimeEq for Scalar25519 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl ConditionallySelectable for Scalar25519 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Scalar25519(Scalar::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl Zeroize for Scalar25519 {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl_field_ops!(Scalar25519);
+
+impl From<Scalar> for Scalar25519 {
+    fn from(scalar: Scalar) -> Self {
+        Scalar25519(scalar)
+    }
+}
+
+impl fmt::Display for Scalar25519 {
+    /// Big-endian hex, leading zeros trimmed
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex: String = self.0.to_bytes().iter().rev().map(|b| format!("{:02x}", b)).collect();
+        let trimmed = hex.trim_start_matches('0');
+        write!(f, "0x{}", if trimmed.is_empty() { "0" } else { trimmed })
+    }
+}
+
+/// Invert many elements with one field inversion (Montgomery's trick)
+pub fn batch_inverse<F: PrimeField>(values: &[F]) -> Option<Vec<F>> {
+    let mut prefix = Vec::with_capacity(values.len());
+    let mut acc = F::ONE;
+    for &value in values {
+        prefix.push(acc);
+        acc *= value;
+    }
+
+    let mut inv = acc.inverse()?;
+    let mut result = vec![F::ZERO; values.len()];
+    for i in (0..values.len()).rev() {
+        result[i] = inv * prefix[i];
+        inv *= values[i];
+    }
+    Some(result)
+}
+
+/// Evaluate P(x) = c0 + c1·x + c2·x² + ... (Horner's rule)
+pub fn evaluate_polynomial<F: PrimeField>(coefficients: &[F], x: F) -> F {
+    coefficients
+        .iter()
+        .rev()
+        .fold(F::ZERO, |acc, &c| acc * x + c)
+}
+
+/// Lagrange basis coefficients L_i(at) for the points `xs`
+///
+/// L_i(at) = ∏_{j≠i} (at − x_j) / (x_i − x_j)
+/// All denominators are inverted together; `xs` must be distinct.
+pub fn lagrange_coefficients<F: PrimeField>(xs: &[F], at: F) -> Result<Vec<F>, String> {
+    let mut numerators = Vec::with_capacity(xs.len());
+    let mut denominators = Vec::with_capacity(xs.len());
+
+    for (i, &xi) in xs.iter().enumerate() {
+        let mut numerator = F::ONE;
+        let mut denominator = F::ONE;
+        for (j, &xj) in xs.iter().enumerate() {
+            if i != j {
+                numerator *= at - xj;
+                denominator *= xi - xj;
+            }
+        }
+        numerators.push(numerator);
+        denominators.push(denominator);
+    }
+
+    let inverses = batch_inverse(&denominators)
+        .ok_or_else(|| "Duplicate interpolation points in Lagrange coefficients".to_string())?;
+
+    Ok(numerators
+        .into_iter()
+        .zip(inverses)
+        .map(|(n, d)| n * d)
+        .collect())
+}
+
+/// Interpolate the polynomial through `points` and evaluate it at `at`
+///
+/// With at = 0 this reconstructs a Shamir secret from its shares
+pub fn interpolate_at<F: PrimeField>(points: &[(F, F)], at: F) -> Result<F, String> {
+    if points.is_empty() {
+        return Err("Cannot interpolate from zero points".to_string());
+    }
+    let xs: Vec<F> = points.iter().map(|&(x, _)| x).collect();
+    let coefficients = lagrange_coefficients(&xs, at)?;
+    Ok(points
+        .iter()
+        .zip(coefficients)
+        .map(|(&(_, y), l)| y * l)
+        .sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fe(value: u64) -> FieldElement {
+        FieldElement::new(value)
+    }
+
+    #[test]
+    fn test_mod_operations() {
+        let p = FIELD_PRIME;
+
+        assert_eq!(fe(5) + fe(3), fe(8));
+        assert_eq!(fe(999_999_999) + fe(8), FieldElement::ZERO); // (p-8) + 8 = p ≡ 0
+
+        assert_eq!(fe(8) - fe(3), fe(5));
+        assert_eq!((fe(3) - fe(8)).value(), p - 5);
+
+        assert_eq!(fe(3) * fe(4), fe(12));
+        assert_eq!(fe(1000) * fe(1001), fe(1_001_000));
+        // Products near p² no longer overflow
+        assert_eq!(fe(p - 1) * fe(p - 1), FieldElement::ONE);
+
+        assert_eq!(-fe(1), fe(p - 1));
+        assert_eq!(FieldElement::from_i64(-5), fe(p - 5));
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        // 3 * inv(3) ≡ 1 (mod p)
+        assert_eq!(fe(3) * fe(3).inverse().unwrap(), FieldElement::ONE);
+        assert_eq!(fe(5) / fe(5), FieldElement::ONE);
+
+        // For prime p, every non-zero element has an inverse
+        for a in 1..20 {
+            assert!(fe(a).inverse().is_some());
+        }
+        assert!(FieldElement::ZERO.inverse().is_none());
+    }
+
+    #[test]
+    fn test_pow() {
+        assert_eq!(fe(2).pow(10), fe(1024));
+        assert_eq!(fe(7).pow(0), FieldElement::ONE);
+        // Fermat: a^(p-1) = 1
+        assert_eq!(fe(123_456).pow(FIELD_PRIME - 1), FieldElement::ONE);
+    }
+
+    #[test]
+    fn test_batch_inverse() {
+        let values: Vec<FieldElement> = (1..10).map(fe).collect();
+        let inverses = batch_inverse(&values).unwrap();
+        for (v, inv) in values.iter().zip(inverses) {
+            assert_eq!(*v * inv, FieldElement::ONE);
+        }
+        assert!(batch_inverse(&[fe(1), FieldElement::ZERO]).is_none());
+    }
+
+    #[test]
+    fn test_lagrange_coefficient_modular() {
+        let ids = [fe(1), fe(2), fe(3)];
+        let l = lagrange_coefficients(&ids, FieldElement::ZERO).unwrap();
+
+        // L1(0) = (-2)(-3) / (1-2)(1-3) = 3
+        assert_eq!(l[0], fe(3));
+        // L2(0) = (-1)(-3) / (2-1)(2-3) = -3 ≡ p-3
+        assert_eq!(l[1], fe(FIELD_PRIME - 3));
+        // L3(0) = (-1)(-2) / (3-1)(3-2) = 1
+        assert_eq!(l[2], fe(1));
+    }
+
+    #[test]
+    fn test_lagrange_rejects_duplicate_points() {
+        assert!(lagrange_coefficients(&[fe(1), fe(1)], FieldElement::ZERO).is_err());
+        assert!(interpolate_at(&[], FieldElement::ZERO).is_err());
+    }
+
+    #[test]
+    fn test_interpolate_at_arbitrary_points() {
+        // P(x) = 42 + 10x + 7x²
+        let poly = [fe(42), fe(10), fe(7)];
+        let points: Vec<_> = [2u64, 5, 9]
+            .iter()
+            .map(|&x| (fe(x), evaluate_polynomial(&poly, fe(x))))
+            .collect();
+
+        assert_eq!(interpolate_at(&points, FieldElement::ZERO).unwrap(), fe(42));
+        for x in [1u64, 4, 100, FIELD_PRIME - 1] {
+            assert_eq!(
+                interpolate_at(&points, fe(x)).unwrap(),
+                evaluate_polynomial(&poly, fe(x))
+            );
+        }
+    }
+
+    #[test]
+    fn test_goldilocks_arithmetic_near_u64_max() {
+        let p_minus_1 = Goldilocks::new(GOLDILOCKS_PRIME - 1);
+        assert_eq!(p_minus_1 + Goldilocks::ONE, Goldilocks::ZERO);
+        assert_eq!(p_minus_1 + p_minus_1, Goldilocks::new(GOLDILOCKS_PRIME - 2));
+        assert_eq!(p_minus_1 * p_minus_1, Goldilocks::ONE);
+        assert_eq!(Goldilocks::new(u64::MAX).value(), u64::MAX - GOLDILOCKS_PRIME);
+        assert_eq!(Goldilocks::from_i64(-1), p_minus_1);
+        assert_eq!(<Goldilocks as PrimeField>::MODULUS_BITS, 64);
+        assert_eq!(<FieldElement as PrimeField>::MODULUS_BITS, 30);
+    }
+
+    #[test]
+    fn test_mersenne127_arithmetic() {
+        let p_minus_1 = Mersenne127::new(MERSENNE_127_PRIME - 1);
+        assert_eq!(Mersenne127::new(MERSENNE_127_PRIME), Mersenne127::ZERO);
+        assert_eq!(Mersenne127::new(u128::MAX), Mersenne127::ONE);
+        assert_eq!(p_minus_1 + Mersenne127::ONE, Mersenne127::ZERO);
+        assert_eq!(p_minus_1 * p_minus_1, Mersenne127::ONE);
+        assert_eq!(-Mersenne127::ONE, p_minus_1);
+
+        // 2^64 · 2^64 = 2^128 ≡ 2
+        let two_64 = Mersenne127::new(1 << 64);
+        assert_eq!(two_64 * two_64, Mersenne127::from(2u64));
+
+        // Products of full u64 values fit without reduction
+        let a = Mersenne127::from(u64::MAX);
+        assert_eq!((a * a).value(), u64::MAX as u128 * u64::MAX as u128 % MERSENNE_127_PRIME);
+    }
+
+    #[test]
+    fn test_scalar25519_arithmetic() {
+        let three = Scalar25519::from(3u64);
+        assert_eq!(three * PrimeField::inverse(three).unwrap(), Scalar25519::ONE);
+        assert_eq!(Scalar25519::from(-5i64) + Scalar25519::from(5u64), Scalar25519::ZERO);
+        assert_eq!(Scalar25519::from(255u64).to_string(), "0xff");
+        assert!(PrimeField::inverse(Scalar25519::ZERO).is_none());
+    }
+
+    fn check_field_axioms<F: PrimeField>() {
+        let values: Vec<F> = [0i64, 1, 2, 7, -1, -12345, i64::MAX, i64::MIN]
+            .iter()
+            .map(|&v| F::from_i64(v))
+            .collect();
+
+        for &a in &values {
+            assert_eq!(a + F::ZERO, a);
+            assert_eq!(a * F::ONE, a);
+            assert_eq!(a + (-a), F::ZERO);
+            if !a.is_zero() {
+                assert_eq!(a * a.inverse().unwrap(), F::ONE);
+            }
+            for &b in &values {
+                assert_eq!(a + b, b + a);
+                assert_eq!(a * b, b * a);
+                assert_eq!((a - b) + b, a);
+                for &c in &values {
+                    assert_eq!(a * (b + c), a * b + a * c);
+                }
+            }
+        }
+        assert_eq!(F::from_u64(2).pow(10), F::from_u64(1024));
+    }
+
+    #[test]
+    fn test_field_axioms_hold_in_every_field() {
+        check_field_axioms::<FieldElement>();
+        check_field_axioms::<Goldilocks>();
+        check_field_axioms::<Mersenne127>();
+        check_field_axioms::<Scalar25519>();
+    }
+
+    fn check_interpolation<F: PrimeField>() {
+        let poly = [F::from_i64(-42), F::from_u64(10), F::from_u64(7)];
+        let points: Vec<(F, F)> = [2u64, 5, 9]
+            .iter()
+            .map(|&x| (F::from_u64(x), evaluate_polynomial(&poly, F::from_u64(x))))
+            .collect();
+        assert_eq!(interpolate_at(&points, F::ZERO).unwrap(), poly[0]);
+    }
+
+    #[test]
+    fn test_interpolation_in_every_field() {
+        check_interpolation::<FieldElement>();
+        check_interpolation::<Goldilocks>();
+        check_interpolation::<Mersenne127>();
+        check_interpolation::<Scalar25519>();
+    }
+
+    fn check_random<F: PrimeField>() {
+        use rand::SeedableRng;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(11);
+        let samples: Vec<F> = (0..64).map(|_| F::random(&mut rng)).collect();
+        let distinct: std::collections::HashSet<F> = samples.iter().copied().collect();
+        assert!(distinct.len() > 60);
+
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(11);
+        assert_eq!(F::random(&mut rng), samples[0]);
+    }
+
+    #[test]
+    fn test_random_elements_are_seed_reproducible() {
+        check_random::<FieldElement>();
+        check_random::<Goldilocks>();
+        check_random::<Mersenne127>();
+        check_random::<Scalar25519>();
+    }
+
+    fn check_branchless_ops_match_reference<const P: u64>() {
+        use rand::SeedableRng;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(5);
+        let p = P as u128;
+        let edges = [0, 1, 2, P - 2, P - 1, P, P + 1, u64::MAX / 2, u64::MAX - 1, u64::MAX];
+        let values: Vec<u64> = edges.iter().copied().chain((0..200).map(|_| rng.next_u64())).collect();
+
+        for &a in &values {
+            assert_eq!(Fp64::<P>::new(a).value() as u128, a as u128 % p);
+            assert_eq!(Fp64::<P>::from_i64(a as i64).value() as i128, (a as i64 as i128).rem_euclid(p as i128));
+            for &b in values.iter().step_by(7) {
+                let (x, y) = (Fp64::<P>::new(a), Fp64::<P>::new(b));
+                let (xv, yv) = (x.value() as u128, y.value() as u128);
+                assert_eq!((x + y).value() as u128, (xv + yv) % p);
+                assert_eq!((x - y).value() as u128, (xv + p - yv) % p);
+                assert_eq!((x * y).value() as u128, xv * yv % p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_branchless_ops_match_reference() {
+        check_branchless_ops_match_reference::<FIELD_PRIME>();
+        check_branchless_ops_match_reference::<GOLDILOCKS_PRIME>();
+        check_branchless_ops_match_reference::<3>();
+
+        let a = Mersenne127::new(MERSENNE_127_PRIME - 5);
+        assert_eq!((a - Mersenne127::from(7u64)).value(), MERSENNE_127_PRIME - 12);
+        assert_eq!((Mersenne127::from(7u64) - a).value(), 12);
+        assert_eq!(a.pow_u128(MERSENNE_127_PRIME - 1), Mersenne127::ONE);
+    }
+
+    #[test]
+    fn test_constant_time_select_and_eq() {
+        let (a, b) = (fe(3), fe(4));
+        assert_eq!(FieldElement::conditional_select(&a, &b, Choice::from(0)), a);
+        assert_eq!(FieldElement::conditional_select(&a, &b, Choice::from(1)), b);
+        assert!(bool::from(a.ct_eq(&fe(3))));
+        assert!(!bool::from(Scalar25519::ONE.ct_eq(&Scalar25519::ZERO)));
+    }
+
+    #[test]
+    fn test_zeroize_clears_elements() {
+        let mut a = Mersenne127::new(12345);
+        a.zeroize();
+        assert_eq!(a, Mersenne127::ZERO);
+        let mut b = Scalar25519::from(99u64);
+        b.zeroize();
+        assert_eq!(b, Scalar25519::ZERO);
+    }
+}
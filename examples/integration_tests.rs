@@ -1,7 +1,9 @@
-/// End-to-End FHE Integration Tests
-/// Combines Week 3 (FHE Aggregation) with Week 4 (Threshold Decryption)
+//! End-to-End FHE Integration Tests
+//! Combines Week 3 (FHE Aggregation) with Week 4 (Threshold Decryption)
 
-use std::collections::HashMap;
+use blocksense_examples::field::FieldElement;
+use blocksense_examples::robust::{robust_reconstruct, RobustReconstruction};
+use blocksense_examples::secret::Secret;
 
 // ============================================================================
 // Week 3: FHE Module (Simplified for testing)
@@ -61,71 +63,11 @@ impl FheBackend for MockFhe {
 // Week 4: Threshold Decryption Module
 // ============================================================================
 
-const FIELD_PRIME: i64 = 1_000_000_007;
-
-fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
-    if a == 0 {
-        return (b, 0, 1);
-    }
-    let (gcd, x1, y1) = extended_gcd(b % a, a);
-    let x = y1 - (b / a) * x1;
-    let y = x1;
-    (gcd, x, y)
-}
-
-fn mod_inverse(mut a: i64, p: i64) -> Option<i64> {
-    a = a % p;
-    let (gcd, x, _) = extended_gcd(a, p);
-    if gcd != 1 {
-        return None;
-    }
-    let result = (x % p + p) % p;
-    Some(result)
-}
-
-fn mod_mul(a: i64, b: i64, p: i64) -> i64 {
-    ((a % p) * (b % p)) % p
-}
-
-fn mod_add(a: i64, b: i64, p: i64) -> i64 {
-    ((a % p) + (b % p)) % p
-}
-
-fn mod_sub(a: i64, b: i64, p: i64) -> i64 {
-    let result = ((a % p) - (b % p)) % p;
-    if result < 0 {
-        result + p
-    } else {
-        result
-    }
-}
-
-fn lagrange_coefficient_modular(
-    participant_id: i64,
-    all_participant_ids: &[i64],
-    p: i64,
-) -> i64 {
-    let mut numerator = 1i64;
-    let mut denominator = 1i64;
-
-    for &xj in all_participant_ids {
-        if xj != participant_id {
-            let neg_xj = if xj < 0 { p + (xj % p) } else { (p - (xj % p)) % p };
-            numerator = mod_mul(numerator, neg_xj, p);
-            let diff = mod_sub(participant_id, xj, p);
-            denominator = mod_mul(denominator, diff, p);
-        }
-    }
-
-    match mod_inverse(denominator, p) {
-        Some(inv) => mod_mul(numerator, inv, p),
-        None => 0,
-    }
-}
-
+#[derive(Debug)]
 pub struct ThresholdDecryptor {
     pub id: u32,
-    pub key_share: i64,
+    /// Wiped on drop, printed as [REDACTED]
+    pub key_share: Secret<i64>,
 }
 
 pub struct ThresholdScheme {
@@ -144,39 +86,76 @@ impl ThresholdScheme {
     }
 
     pub fn register_decryptor(&mut self, id: u32, key_share: i64) {
-        self.decryptors.push(ThresholdDecryptor { id, key_share });
+        self.decryptors.push(ThresholdDecryptor {
+            id,
+            key_share: Secret::new(key_share),
+        });
     }
 
     pub fn decrypt(&self, encrypted_value: i64) -> Option<i64> {
-        if self.decryptors.len() < self.threshold as usize {
-            return None;
-        }
+        self.decrypt_with_report(encrypted_value)
+            .ok()
+            .map(|result| result.secret.value() as i64)
+    }
 
-        let participant_ids: Vec<i64> = self
+    /// Decode from all registered shares, correcting and reporting bad ones
+    pub fn decrypt_with_report(
+        &self,
+        _encrypted_value: i64,
+    ) -> Result<RobustReconstruction<FieldElement>, String> {
+        let shares: Vec<(u32, FieldElement)> = self
             .decryptors
             .iter()
-            .take(self.threshold as usize)
-            .map(|d| d.id as i64)
+            .map(|d| (d.id, FieldElement::from(*d.key_share.expose())))
             .collect();
 
-        let mut secret = 0i64;
+        robust_reconstruct(&shares, self.threshold as usize)
+    }
+}
 
-        for (i, decryptor) in self
-            .decryptors
-            .iter()
-            .take(self.threshold as usize)
-            .enumerate()
-        {
-            let li = lagrange_coefficient_modular(
-                decryptor.id as i64,
-                &participant_ids,
-                FIELD_PRIME,
-            );
-            let contribution = mod_mul(decryptor.key_share, li, FIELD_PRIME);
-            secret = mod_add(secret, contribution, FIELD_PRIME);
-        }
+fn main() {
+    println!("╔═══════════════════════════════════════════════════════╗");
+    println!("║  FHE + Threshold Integration Tests                   ║");
+    println!("╚═══════════════════════════════════════════════════════╝\n");
+
+    println!("Running integration tests...");
+    println!();
+
+    // Demo: FHE aggregation + threshold decryption
+    let fhe = MockFhe;
+
+    // Scenario: 3 price feeds
+    let prices = [100, 105, 102];
+    let weights = [2, 1, 3];
+
+    println!("📊 Scenario: 3 Price Feeds (weighted aggregation)");
+    for (i, (price, weight)) in prices.iter().zip(weights.iter()).enumerate() {
+        println!("  Feed {}: price={}, weight={}", i + 1, price, weight);
+    }
+    println!();
+
+    // FHE: Aggregate
+    let mut ct_agg = fhe.encrypt(0);
+    for (price, weight) in prices.iter().zip(weights.iter()) {
+        let ct = fhe.encrypt(*price);
+        let ct_w = fhe.scalar_mul(&ct, *weight as i64);
+        ct_agg = fhe.add(&ct_agg, &ct_w);
+    }
+
+    let agg = fhe.decrypt(&ct_agg);
+    println!("🔐 FHE Aggregation: {} (encrypted)", agg);
+    println!();
+
+    // Threshold: Setup committee
+    println!("🔓 Threshold Decryption Setup (3-of-5)");
+    let mut scheme = ThresholdScheme::new(3, 5);
+    for id in 1..=5 {
+        scheme.register_decryptor(id, agg + 10 * id as i64);
+    }
 
-        Some(secret)
+    match scheme.decrypt(agg) {
+        Some(result) => println!("  Final result: {} ✓", result),
+        None => println!("  Insufficient decryptors"),
     }
 }
 
@@ -230,8 +209,8 @@ mod tests {
         let fhe = MockFhe;
 
         // Step 1: Encrypt provider values
-        let provider_values = vec![5000i64, 4900, 5200, 5050, 4950];
-        let weights = vec![2, 1, 3, 2, 1];
+        let provider_values = [5000i64, 4900, 5200, 5050, 4950];
+        let weights = [2, 1, 3, 2, 1];
 
         // Step 2: FHE aggregation (homomorphic)
         let mut ct_sum = fhe.encrypt(0);
@@ -293,13 +272,33 @@ mod tests {
         assert_eq!(scheme2.decrypt(encrypted_result), Some(1));
     }
 
+    #[test]
+    fn test_integration_corrupted_decryptor_is_identified() {
+        // One committee member submits a wrong share
+        let mut committee = ThresholdScheme::new(3, 5);
+        for id in 1..=5 {
+            let share = 1 + 10 * id as i64 + if id == 2 { 777 } else { 0 };
+            committee.register_decryptor(id, share);
+        }
+
+        let report = committee.decrypt_with_report(1).unwrap();
+        assert_eq!(report.secret, FieldElement::new(1));
+        assert_eq!(report.faulty_ids, vec![2]);
+        assert_eq!(committee.decrypt(1), Some(1));
+
+        // A second bad share exceeds what 5 shares can correct for k = 3
+        *committee.decryptors[3].key_share.expose_mut() += 5;
+        assert!(committee.decrypt_with_report(1).is_err());
+        assert_eq!(committee.decrypt(1), None);
+    }
+
     #[test]
     fn test_integration_privacy_guarantee() {
         // Verify: Individual provider values never revealed
         let fhe = MockFhe;
 
-        let provider_values = vec![1234i64, 5678, 9012];
-        let weights = vec![3, 2, 1];
+        let provider_values = [1234i64, 5678, 9012];
+        let weights = [3, 2, 1];
 
         // FHE workflow: All values stay encrypted
         let mut ct_sum = fhe.encrypt(0);
@@ -315,7 +314,7 @@ mod tests {
 
         // Only final aggregate decrypted
         let aggregate = fhe.decrypt(&ct_sum);
-        assert_eq!(aggregate, 1234 * 3 + 5678 * 2 + 9012 * 1);
+        assert_eq!(aggregate, 1234 * 3 + 5678 * 2 + 9012);
 
         // Threshold: Committee can't see aggregate until threshold met
         let mut scheme = ThresholdScheme::new(3, 5);
@@ -339,8 +338,8 @@ mod tests {
         let fhe = MockFhe;
 
         // Providers submit encrypted prices
-        let prices = vec![5100i64, 5150, 5120];
-        let weights = vec![2, 3, 1]; // Higher weight on more trusted provider
+        let prices = [5100i64, 5150, 5120];
+        let weights = [2, 3, 1]; // Higher weight on more trusted provider
 
         // Encrypt and aggregate
         let mut ct_aggregate = fhe.encrypt(0);
@@ -352,7 +351,7 @@ mod tests {
 
         // Expected: (5100*2 + 5150*3 + 5120*1) / 6 ≈ 5128
         let agg_value = fhe.decrypt(&ct_aggregate);
-        assert_eq!(agg_value, 5100 * 2 + 5150 * 3 + 5120 * 1);
+        assert_eq!(agg_value, 5100 * 2 + 5150 * 3 + 5120);
 
         // Threshold comparison: Is aggregate > 5100?
         let ct_threshold = fhe.encrypt(5100);
@@ -446,49 +445,3 @@ mod tests {
         println!("\n✅ Full E2E workflow complete!");
     }
 }
-
-fn main() {
-    println!("╔═══════════════════════════════════════════════════════╗");
-    println!("║  FHE + Threshold Integration Tests                   ║");
-    println!("╚═══════════════════════════════════════════════════════╝\n");
-
-    println!("Running integration tests...");
-    println!();
-
-    // Demo: FHE aggregation + threshold decryption
-    let fhe = MockFhe;
-
-    // Scenario: 3 price feeds
-    let prices = vec![100, 105, 102];
-    let weights = vec![2, 1, 3];
-
-    println!("📊 Scenario: 3 Price Feeds (weighted aggregation)");
-    for (i, (price, weight)) in prices.iter().zip(weights.iter()).enumerate() {
-        println!("  Feed {}: price={}, weight={}", i + 1, price, weight);
-    }
-    println!();
-
-    // FHE: Aggregate
-    let mut ct_agg = fhe.encrypt(0);
-    for (price, weight) in prices.iter().zip(weights.iter()) {
-        let ct = fhe.encrypt(*price);
-        let ct_w = fhe.scalar_mul(&ct, *weight as i64);
-        ct_agg = fhe.add(&ct_agg, &ct_w);
-    }
-
-    let agg = fhe.decrypt(&ct_agg);
-    println!("🔐 FHE Aggregation: {} (encrypted)", agg);
-    println!();
-
-    // Threshold: Setup committee
-    println!("🔓 Threshold Decryption Setup (3-of-5)");
-    let mut scheme = ThresholdScheme::new(3, 5);
-    for id in 1..=5 {
-        scheme.register_decryptor(id, agg + 10 * id as i64);
-    }
-
-    match scheme.decrypt(agg) {
-        Some(result) => println!("  Final result: {} ✓", result),
-        None => println!("  Insufficient decryptors"),
-    }
-}
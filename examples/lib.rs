@@ -0,0 +1,27 @@
+//! Shared modules for the Blocksense FHE Oracle examples
+//!
+//! Each binary in this package pulls the FHE backend, aggregation and
+//! threshold decryption modules from here instead of re-declaring them.
+
+pub mod aggregation;
+pub mod coordinator;
+pub mod decryptor_service;
+pub mod dkg;
+#[cfg(test)]
+mod dudect;
+pub mod event_lifecycle;
+pub mod event_store;
+pub mod field;
+pub mod fhe_module;
+pub mod participant_selection;
+pub mod privacy;
+pub mod proactive;
+pub mod robust;
+pub mod secret;
+pub mod shamir;
+pub mod share_storage;
+pub mod threshold_decryption;
+pub mod threshold_lwe;
+pub mod threshold_signature;
+pub mod verifiable_decryption;
+pub mod vss;
@@ -1,18 +1,19 @@
-/// Main aggregation example
-///
-/// Demonstrates complete oracle aggregation workflow with FHE
-///
-/// Example: Prediction market on ETH price
-/// - 5 providers submit predictions (encrypted)
-/// - Aggregate: weighted average of predictions
-/// - Threshold: 5000 (decision point)
-/// - Result: YES if aggregate > threshold, else NO
+//! Main aggregation example
+//!
+//! Demonstrates complete oracle aggregation workflow with FHE
+//!
+//! Example: Prediction market on ETH price
+//! - 5 providers submit predictions (encrypted)
+//! - Aggregate: weighted average of predictions
+//! - Threshold: 5000 (decision point)
+//! - Result: YES if aggregate > threshold, else NO
+//!
+//! Runs on `LweFhe`, which holds the full LWE key, so it only builds with
+//! `--features insecure`.
 
-mod fhe_module;
-mod aggregation;
-
-use fhe_module::{MockFhe, FheBackend};
-use aggregation::{ProviderSubmission, oracle_aggregation_workflow, reveal_oracle_result};
+use blocksense_examples::aggregation::{ProviderSubmission, oracle_aggregation_workflow, reveal_oracle_result};
+use blocksense_examples::threshold_lwe::{LweDecryptionCommittee, LweFhe, LweParams, LweSecretKey};
+use rand::rngs::OsRng;
 
 fn main() {
     println!("\n╔═══════════════════════════════════════════════════════════╗");
@@ -20,10 +21,17 @@ fn main() {
     println!("║  Week 3: Rust Integer Sum + Threshold Comparison      ║");
     println!("╚═══════════════════════════════════════════════════════════╝\n");
     
-    // Initialize FHE backend
-    let fhe_backend = MockFhe;
-    println!("✓ FHE Backend initialized (using Mock TFHE)");
-    println!("  Ready for real TFHE integration in next phase\n");
+    // Initialize FHE backend and a 3-of-5 decryption committee
+    let params = LweParams::default();
+    let key = LweSecretKey::generate(&params, &mut OsRng);
+    let shares = key
+        .deal_shares(3, 5, &mut OsRng)
+        .expect("3-of-5 is a valid threshold");
+    let committee = LweDecryptionCommittee::new(params, 3, shares)
+        .expect("dealt shares match the parameters");
+    let fhe_backend = LweFhe::new(params, key).expect("key matches the parameters");
+    println!("✓ FHE Backend initialized (LWE; insecure key-holding stand-in for bootstrapping)");
+    println!("✓ 3-of-5 decryption committee holds the key shares\n");
     
     // Simulate prediction event
     println!("Event: ETH Price Prediction Market");
@@ -35,26 +43,31 @@ fn main() {
             provider_id: "Provider A".to_string(),
             quantized_value: 5000,
             weight: 2,
+            timestamp: 1729418400,
         },
         ProviderSubmission {
             provider_id: "Provider B".to_string(),
             quantized_value: 4900,
             weight: 1,
+            timestamp: 1729418400,
         },
         ProviderSubmission {
             provider_id: "Provider C".to_string(),
             quantized_value: 5200,
             weight: 3,
+            timestamp: 1729418400,
         },
         ProviderSubmission {
             provider_id: "Provider D".to_string(),
             quantized_value: 5050,
             weight: 2,
+            timestamp: 1729418400,
         },
         ProviderSubmission {
             provider_id: "Provider E".to_string(),
             quantized_value: 4950,
             weight: 1,
+            timestamp: 1729418400,
         },
     ];
     
@@ -74,8 +87,9 @@ fn main() {
     // Run aggregation workflow
     let result = oracle_aggregation_workflow(&fhe_backend, &submissions, threshold);
     
-    // Reveal final result
-    let decision = reveal_oracle_result(&fhe_backend, &result);
+    // Reveal final result through the committee
+    let decision = reveal_oracle_result(&committee, &result)
+        .expect("committee decryption failed");
     
     // Verification
     println!("╔═══════════════════════════════════════════════════════════╗");
@@ -0,0 +1,191 @@
+//! Participant Selection for Threshold Decryption
+//!
+//! A strategy orders the registered decryptors by preference. Decryption
+//! asks as many as it needs at once, in that order, and replaces each one
+//! that fails or times out with the next, so the order doubles as the
+//! fallback list. [`FirstResponders`] instead asks everyone at once and
+//! keeps whoever answers first. The randomised strategies take a public
+//! seed, letting anyone recompute which decryptors were chosen.
+
+use crate::threshold_decryption::Decryptor;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Orders decryptors by preference, most preferred first
+pub trait SelectionStrategy {
+    fn order<'a>(&self, decryptors: &'a [Decryptor]) -> Vec<&'a Decryptor>;
+
+    /// Ask every decryptor at once rather than only as many as are needed
+    fn ask_all(&self) -> bool {
+        false
+    }
+}
+
+/// Every decryptor is asked at once and the first to answer are used, in
+/// the order they answered in; `order` is registration order, which is
+/// also what a selection made before anyone answers gets
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FirstResponders;
+
+impl SelectionStrategy for FirstResponders {
+    fn order<'a>(&self, decryptors: &'a [Decryptor]) -> Vec<&'a Decryptor> {
+        decryptors.iter().collect()
+    }
+
+    fn ask_all(&self) -> bool {
+        true
+    }
+}
+
+/// Fastest decryptors first by observed latency; unmeasured ones go last
+#[derive(Clone, Debug, Default)]
+pub struct LowestLatency {
+    latencies: HashMap<u32, Duration>,
+}
+
+impl LowestLatency {
+    pub fn new(latencies: HashMap<u32, Duration>) -> Self {
+        LowestLatency { latencies }
+    }
+
+    /// Record the latest round-trip time of decryptor `id`
+    pub fn record(&mut self, id: u32, latency: Duration) {
+        self.latencies.insert(id, latency);
+    }
+}
+
+impl SelectionStrategy for LowestLatency {
+    fn order<'a>(&self, decryptors: &'a [Decryptor]) -> Vec<&'a Decryptor> {
+        let mut ordered: Vec<&Decryptor> = decryptors.iter().collect();
+        // Stable sort keeps registration order among equal latencies
+        ordered.sort_by_key(|d| self.latencies.get(&d.id).copied().unwrap_or(Duration::MAX));
+        ordered
+    }
+}
+
+/// Weighted random order: a decryptor with twice the reputation is twice
+/// as likely to be picked at each step
+///
+/// Uses Efraimidis–Spirakis sampling (key u^(1/w)) driven by a public
+/// seed. Decryptors without a positive reputation come last, by id.
+#[derive(Clone, Debug)]
+pub struct ReputationWeighted {
+    reputations: HashMap<u32, f64>,
+    seed: [u8; 32],
+}
+
+impl ReputationWeighted {
+    pub fn new(reputations: HashMap<u32, f64>, seed: [u8; 32]) -> Self {
+        ReputationWeighted { reputations, seed }
+    }
+}
+
+impl SelectionStrategy for ReputationWeighted {
+    fn order<'a>(&self, decryptors: &'a [Decryptor]) -> Vec<&'a Decryptor> {
+        let mut rng = ChaCha20Rng::from_seed(self.seed);
+        let mut keyed: Vec<(f64, &Decryptor)> = sorted_by_id(decryptors)
+            .into_iter()
+            .map(|d| {
+                let u: f64 = rng.gen();
+                let weight = self.reputations.get(&d.id).copied().unwrap_or(0.0);
+                let key = if weight > 0.0 && weight.is_finite() {
+                    u.powf(1.0 / weight)
+                } else {
+                    -1.0
+                };
+                (key, d)
+            })
+            .collect();
+        keyed.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+        keyed.into_iter().map(|(_, d)| d).collect()
+    }
+}
+
+/// Uniformly random order derived from a public seed
+#[derive(Clone, Copy, Debug)]
+pub struct SeededRandom {
+    seed: [u8; 32],
+}
+
+impl SeededRandom {
+    pub fn new(seed: [u8; 32]) -> Self {
+        SeededRandom { seed }
+    }
+}
+
+impl SelectionStrategy for SeededRandom {
+    fn order<'a>(&self, decryptors: &'a [Decryptor]) -> Vec<&'a Decryptor> {
+        let mut ordered = sorted_by_id(decryptors);
+        ordered.shuffle(&mut ChaCha20Rng::from_seed(self.seed));
+        ordered
+    }
+}
+
+/// Sort by id so seeded orders do not depend on registration order
+fn sorted_by_id(decryptors: &[Decryptor]) -> Vec<&Decryptor> {
+    let mut sorted: Vec<&Decryptor> = decryptors.iter().collect();
+    sorted.sort_by_key(|d| d.id);
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::threshold_decryption::SecretShare;
+
+    fn decryptors(ids: &[u32]) -> Vec<Decryptor> {
+        ids.iter()
+            .map(|&id| Decryptor::new(id, "decryptor", SecretShare::new(id, 0)))
+            .collect()
+    }
+
+    fn ids(ordered: &[&Decryptor]) -> Vec<u32> {
+        ordered.iter().map(|d| d.id).collect()
+    }
+
+    #[test]
+    fn test_first_responders_and_latency_order() {
+        let all = decryptors(&[3, 1, 5, 2]);
+        assert_eq!(ids(&FirstResponders.order(&all)), vec![3, 1, 5, 2]);
+        assert!(FirstResponders.ask_all());
+
+        let mut latency = LowestLatency::default();
+        latency.record(5, Duration::from_millis(10));
+        latency.record(2, Duration::from_millis(40));
+        latency.record(3, Duration::from_millis(20));
+        assert_eq!(ids(&latency.order(&all)), vec![5, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_seeded_orders_are_reproducible() {
+        let all = decryptors(&[1, 2, 3, 4, 5]);
+        let reversed = decryptors(&[5, 4, 3, 2, 1]);
+        let random = SeededRandom::new([7; 32]);
+        assert_eq!(ids(&random.order(&all)), ids(&random.order(&reversed)));
+        assert_ne!(ids(&random.order(&all)), ids(&SeededRandom::new([8; 32]).order(&all)));
+
+        let reputations = HashMap::from([(1, 1.0), (2, 2.0), (3, 0.0), (4, 5.0), (5, 1.0)]);
+        let weighted = ReputationWeighted::new(reputations, [7; 32]);
+        let order = ids(&weighted.order(&all));
+        assert_eq!(order, ids(&weighted.order(&reversed)));
+        assert_eq!(order.last(), Some(&3));
+    }
+
+    #[test]
+    fn test_reputation_weights_bias_selection() {
+        let all = decryptors(&[1, 2, 3, 4, 5]);
+        let reputations = HashMap::from([(1, 1.0), (2, 1.0), (3, 1.0), (4, 1.0), (5, 20.0)]);
+        let first_picks = (0..200u8)
+            .filter(|&s| {
+                let weighted = ReputationWeighted::new(reputations.clone(), [s; 32]);
+                weighted.order(&all)[0].id == 5
+            })
+            .count();
+        // Expected 20/24 of 200 ≈ 167
+        assert!(first_picks > 140, "picked first {} times", first_picks);
+    }
+}
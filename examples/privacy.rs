@@ -0,0 +1,555 @@
+//! Differential Privacy for Revealed Aggregates
+//!
+//! Adds calibrated, encrypted discrete Laplace noise to the aggregate, and to
+//! the dispersion when an event computes one, before threshold decryption.
+//! Repeated events then cannot be differenced to recover an individual
+//! provider's value. A privacy-budget accountant tracks the epsilon spent per
+//! provider set (basic sequential composition).
+//!
+//! Trust assumption: noise is generated jointly by a committee of n members,
+//! and no single party ever knows it. Each member samples a share
+//! Pólya(1/h, q) − Pólya(1/h, q), with q = e^(−ε/Δ) and h = n − t, and
+//! submits it encrypted. Discrete Laplace is infinitely divisible, so any h
+//! shares add up to exactly DLap(Δ/ε). Up to t colluding members, together
+//! with the aggregator who only sees ciphertexts, can remove their own
+//! shares but still face full-strength noise from the honest ones.
+
+use crate::aggregation::AggregationResult;
+use crate::fhe_module::{FheBackend, FheCiphertext};
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use rand_distr::{Distribution, Gamma, Poisson};
+use std::collections::{BTreeSet, HashMap};
+
+/// Largest quantized percentage, the value domain of dispersion
+const PERCENT_SCALE: i64 = 10000;
+
+/// Per-event differential privacy setting
+#[derive(Clone, Debug, PartialEq)]
+pub struct DpConfig {
+    /// Privacy loss ε spent by revealing this event's aggregate
+    pub epsilon: f64,
+    /// Max change of the aggregate when one provider's value changes
+    pub sensitivity: i64,
+}
+
+impl DpConfig {
+    pub fn new(epsilon: f64, sensitivity: i64) -> Result<Self, String> {
+        if !epsilon.is_finite() || epsilon <= 0.0 {
+            return Err(format!("Epsilon must be positive and finite: {}", epsilon));
+        }
+        if sensitivity <= 0 {
+            return Err(format!("Sensitivity must be positive: {}", sensitivity));
+        }
+        Ok(DpConfig { epsilon, sensitivity })
+    }
+
+    /// Percentage market: one provider moves the weighted sum by at most
+    /// max_weight × 10000
+    pub fn for_percent_market(epsilon: f64, max_weight: i64) -> Result<Self, String> {
+        Self::new(epsilon, max_weight * PERCENT_SCALE)
+    }
+
+    /// Percentage market dispersion: one provider moves W·Σ(wᵢ·xᵢ²) − (Σ wᵢ·xᵢ)²
+    /// by at most 2·W·max_weight·10000²
+    pub fn for_percent_dispersion(epsilon: f64, max_weight: i64, total_weight: i64) -> Result<Self, String> {
+        let sensitivity = [max_weight, PERCENT_SCALE, PERCENT_SCALE, 2]
+            .iter()
+            .try_fold(total_weight, |acc, &x| acc.checked_mul(x))
+            .ok_or_else(|| format!("Dispersion sensitivity overflows i64 at total weight {}", total_weight))?;
+        Self::new(epsilon, sensitivity)
+    }
+
+    /// Laplace scale b = Δ / ε
+    pub fn scale(&self) -> f64 {
+        self.sensitivity as f64 / self.epsilon
+    }
+}
+
+/// Committee that generates the noise jointly
+///
+/// Each of `size` members contributes a noise share. Shares are sized so
+/// that any `size − max_colluding` of them already sum to DLap(Δ/ε).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NoiseCommittee {
+    pub size: usize,
+    pub max_colluding: usize,
+}
+
+impl NoiseCommittee {
+    pub fn new(size: usize, max_colluding: usize) -> Result<Self, String> {
+        if max_colluding >= size {
+            return Err(format!(
+                "Noise committee of {} cannot tolerate {} colluding members",
+                size, max_colluding
+            ));
+        }
+        Ok(NoiseCommittee { size, max_colluding })
+    }
+
+    /// Shares that must stay secret for the full noise to remain
+    pub fn honest(&self) -> usize {
+        self.size - self.max_colluding
+    }
+}
+
+/// A committee member's noise source
+///
+/// Production uses OS entropy; the mock backend and tests use a fixed seed
+/// so noisy results are reproducible.
+pub struct DpNoiseSource {
+    rng: ChaCha20Rng,
+}
+
+impl DpNoiseSource {
+    pub fn from_entropy() -> Self {
+        DpNoiseSource {
+            rng: ChaCha20Rng::from_entropy(),
+        }
+    }
+
+    pub fn seeded(seed: u64) -> Self {
+        DpNoiseSource {
+            rng: ChaCha20Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Sample from the discrete Laplace distribution P(k) ∝ exp(−|k| / scale)
+    pub fn sample_discrete_laplace(&mut self, scale: f64) -> i64 {
+        sample_discrete_laplace(&mut self.rng, scale)
+    }
+
+    /// This member's share of DLap(scale) noise
+    pub fn sample_noise_share(&mut self, scale: f64, committee: &NoiseCommittee) -> i64 {
+        sample_discrete_laplace_share(&mut self.rng, scale, committee.honest())
+    }
+
+    /// This member's encrypted contribution to an event's noise
+    pub fn encrypted_noise_share<B: FheBackend + ?Sized>(
+        &mut self,
+        backend: &B,
+        config: &DpConfig,
+        committee: &NoiseCommittee,
+    ) -> FheCiphertext {
+        backend.encrypt(self.sample_noise_share(config.scale(), committee))
+    }
+}
+
+/// Discrete Laplace as the difference of two i.i.d. geometric samples
+pub fn sample_discrete_laplace<R: RngCore>(rng: &mut R, scale: f64) -> i64 {
+    sample_geometric(rng, scale) - sample_geometric(rng, scale)
+}
+
+/// One of `parts` i.i.d. shares that sum to DLap(scale)
+///
+/// Geometric(q) is Pólya(1, q), and Pólya(r, q) sums over r, so each share is
+/// Pólya(1/parts, q) − Pólya(1/parts, q).
+pub fn sample_discrete_laplace_share<R: RngCore>(rng: &mut R, scale: f64, parts: usize) -> i64 {
+    let shape = 1.0 / parts as f64;
+    sample_polya(rng, scale, shape) - sample_polya(rng, scale, shape)
+}
+
+/// Geometric on {0, 1, ...} with P(k) ∝ exp(−k / scale), by inverse CDF
+fn sample_geometric<R: RngCore>(rng: &mut R, scale: f64) -> i64 {
+    // u in (0, 1] so ln(u) is finite
+    let u: f64 = 1.0 - rng.gen::<f64>();
+    (-scale * u.ln()).floor() as i64
+}
+
+/// Pólya (negative binomial) with real shape r and q = exp(−1 / scale), as
+/// the Gamma(r, q / (1 − q)) mixture of Poissons
+fn sample_polya<R: RngCore>(rng: &mut R, scale: f64, shape: f64) -> i64 {
+    let q = (-1.0 / scale).exp();
+    let rate = Gamma::new(shape, q / (1.0 - q))
+        .expect("shape and scale are positive")
+        .sample(rng);
+    match Poisson::new(rate) {
+        Ok(poisson) => poisson.sample(rng) as i64,
+        // Gamma with a small shape can round to 0
+        Err(_) => 0,
+    }
+}
+
+/// Privacy-budget accountant keyed by provider set
+///
+/// Every reveal over the same set of providers spends ε from that set's
+/// budget; a reveal that would overspend is refused before decryption.
+#[derive(Clone, Debug)]
+pub struct PrivacyAccountant {
+    pub budget: f64,
+    spent: HashMap<BTreeSet<String>, f64>,
+}
+
+impl PrivacyAccountant {
+    pub fn new(budget: f64) -> Result<Self, String> {
+        if !budget.is_finite() || budget <= 0.0 {
+            return Err(format!("Privacy budget must be positive and finite: {}", budget));
+        }
+        Ok(PrivacyAccountant {
+            budget,
+            spent: HashMap::new(),
+        })
+    }
+
+    /// Canonical key for a provider set (order and duplicates ignored);
+    /// ids are free-form, so they are kept whole rather than joined
+    pub fn provider_set_key<S: AsRef<str>>(provider_ids: &[S]) -> BTreeSet<String> {
+        provider_ids.iter().map(|id| id.as_ref().to_string()).collect()
+    }
+
+    pub fn spent<S: AsRef<str>>(&self, provider_ids: &[S]) -> f64 {
+        self.spent
+            .get(&Self::provider_set_key(provider_ids))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    pub fn remaining<S: AsRef<str>>(&self, provider_ids: &[S]) -> f64 {
+        self.budget - self.spent(provider_ids)
+    }
+
+    /// Spend ε for a provider set; returns the remaining budget
+    pub fn charge<S: AsRef<str>>(&mut self, provider_ids: &[S], epsilon: f64) -> Result<f64, String> {
+        let key = Self::provider_set_key(provider_ids);
+        let spent = self.spent.get(&key).copied().unwrap_or(0.0);
+        // Small tolerance so spending the budget in equal parts is not refused
+        if spent + epsilon > self.budget + 1e-9 {
+            return Err(format!(
+                "Privacy budget exhausted for {:?}: spent {}, requested {}, budget {}",
+                key, spent, epsilon, self.budget
+            ));
+        }
+        self.spent.insert(key, spent + epsilon);
+        Ok(self.budget - spent - epsilon)
+    }
+}
+
+/// Encrypted noise shares the committee contributed for one event
+#[derive(Clone, Debug)]
+pub struct EventNoise {
+    pub committee: NoiseCommittee,
+    /// Setting and one share per member for the aggregate
+    pub aggregate: (DpConfig, Vec<FheCiphertext>),
+    /// Setting and one share per member for the dispersion, if computed
+    pub dispersion: Option<(DpConfig, Vec<FheCiphertext>)>,
+}
+
+/// Add the committee's encrypted noise to a ciphertext
+///
+/// Computes: CT_noisy = CT + Σ Enc(nᵢ), one share nᵢ per committee member.
+/// Every member must contribute: with fewer, colluding members could be
+/// most of the contributors.
+pub fn homomorphic_add_noise<B: FheBackend + ?Sized>(
+    backend: &B,
+    ct: &FheCiphertext,
+    committee: &NoiseCommittee,
+    shares: &[FheCiphertext],
+) -> Result<FheCiphertext, String> {
+    if shares.len() != committee.size {
+        return Err(format!(
+            "Need a noise share from each of {} committee members, got {}",
+            committee.size,
+            shares.len()
+        ));
+    }
+    Ok(shares.iter().fold(ct.clone(), |acc, share| backend.add(&acc, share)))
+}
+
+/// Apply the DP mechanism to an aggregation result before decryption
+///
+/// Charges ε (plus the dispersion's ε, if any) against the provider set,
+/// replaces the aggregate with a noisy one and recomputes the difference and
+/// comparison from it. The dispersion and its disagreement flag get the same
+/// treatment, so nothing revealed depends on exact values. Fails without
+/// charging anything if the event computes a dispersion and `noise` has none
+/// for it, or if its aggregate shares were generated for another `config`.
+pub fn apply_differential_privacy<B: FheBackend + ?Sized, S: AsRef<str>>(
+    backend: &B,
+    result: &mut AggregationResult,
+    provider_ids: &[S],
+    config: &DpConfig,
+    accountant: &mut PrivacyAccountant,
+    noise: &EventNoise,
+) -> Result<(), String> {
+    let (aggregate_config, aggregate_shares) = &noise.aggregate;
+    if aggregate_config != config {
+        return Err(format!(
+            "Aggregate noise was generated for ε = {} and sensitivity {}, not ε = {} and sensitivity {}",
+            aggregate_config.epsilon, aggregate_config.sensitivity, config.epsilon, config.sensitivity
+        ));
+    }
+    let dispersion_noise = match (&result.dispersion, &noise.dispersion) {
+        (Some(_), None) => return Err("Dispersion would be revealed without noise".to_string()),
+        (Some(_), Some(dispersion_noise)) => Some(dispersion_noise),
+        (None, _) => None,
+    };
+    let epsilon = config.epsilon + dispersion_noise.map_or(0.0, |(dispersion_config, _)| dispersion_config.epsilon);
+
+    let ct_noisy = homomorphic_add_noise(backend, &result.aggregate_ciphertext, &noise.committee, aggregate_shares)?;
+    let ct_noisy_variance = match (&result.dispersion, dispersion_noise) {
+        (Some(dispersion), Some((_, shares))) => Some(homomorphic_add_noise(
+            backend,
+            &dispersion.scaled_variance_ciphertext,
+            &noise.committee,
+            shares,
+        )?),
+        _ => None,
+    };
+    let remaining = accountant.charge(provider_ids, epsilon)?;
+
+    result.diff_ciphertext = backend.sub(&ct_noisy, &result.threshold_ciphertext);
+    result.comparison_result_ciphertext = backend.gt(&ct_noisy, &result.threshold_ciphertext);
+    result.aggregate_ciphertext = ct_noisy;
+    if let (Some(dispersion), Some(ct_noisy_variance)) = (&mut result.dispersion, ct_noisy_variance) {
+        if let Some(limit) = dispersion.disagreement_limit {
+            dispersion.disagreement_ciphertext = Some(backend.gt(&ct_noisy_variance, &backend.encrypt(limit)));
+        }
+        dispersion.scaled_variance_ciphertext = ct_noisy_variance;
+    }
+
+    result.metadata.insert("dp_epsilon".to_string(), epsilon.to_string());
+    result.metadata.insert("dp_sensitivity".to_string(), config.sensitivity.to_string());
+    result.metadata.insert("dp_budget_remaining".to_string(), remaining.to_string());
+    result.metadata.insert("dp_noise_committee".to_string(), noise.committee.size.to_string());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregation::{
+        oracle_aggregation_workflow, oracle_aggregation_workflow_with_policy, reveal_oracle_result,
+        DisclosurePolicy, ProviderSubmission,
+    };
+    use crate::fhe_module::MockFhe;
+
+    /// One encrypted share per member, each from its own noise source
+    fn shares(backend: &MockFhe, config: &DpConfig, committee: &NoiseCommittee, seed: u64) -> Vec<FheCiphertext> {
+        (0..committee.size as u64)
+            .map(|member| DpNoiseSource::seeded(seed * 100 + member).encrypted_noise_share(backend, config, committee))
+            .collect()
+    }
+
+    fn event_noise(backend: &MockFhe, config: &DpConfig, seed: u64) -> EventNoise {
+        let committee = NoiseCommittee::new(3, 1).unwrap();
+        EventNoise {
+            committee,
+            aggregate: (config.clone(), shares(backend, config, &committee, seed)),
+            dispersion: None,
+        }
+    }
+
+    fn submissions() -> Vec<ProviderSubmission> {
+        [("A", 5000, 2), ("B", 4900, 1), ("C", 5200, 3)]
+            .iter()
+            .map(|(id, value, weight)| ProviderSubmission {
+                provider_id: id.to_string(),
+                quantized_value: *value,
+                weight: *weight,
+                timestamp: 1729418400,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_dp_config_validation() {
+        assert!(DpConfig::new(0.0, 10).is_err());
+        assert!(DpConfig::new(f64::NAN, 10).is_err());
+        assert!(DpConfig::new(1.0, 0).is_err());
+
+        let config = DpConfig::for_percent_market(0.5, 3).unwrap();
+        assert_eq!(config.sensitivity, 30000);
+        assert_eq!(config.scale(), 60000.0);
+
+        let dispersion = DpConfig::for_percent_dispersion(1.0, 3, 6).unwrap();
+        assert_eq!(dispersion.sensitivity, 2 * 6 * 3 * 10000 * 10000);
+        assert!(DpConfig::for_percent_dispersion(1.0, 1 << 20, 1 << 20).is_err());
+
+        assert!(NoiseCommittee::new(3, 3).is_err());
+        assert_eq!(NoiseCommittee::new(5, 2).unwrap().honest(), 3);
+    }
+
+    #[test]
+    fn test_honest_noise_shares_sum_to_discrete_laplace() {
+        // 5 members tolerating 2 colluders: the 3 honest shares alone must
+        // have the spread of DLap(b), E|X| = 2q / (1 - q²)
+        let scale = 50.0;
+        let committee = NoiseCommittee::new(5, 2).unwrap();
+        let mut honest: Vec<DpNoiseSource> = (0..3).map(DpNoiseSource::seeded).collect();
+        let n = 20000;
+        let samples: Vec<i64> = (0..n)
+            .map(|_| honest.iter_mut().map(|m| m.sample_noise_share(scale, &committee)).sum())
+            .collect();
+
+        let mean = samples.iter().sum::<i64>() as f64 / n as f64;
+        let mean_abs = samples.iter().map(|x| x.abs()).sum::<i64>() as f64 / n as f64;
+        let q = (-1.0 / scale).exp();
+        let expected_abs = 2.0 * q / (1.0 - q * q);
+
+        assert!(mean.abs() < 2.0, "mean {} too far from 0", mean);
+        assert!((mean_abs - expected_abs).abs() / expected_abs < 0.05);
+    }
+
+    #[test]
+    fn test_noise_needs_a_share_from_every_member() {
+        let backend = MockFhe;
+        let config = DpConfig::new(1.0, 100).unwrap();
+        let mut noise = event_noise(&backend, &config, 1);
+        noise.aggregate.1.pop();
+
+        let mut accountant = PrivacyAccountant::new(10.0).unwrap();
+        let mut result = oracle_aggregation_workflow(&backend, &submissions(), 5000);
+        let ids = ["A", "B", "C"];
+        assert!(apply_differential_privacy(&backend, &mut result, &ids, &config, &mut accountant, &noise).is_err());
+        assert_eq!(accountant.spent(&ids), 0.0);
+    }
+
+    #[test]
+    fn test_noise_must_match_the_charged_config() {
+        let backend = MockFhe;
+        let config = DpConfig::new(1.0, 100).unwrap();
+        let noise = event_noise(&backend, &config, 1);
+
+        // Shares calibrated for ε = 1 cannot be charged as ε = 0.1, nor
+        // applied for a larger sensitivity
+        let mut accountant = PrivacyAccountant::new(10.0).unwrap();
+        let mut result = oracle_aggregation_workflow(&backend, &submissions(), 5000);
+        let ids = ["A", "B", "C"];
+        for other in [DpConfig::new(0.1, 100).unwrap(), DpConfig::new(1.0, 1000).unwrap()] {
+            assert!(apply_differential_privacy(&backend, &mut result, &ids, &other, &mut accountant, &noise).is_err());
+        }
+        assert_eq!(accountant.spent(&ids), 0.0);
+        apply_differential_privacy(&backend, &mut result, &ids, &config, &mut accountant, &noise).unwrap();
+        assert_eq!(accountant.spent(&ids), 1.0);
+    }
+
+    #[test]
+    fn test_dispersion_is_noised_or_refused() {
+        let backend = MockFhe;
+        let subs = submissions();
+        let ids: Vec<&str> = subs.iter().map(|s| s.provider_id.as_str()).collect();
+        let policy = DisclosurePolicy {
+            compute_dispersion: true,
+            reveal_dispersion: true,
+            max_std_dev: Some(100),
+        };
+        let config = DpConfig::for_percent_market(1.0, 3).unwrap();
+        let dispersion_config = DpConfig::for_percent_dispersion(1.0, 3, 6).unwrap();
+        let mut accountant = PrivacyAccountant::new(10.0).unwrap();
+
+        // No dispersion noise: refused before anything is charged
+        let mut result = oracle_aggregation_workflow_with_policy(&backend, &subs, 5000, &policy).unwrap();
+        let exact_variance = backend.decrypt(&result.dispersion.as_ref().unwrap().scaled_variance_ciphertext);
+        let noise = event_noise(&backend, &config, 2);
+        assert!(apply_differential_privacy(&backend, &mut result, &ids, &config, &mut accountant, &noise).is_err());
+        assert_eq!(accountant.spent(&ids), 0.0);
+
+        let noise = EventNoise {
+            dispersion: Some((
+                dispersion_config.clone(),
+                shares(&backend, &dispersion_config, &noise.committee, 3),
+            )),
+            ..noise
+        };
+        apply_differential_privacy(&backend, &mut result, &ids, &config, &mut accountant, &noise).unwrap();
+        assert_eq!(accountant.spent(&ids), 2.0);
+
+        let dispersion = result.dispersion.as_ref().unwrap();
+        let noisy_variance = backend.decrypt(&dispersion.scaled_variance_ciphertext);
+        assert_ne!(noisy_variance, exact_variance);
+        // The flag is recomputed from the noisy variance
+        let limit = dispersion.disagreement_limit.unwrap();
+        let flag = backend.decrypt(dispersion.disagreement_ciphertext.as_ref().unwrap());
+        assert_eq!(flag, (noisy_variance > limit) as i64);
+    }
+
+    #[test]
+    fn test_seeded_noise_is_deterministic() {
+        let mut a = DpNoiseSource::seeded(7);
+        let mut b = DpNoiseSource::seeded(7);
+        let xs: Vec<i64> = (0..32).map(|_| a.sample_discrete_laplace(100.0)).collect();
+        let ys: Vec<i64> = (0..32).map(|_| b.sample_discrete_laplace(100.0)).collect();
+        assert_eq!(xs, ys);
+        assert!(xs.iter().any(|&x| x != 0));
+    }
+
+    #[test]
+    fn test_discrete_laplace_is_centered_with_expected_spread() {
+        // E|X| for DLap with q = e^(-1/b) is 2q / (1 - q²); ≈ b for large b
+        let scale = 50.0;
+        let mut noise = DpNoiseSource::seeded(42);
+        let n = 20000;
+        let samples: Vec<i64> = (0..n).map(|_| noise.sample_discrete_laplace(scale)).collect();
+
+        let mean = samples.iter().sum::<i64>() as f64 / n as f64;
+        let mean_abs = samples.iter().map(|x| x.abs()).sum::<i64>() as f64 / n as f64;
+        let q = (-1.0 / scale).exp();
+        let expected_abs = 2.0 * q / (1.0 - q * q);
+
+        assert!(mean.abs() < 2.0, "mean {} too far from 0", mean);
+        assert!((mean_abs - expected_abs).abs() / expected_abs < 0.05);
+    }
+
+    #[test]
+    fn test_accountant_tracks_budget_per_provider_set() {
+        let mut accountant = PrivacyAccountant::new(1.0).unwrap();
+
+        assert!(accountant.charge(&["A", "B"], 0.5).is_ok());
+        // Same set in a different order shares the budget
+        assert!(accountant.charge(&["B", "A"], 0.5).is_ok());
+        assert!(accountant.charge(&["A", "B"], 0.1).is_err());
+        assert_eq!(accountant.remaining(&["A", "B"]), 0.0);
+
+        // A different set has its own budget
+        assert!(accountant.charge(&["A", "B", "C"], 0.5).is_ok());
+        assert_eq!(accountant.spent(&["C", "B", "A"]), 0.5);
+
+        // Ids are free-form: one provider "A,B" is not the set {A, B}
+        assert_eq!(accountant.spent(&["A,B"]), 0.0);
+        assert!(accountant.charge(&["A,B"], 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_noisy_reveal_is_reproducible_and_recomputes_comparison() {
+        let backend = MockFhe;
+        let subs = submissions();
+        let ids: Vec<&str> = subs.iter().map(|s| s.provider_id.as_str()).collect();
+        let exact: i64 = subs.iter().map(|s| s.quantized_value * s.weight).sum();
+        let config = DpConfig::for_percent_market(1.0, 3).unwrap();
+
+        let run = |seed: u64| {
+            let mut accountant = PrivacyAccountant::new(10.0).unwrap();
+            let noise = event_noise(&backend, &config, seed);
+            // Threshold exactly at the true aggregate: only noise decides
+            let mut result = oracle_aggregation_workflow(&backend, &subs, exact);
+            apply_differential_privacy(&backend, &mut result, &ids, &config, &mut accountant, &noise).unwrap();
+            reveal_oracle_result(&backend, &result).unwrap()
+        };
+
+        let first = run(1);
+        let second = run(1);
+        assert_eq!(first.aggregate_value, second.aggregate_value);
+        assert_ne!(first.aggregate_value, exact);
+        assert_eq!(first.is_above_threshold, first.aggregate_value > exact);
+    }
+
+    #[test]
+    fn test_reveal_refused_when_budget_exhausted() {
+        let backend = MockFhe;
+        let subs = submissions();
+        let ids: Vec<&str> = subs.iter().map(|s| s.provider_id.as_str()).collect();
+        let config = DpConfig::new(0.6, 30000).unwrap();
+        let mut accountant = PrivacyAccountant::new(1.0).unwrap();
+        let noise = event_noise(&backend, &config, 3);
+
+        let mut first = oracle_aggregation_workflow(&backend, &subs, 5000);
+        assert!(apply_differential_privacy(&backend, &mut first, &ids, &config, &mut accountant, &noise).is_ok());
+        assert_eq!(first.metadata.get("dp_epsilon").unwrap(), "0.6");
+
+        let mut second = oracle_aggregation_workflow(&backend, &subs, 5000);
+        let before = backend.decrypt(&second.aggregate_ciphertext);
+        assert!(apply_differential_privacy(&backend, &mut second, &ids, &config, &mut accountant, &noise).is_err());
+        // Refused before touching the ciphertext
+        assert_eq!(backend.decrypt(&second.aggregate_ciphertext), before);
+    }
+}
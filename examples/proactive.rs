@@ -0,0 +1,391 @@
+//! Proactive Share Refresh and Committee Resharing
+//!
+//! Refresh: every holder deals a random degree k−1 sharing of zero and each
+//! holder adds the sub-shares it receives. The secret does not change, but
+//! shares from different epochs no longer combine, so an attacker has to
+//! steal k shares within one epoch instead of over the key's lifetime.
+//!
+//! Resharing: each member i of a k-quorum of the old committee deals its
+//! share xᵢ with a fresh degree k′−1 polynomial to the new ids. New holder j
+//! keeps x′ⱼ = ∑ λᵢ·sᵢⱼ, a k′-of-n′ share of the same secret, so ciphertexts
+//! under the shared key stay valid.
+//!
+//! Over Ristretto255 the dealings carry Feldman commitments: a refresh
+//! commitment must open to zero at x = 0, and a reshare commitment to the
+//! dealer's published verification share xᵢ·G.
+
+use crate::field::{lagrange_coefficients, PrimeField, Scalar25519};
+use crate::shamir::Polynomial;
+use crate::vss::FeldmanCommitment;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::traits::Identity;
+use rand::{CryptoRng, RngCore};
+use std::collections::{HashMap, HashSet};
+
+/// Sub-shares one dealer sends to each recipient
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dealing<F: PrimeField> {
+    pub dealer: u32,
+    pub sub_shares: HashMap<u32, F>,
+}
+
+impl<F: PrimeField> Dealing<F> {
+    fn sub_share(&self, id: u32) -> Result<F, String> {
+        self.sub_shares
+            .get(&id)
+            .copied()
+            .ok_or_else(|| format!("Dealer {} sent no sub-share to {}", self.dealer, id))
+    }
+}
+
+fn check_recipients(threshold: u32, recipients: &[u32]) -> Result<(), String> {
+    let unique: HashSet<u32> = recipients.iter().copied().collect();
+    if unique.len() != recipients.len() {
+        return Err("Duplicate recipient ids".to_string());
+    }
+    if recipients.contains(&0) {
+        return Err("Share id 0 would reveal the secret".to_string());
+    }
+    if threshold == 0 || threshold as usize > recipients.len() {
+        return Err(format!(
+            "Invalid threshold {} for {} recipients",
+            threshold,
+            recipients.len()
+        ));
+    }
+    Ok(())
+}
+
+fn deal_polynomial<F: PrimeField>(dealer: u32, polynomial: &Polynomial<F>, recipients: &[u32]) -> Dealing<F> {
+    Dealing {
+        dealer,
+        sub_shares: recipients
+            .iter()
+            .map(|&id| (id, polynomial.evaluate(F::from_u64(id as u64))))
+            .collect(),
+    }
+}
+
+/// Deal a random k-of-n sharing of zero to `holders`
+pub fn deal_zero_sharing<F: PrimeField, R: RngCore + CryptoRng>(
+    dealer: u32,
+    threshold: u32,
+    holders: &[u32],
+    rng: &mut R,
+) -> Result<Dealing<F>, String> {
+    check_recipients(threshold, holders)?;
+    let polynomial = Polynomial::random(F::ZERO, threshold as usize - 1, rng);
+    Ok(deal_polynomial(dealer, &polynomial, holders))
+}
+
+/// Add every zero sub-share addressed to `id` to its current share
+pub fn apply_refresh<F: PrimeField>(id: u32, share: F, dealings: &[Dealing<F>]) -> Result<F, String> {
+    dealings
+        .iter()
+        .try_fold(share, |acc, dealing| Ok(acc + dealing.sub_share(id)?))
+}
+
+/// Run one refresh epoch in which every holder deals
+pub fn refresh_shares<F: PrimeField, R: RngCore + CryptoRng>(
+    shares: &HashMap<u32, F>,
+    threshold: u32,
+    rng: &mut R,
+) -> Result<HashMap<u32, F>, String> {
+    let mut holders: Vec<u32> = shares.keys().copied().collect();
+    holders.sort_unstable();
+    let dealings = holders
+        .iter()
+        .map(|&dealer| deal_zero_sharing(dealer, threshold, &holders, rng))
+        .collect::<Result<Vec<Dealing<F>>, String>>()?;
+
+    shares
+        .iter()
+        .map(|(&id, &share)| Ok((id, apply_refresh(id, share, &dealings)?)))
+        .collect()
+}
+
+/// Deal `share` as the secret of a fresh k′-of-n′ sharing to `new_ids`
+pub fn deal_reshare<F: PrimeField, R: RngCore + CryptoRng>(
+    dealer: u32,
+    share: F,
+    new_threshold: u32,
+    new_ids: &[u32],
+    rng: &mut R,
+) -> Result<Dealing<F>, String> {
+    check_recipients(new_threshold, new_ids)?;
+    let polynomial = Polynomial::random(share, new_threshold as usize - 1, rng);
+    Ok(deal_polynomial(dealer, &polynomial, new_ids))
+}
+
+/// x′ⱼ = ∑ λᵢ·sᵢⱼ over the dealers, who must form a quorum of the old committee
+pub fn combine_reshare<F: PrimeField>(
+    id: u32,
+    dealings: &[Dealing<F>],
+    old_threshold: u32,
+) -> Result<F, String> {
+    if dealings.len() < old_threshold as usize {
+        return Err(format!(
+            "Need {} old shareholders to reshare, only have {}",
+            old_threshold,
+            dealings.len()
+        ));
+    }
+    let dealers: Vec<F> = dealings.iter().map(|d| F::from_u64(d.dealer as u64)).collect();
+    let lambdas = lagrange_coefficients(&dealers, F::ZERO)?;
+    dealings
+        .iter()
+        .zip(lambdas)
+        .try_fold(F::ZERO, |acc, (dealing, lambda)| Ok(acc + lambda * dealing.sub_share(id)?))
+}
+
+/// Move k-of-n `shares` to a k′-of-n′ committee on `new_ids`
+///
+/// The lowest `old_threshold` old ids deal; the rest take no part.
+pub fn reshare_shares<F: PrimeField, R: RngCore + CryptoRng>(
+    shares: &HashMap<u32, F>,
+    old_threshold: u32,
+    new_threshold: u32,
+    new_ids: &[u32],
+    rng: &mut R,
+) -> Result<HashMap<u32, F>, String> {
+    let mut dealers: Vec<u32> = shares.keys().copied().collect();
+    dealers.sort_unstable();
+    dealers.truncate(old_threshold as usize);
+    let dealings = dealers
+        .iter()
+        .map(|&dealer| deal_reshare(dealer, shares[&dealer], new_threshold, new_ids, rng))
+        .collect::<Result<Vec<Dealing<F>>, String>>()?;
+
+    new_ids
+        .iter()
+        .map(|&id| Ok((id, combine_reshare(id, &dealings, old_threshold)?)))
+        .collect()
+}
+
+/// Dealing over Ristretto255 with a Feldman commitment to its polynomial
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifiableDealing {
+    pub dealing: Dealing<Scalar25519>,
+    pub commitment: FeldmanCommitment,
+}
+
+impl VerifiableDealing {
+    /// Committed zero sharing for a refresh epoch
+    pub fn zero_sharing<R: RngCore + CryptoRng>(
+        dealer: u32,
+        threshold: u32,
+        holders: &[u32],
+        rng: &mut R,
+    ) -> Result<Self, String> {
+        check_recipients(threshold, holders)?;
+        let polynomial = Polynomial::random(Scalar25519::ZERO, threshold as usize - 1, rng);
+        Ok(VerifiableDealing {
+            dealing: deal_polynomial(dealer, &polynomial, holders),
+            commitment: FeldmanCommitment::from_polynomial(&polynomial),
+        })
+    }
+
+    /// Committed resharing of the dealer's share
+    pub fn reshare<R: RngCore + CryptoRng>(
+        dealer: u32,
+        share: Scalar25519,
+        new_threshold: u32,
+        new_ids: &[u32],
+        rng: &mut R,
+    ) -> Result<Self, String> {
+        check_recipients(new_threshold, new_ids)?;
+        let polynomial = Polynomial::random(share, new_threshold as usize - 1, rng);
+        Ok(VerifiableDealing {
+            dealing: deal_polynomial(dealer, &polynomial, new_ids),
+            commitment: FeldmanCommitment::from_polynomial(&polynomial),
+        })
+    }
+
+    /// Recipient check of a refresh sub-share: degree k−1, secret zero
+    pub fn verify_refresh(&self, threshold: u32, id: u32) -> Result<(), String> {
+        if self.commitment.public_secret() != RistrettoPoint::identity() {
+            return Err(format!("Dealer {} refreshes with a non-zero secret", self.dealing.dealer));
+        }
+        self.verify_sub_share(threshold, id)
+    }
+
+    /// Recipient check of a reshare sub-share against the dealer's old
+    /// verification share xᵢ·G
+    pub fn verify_reshare(
+        &self,
+        dealer_verification_share: RistrettoPoint,
+        new_threshold: u32,
+        id: u32,
+    ) -> Result<(), String> {
+        if self.commitment.public_secret() != dealer_verification_share {
+            return Err(format!(
+                "Dealer {} reshares a value other than its key share",
+                self.dealing.dealer
+            ));
+        }
+        self.verify_sub_share(new_threshold, id)
+    }
+
+    fn verify_sub_share(&self, threshold: u32, id: u32) -> Result<(), String> {
+        if self.commitment.threshold() != threshold as usize {
+            return Err(format!(
+                "Dealer {} committed to degree {}, expected {}",
+                self.dealing.dealer,
+                self.commitment.threshold().saturating_sub(1),
+                threshold - 1
+            ));
+        }
+        if !self.commitment.verify_share(id, self.dealing.sub_share(id)?) {
+            return Err(format!(
+                "Sub-share from dealer {} to {} does not match its commitment",
+                self.dealing.dealer, id
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// New verification share x′ⱼ·G = ∑ λᵢ·Cᵢ(j), computable by anyone
+pub fn reshared_verification_share(dealings: &[VerifiableDealing], id: u32) -> Result<RistrettoPoint, String> {
+    let dealers: Vec<Scalar25519> = dealings
+        .iter()
+        .map(|d| Scalar25519::from(d.dealing.dealer))
+        .collect();
+    let lambdas = lagrange_coefficients(&dealers, Scalar25519::ZERO)?;
+    Ok(dealings
+        .iter()
+        .zip(lambdas)
+        .map(|(d, lambda)| d.commitment.share_commitment(id) * lambda.scalar())
+        .sum())
+}
+
+/// x·G for a share, as published next to it
+pub fn verification_share(share: Scalar25519) -> RistrettoPoint {
+    RISTRETTO_BASEPOINT_POINT * share.scalar()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dkg::run_dkg;
+    use crate::field::{interpolate_at, FieldElement};
+    use crate::shamir::ShamirSchemeModular;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    fn secret_of<F: PrimeField>(shares: &HashMap<u32, F>, ids: &[u32]) -> F {
+        let points: Vec<(F, F)> = ids
+            .iter()
+            .map(|&id| (F::from_u64(id as u64), shares[&id]))
+            .collect();
+        interpolate_at(&points, F::ZERO).unwrap()
+    }
+
+    #[test]
+    fn test_refresh_keeps_secret_and_changes_every_share() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let scheme = ShamirSchemeModular::<FieldElement>::new(3, 5).unwrap();
+        let shares = scheme.deal(FieldElement::new(42), &mut rng);
+
+        let refreshed = refresh_shares(&shares, 3, &mut rng).unwrap();
+        assert_eq!(scheme.recover_secret(&refreshed), Some(FieldElement::new(42)));
+        for (id, share) in &shares {
+            assert_ne!(refreshed[id], *share);
+        }
+    }
+
+    #[test]
+    fn test_shares_from_different_epochs_do_not_combine() {
+        let mut rng = ChaCha20Rng::seed_from_u64(2);
+        let scheme = ShamirSchemeModular::<FieldElement>::new(3, 5).unwrap();
+        let old = scheme.deal(FieldElement::new(42), &mut rng);
+        let new = refresh_shares(&old, 3, &mut rng).unwrap();
+
+        // Two shares stolen before the refresh, one after
+        let mixed: HashMap<u32, FieldElement> =
+            [(1, old[&1]), (2, old[&2]), (3, new[&3])].into_iter().collect();
+        assert_ne!(secret_of(&mixed, &[1, 2, 3]), FieldElement::new(42));
+    }
+
+    #[test]
+    fn test_reshare_to_larger_and_smaller_committees() {
+        let mut rng = ChaCha20Rng::seed_from_u64(3);
+        let scheme = ShamirSchemeModular::<FieldElement>::new(3, 5).unwrap();
+        let shares = scheme.deal(FieldElement::new(42), &mut rng);
+
+        // 3-of-5 → 4-of-7 with all-new ids
+        let grown = reshare_shares(&shares, 3, 4, &[10, 11, 12, 13, 14, 15, 16], &mut rng).unwrap();
+        assert_eq!(secret_of(&grown, &[10, 12, 14, 16]), FieldElement::new(42));
+        assert_ne!(secret_of(&grown, &[10, 12, 14]), FieldElement::new(42));
+
+        // 4-of-7 → 2-of-3, keeping two members and adding one
+        let shrunk = reshare_shares(&grown, 4, 2, &[11, 12, 20], &mut rng).unwrap();
+        assert_eq!(secret_of(&shrunk, &[12, 20]), FieldElement::new(42));
+    }
+
+    #[test]
+    fn test_reshare_needs_an_old_quorum() {
+        let mut rng = ChaCha20Rng::seed_from_u64(4);
+        let scheme = ShamirSchemeModular::<FieldElement>::new(3, 5).unwrap();
+        let shares = scheme.deal(FieldElement::new(42), &mut rng);
+
+        let dealings: Vec<Dealing<FieldElement>> = [1, 2]
+            .iter()
+            .map(|&i| deal_reshare(i, shares[&i], 2, &[7, 8], &mut rng).unwrap())
+            .collect();
+        assert!(combine_reshare(7, &dealings, 3).is_err());
+        assert!(reshare_shares(&shares, 3, 4, &[7, 8, 9], &mut rng).is_err());
+        assert!(reshare_shares(&shares, 3, 2, &[7, 7, 9], &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_verifiable_refresh_rejects_non_zero_dealing() {
+        let mut rng = ChaCha20Rng::seed_from_u64(5);
+        let holders = [1, 2, 3, 4];
+        let honest = VerifiableDealing::zero_sharing(1, 3, &holders, &mut rng).unwrap();
+        for &id in &holders {
+            assert!(honest.verify_refresh(3, id).is_ok());
+        }
+
+        // A dealer sharing 1 instead of 0 would shift the key
+        let cheat = VerifiableDealing::reshare(2, Scalar25519::ONE, 3, &holders, &mut rng).unwrap();
+        assert!(cheat.verify_refresh(3, 1).is_err());
+
+        let mut tampered = honest.clone();
+        *tampered.dealing.sub_shares.get_mut(&2).unwrap() += Scalar25519::ONE;
+        assert!(tampered.verify_refresh(3, 2).is_err());
+        assert!(honest.verify_refresh(2, 1).is_err());
+    }
+
+    #[test]
+    fn test_verifiable_reshare_of_dkg_key_keeps_public_key() {
+        let mut rng = ChaCha20Rng::seed_from_u64(6);
+        let outputs = run_dkg(&[1, 2, 3, 4, 5], 3, &HashMap::new(), &mut rng).unwrap();
+        let public_key = outputs[&1].public_key;
+        let new_ids = [6, 7, 8, 9];
+
+        let dealings: Vec<VerifiableDealing> = [2, 4, 5]
+            .iter()
+            .map(|&i| VerifiableDealing::reshare(i, *outputs[&i].key_share.expose(), 2, &new_ids, &mut rng).unwrap())
+            .collect();
+
+        let mut new_shares = HashMap::new();
+        for &id in &new_ids {
+            for dealing in &dealings {
+                let dealer = &outputs[&dealing.dealing.dealer];
+                dealing.verify_reshare(dealer.verification_share(), 2, id).unwrap();
+            }
+            let plain: Vec<Dealing<Scalar25519>> = dealings.iter().map(|d| d.dealing.clone()).collect();
+            let share = combine_reshare(id, &plain, 3).unwrap();
+            assert_eq!(reshared_verification_share(&dealings, id).unwrap(), verification_share(share));
+            new_shares.insert(id, share);
+        }
+
+        assert_eq!(verification_share(secret_of(&new_shares, &[7, 9])), public_key);
+
+        // Resharing something other than the key share is caught
+        let forged = VerifiableDealing::reshare(3, Scalar25519::ONE, 2, &new_ids, &mut rng).unwrap();
+        assert!(forged.verify_reshare(outputs[&3].verification_share(), 2, 6).is_err());
+    }
+}
@@ -0,0 +1,285 @@
+//! Robust Reconstruction of Shamir Secrets
+//!
+//! Shamir shares of a degree k−1 polynomial form a Reed–Solomon codeword, so
+//! reconstruction can use every available share and correct corrupted ones.
+//! Berlekamp–Welch decoding with n shares corrects up to ⌊(n − k)/2⌋ bad
+//! shares and reports which share ids were faulty.
+//!
+//! Unlike plain interpolation, decoding is not constant time: Gaussian
+//! elimination picks pivots based on the share values. Use it when faults
+//! are suspected; `crate::field::interpolate_at` is the constant-time path.
+
+use crate::field::{evaluate_polynomial, PrimeField};
+
+/// Secret recovered by error-correcting decoding
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RobustReconstruction<F: PrimeField> {
+    pub secret: F,
+    /// Share ids whose values do not lie on the decoded polynomial (sorted)
+    pub faulty_ids: Vec<u32>,
+    /// Coefficients of the decoded polynomial, constant term first
+    pub polynomial: Vec<F>,
+}
+
+/// Maximum number of corrupted shares correctable from `available` shares
+pub fn correctable_errors(available: usize, threshold: usize) -> usize {
+    available.saturating_sub(threshold) / 2
+}
+
+/// Reconstruct from all `shares` (id, value), correcting up to
+/// ⌊(n − k)/2⌋ corrupted values
+///
+/// Fails if fewer than `threshold` shares are given, if ids repeat, or if
+/// the shares are too corrupted to decode. Corruption beyond the decoding
+/// radius is caught only when no other degree k−1 polynomial fits, so the
+/// share count should leave room for the expected number of faults.
+pub fn robust_reconstruct<F: PrimeField>(
+    shares: &[(u32, F)],
+    threshold: usize,
+) -> Result<RobustReconstruction<F>, String> {
+    if threshold == 0 {
+        return Err("Threshold must be at least 1".to_string());
+    }
+    if shares.len() < threshold {
+        return Err(format!(
+            "Need {} shares, only have {}",
+            threshold,
+            shares.len()
+        ));
+    }
+    let mut ids: Vec<u32> = shares.iter().map(|&(id, _)| id).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    if ids.len() != shares.len() {
+        return Err("Duplicate share ids".to_string());
+    }
+
+    let points: Vec<(F, F)> = shares
+        .iter()
+        .map(|&(id, y)| (F::from_u64(id as u64), y))
+        .collect();
+    let max_errors = correctable_errors(shares.len(), threshold);
+
+    let polynomial = berlekamp_welch(&points, threshold, max_errors).ok_or_else(|| {
+        format!(
+            "Shares inconsistent: more than {} of {} corrupted",
+            max_errors,
+            shares.len()
+        )
+    })?;
+
+    let mut faulty_ids: Vec<u32> = shares
+        .iter()
+        .zip(&points)
+        .filter(|(_, &(x, y))| evaluate_polynomial(&polynomial, x) != y)
+        .map(|(&(id, _), _)| id)
+        .collect();
+    faulty_ids.sort_unstable();
+    if faulty_ids.len() > max_errors {
+        return Err(format!(
+            "Shares inconsistent: more than {} of {} corrupted",
+            max_errors,
+            shares.len()
+        ));
+    }
+
+    Ok(RobustReconstruction {
+        secret: polynomial[0],
+        faulty_ids,
+        polynomial,
+    })
+}
+
+/// Berlekamp–Welch: find monic E (deg e) and Q (deg < e + k) with
+/// Q(x_i) = y_i·E(x_i) for all i; then P = Q / E
+///
+/// Returns P's k coefficients, or None if no such P exists.
+fn berlekamp_welch<F: PrimeField>(points: &[(F, F)], k: usize, e: usize) -> Option<Vec<F>> {
+    // Unknowns: q_0..q_{e+k−1}, then E's low coefficients e_0..e_{e−1}
+    // Row i: ∑ q_j x^j − y ∑ e_j x^j = y x^e
+    let q_len = e + k;
+    let columns = q_len + e;
+    let mut rows: Vec<Vec<F>> = points
+        .iter()
+        .map(|&(x, y)| {
+            let mut row = Vec::with_capacity(columns + 1);
+            let mut power = F::ONE;
+            let mut powers = Vec::with_capacity(q_len + 1);
+            for _ in 0..=q_len {
+                powers.push(power);
+                power *= x;
+            }
+            row.extend_from_slice(&powers[..q_len]);
+            row.extend(powers[..e].iter().map(|&p| -(y * p)));
+            row.push(y * powers[e]);
+            row
+        })
+        .collect();
+
+    let solution = solve_linear_system(&mut rows, columns)?;
+    let q = &solution[..q_len];
+    let mut error_locator = solution[q_len..].to_vec();
+    error_locator.push(F::ONE);
+
+    let (quotient, remainder) = divide(q, &error_locator);
+    if remainder.iter().any(|c| !c.is_zero()) {
+        return None;
+    }
+    let mut polynomial = quotient;
+    polynomial.resize(k.max(polynomial.len()), F::ZERO);
+    if polynomial[k..].iter().any(|c| !c.is_zero()) {
+        return None;
+    }
+    polynomial.truncate(k);
+    Some(polynomial)
+}
+
+/// Gaussian elimination on an augmented matrix; free variables are set to 0
+fn solve_linear_system<F: PrimeField>(rows: &mut [Vec<F>], columns: usize) -> Option<Vec<F>> {
+    let mut pivot_columns = Vec::new();
+    let mut rank = 0;
+
+    for column in 0..columns {
+        let Some(pivot) = (rank..rows.len()).find(|&r| !rows[r][column].is_zero()) else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+        let inverse = rows[rank][column].inverse()?;
+        for value in rows[rank].iter_mut() {
+            *value *= inverse;
+        }
+        let pivot_row = rows[rank].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r != rank && !row[column].is_zero() {
+                let factor = row[column];
+                for (value, &p) in row[column..].iter_mut().zip(&pivot_row[column..]) {
+                    *value -= factor * p;
+                }
+            }
+        }
+        pivot_columns.push(column);
+        rank += 1;
+    }
+
+    // A zero row with a non-zero right-hand side means no solution
+    if rows[rank..].iter().any(|row| !row[columns].is_zero()) {
+        return None;
+    }
+
+    let mut solution = vec![F::ZERO; columns];
+    for (r, &column) in pivot_columns.iter().enumerate() {
+        solution[column] = rows[r][columns];
+    }
+    Some(solution)
+}
+
+/// Polynomial long division by a monic divisor
+fn divide<F: PrimeField>(dividend: &[F], divisor: &[F]) -> (Vec<F>, Vec<F>) {
+    let divisor_degree = divisor.len() - 1;
+    if dividend.len() <= divisor_degree {
+        return (vec![F::ZERO], dividend.to_vec());
+    }
+
+    let mut remainder = dividend.to_vec();
+    let mut quotient = vec![F::ZERO; dividend.len() - divisor_degree];
+    for i in (0..quotient.len()).rev() {
+        let coefficient = remainder[i + divisor_degree];
+        quotient[i] = coefficient;
+        for (j, &d) in divisor.iter().enumerate() {
+            remainder[i + j] -= coefficient * d;
+        }
+    }
+    remainder.truncate(divisor_degree);
+    (quotient, remainder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::{FieldElement, Scalar25519};
+    use crate::shamir::Polynomial;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    fn deal<F: PrimeField>(secret: F, k: usize, n: u32, seed: u64) -> Vec<(u32, F)> {
+        let polynomial = Polynomial::random(secret, k - 1, &mut ChaCha20Rng::seed_from_u64(seed));
+        let mut shares: Vec<(u32, F)> = polynomial.shares(n).into_iter().collect();
+        shares.sort_unstable_by_key(|&(id, _)| id);
+        shares
+    }
+
+    fn corrupt<F: PrimeField>(shares: &mut [(u32, F)], ids: &[u32]) {
+        for (id, value) in shares.iter_mut() {
+            if ids.contains(id) {
+                *value += F::from_u64(999 * *id as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn test_clean_shares_decode_without_faults() {
+        let shares = deal(FieldElement::new(42), 3, 5, 1);
+        let result = robust_reconstruct(&shares, 3).unwrap();
+        assert_eq!(result.secret, FieldElement::new(42));
+        assert!(result.faulty_ids.is_empty());
+        assert_eq!(result.polynomial.len(), 3);
+    }
+
+    #[test]
+    fn test_corrects_up_to_half_the_redundancy() {
+        // 3-of-7: corrects ⌊(7 − 3)/2⌋ = 2 bad shares
+        let mut shares = deal(FieldElement::new(42), 3, 7, 2);
+        corrupt(&mut shares, &[2, 6]);
+
+        let result = robust_reconstruct(&shares, 3).unwrap();
+        assert_eq!(result.secret, FieldElement::new(42));
+        assert_eq!(result.faulty_ids, vec![2, 6]);
+    }
+
+    #[test]
+    fn test_single_fault_in_3_of_5() {
+        let secret = Scalar25519::from_bytes_mod_order([7; 32]);
+        for bad in 1..=5 {
+            let mut shares = deal(secret, 3, 5, 3);
+            corrupt(&mut shares, &[bad]);
+            let result = robust_reconstruct(&shares, 3).unwrap();
+            assert_eq!(result.secret, secret);
+            assert_eq!(result.faulty_ids, vec![bad]);
+        }
+    }
+
+    #[test]
+    fn test_too_many_faults_are_reported_not_miscorrected() {
+        let mut shares = deal(FieldElement::new(42), 3, 5, 4);
+        corrupt(&mut shares, &[1, 4]);
+        assert!(robust_reconstruct(&shares, 3).is_err());
+    }
+
+    #[test]
+    fn test_detects_but_cannot_correct_with_one_spare_share() {
+        // 3-of-4: zero correctable errors, but inconsistency is still detected
+        let mut shares = deal(FieldElement::new(42), 3, 4, 5);
+        assert!(robust_reconstruct(&shares, 3).is_ok());
+        corrupt(&mut shares, &[3]);
+        assert_eq!(correctable_errors(4, 3), 0);
+        assert!(robust_reconstruct(&shares, 3).is_err());
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        let shares = deal(FieldElement::new(42), 3, 5, 6);
+        assert!(robust_reconstruct(&shares[..2], 3).is_err());
+        assert!(robust_reconstruct(&shares, 0).is_err());
+        let duplicated = vec![shares[0], shares[0], shares[1]];
+        assert!(robust_reconstruct(&duplicated, 2).is_err());
+    }
+
+    #[test]
+    fn test_polynomial_division() {
+        // (x^2 + 3x + 2) / (x + 1) = x + 2
+        let f = |v: u64| FieldElement::new(v);
+        let (q, r) = divide(&[f(2), f(3), f(1)], &[f(1), f(1)]);
+        assert_eq!(q, vec![f(2), f(1)]);
+        assert!(r.iter().all(|c| c.is_zero()));
+    }
+}
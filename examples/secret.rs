@@ -0,0 +1,106 @@
+//! Secret-Bearing Values
+//!
+//! [`Secret`] wraps key material and key shares. The value is zeroized on
+//! drop, `Debug` and `Display` print `[REDACTED]`, and duplicating it takes
+//! an explicit `clone_secret()` call instead of an implicit `Clone`, so a
+//! share cannot be copied or logged by accident.
+
+use std::fmt;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Placeholder printed instead of a secret value
+pub const REDACTED: &str = "[REDACTED]";
+
+/// A value that is wiped on drop and never printed
+pub struct Secret<T: Zeroize>(T);
+
+impl<T: Zeroize> Secret<T> {
+    pub fn new(value: T) -> Self {
+        Secret(value)
+    }
+
+    /// Borrow the secret value
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    pub fn expose_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Zeroize + Clone> Secret<T> {
+    /// Deliberate copy of the secret; the copy is wiped on its own drop
+    pub fn clone_secret(&self) -> Self {
+        Secret(self.0.clone())
+    }
+}
+
+impl<T: Zeroize> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Secret(value)
+    }
+}
+
+/// Constant-time comparison, so equality checks do not leak the value
+impl<T: Zeroize + ConstantTimeEq> PartialEq for Secret<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.ct_eq(&other.0).into()
+    }
+}
+
+impl<T: Zeroize + ConstantTimeEq> Eq for Secret<T> {}
+
+impl<T: Zeroize> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret({})", REDACTED)
+    }
+}
+
+impl<T: Zeroize> fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T: Zeroize> Zeroize for Secret<T> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<T: Zeroize> ZeroizeOnDrop for Secret<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::Scalar25519;
+
+    #[test]
+    fn test_secret_is_redacted_and_compared() {
+        let secret = Secret::new(Scalar25519::from(42u64));
+        assert_eq!(format!("{:?}", secret), "Secret([REDACTED])");
+        assert_eq!(secret.to_string(), "[REDACTED]");
+        assert!(!format!("{:?}", Some(Secret::new(12345i64))).contains("12345"));
+
+        let copy = secret.clone_secret();
+        assert_eq!(copy, secret);
+        assert_ne!(Secret::new(Scalar25519::from(43u64)), secret);
+    }
+
+    #[test]
+    fn test_zeroize_wipes_value() {
+        let mut secret = Secret::new(987_654_321i64);
+        *secret.expose_mut() += 1;
+        assert_eq!(*secret.expose(), 987_654_322);
+        secret.zeroize();
+        assert_eq!(*secret.expose(), 0);
+    }
+}
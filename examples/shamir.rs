@@ -0,0 +1,313 @@
+//! Shamir Secret Sharing over a Prime Field
+//!
+//! Generic over [`PrimeField`], so the same scheme splits a demo value in
+//! GF(10^9 + 7), a TFHE key coefficient in Goldilocks, a 64-bit aggregate in
+//! GF(2^127 − 1) or a Ristretto255 scalar.
+//!
+//! A k-of-n dealing samples the k − 1 non-constant coefficients from a
+//! CSPRNG; the polynomial is zeroized as soon as the shares are evaluated.
+
+use crate::field::{evaluate_polynomial, interpolate_at, FieldElement, PrimeField};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Dealer polynomial P(x) = secret + a1·x + … + a_{k−1}·x^{k−1}
+///
+/// Holds the secret in its constant term, so coefficients are wiped on drop.
+pub struct Polynomial<F: PrimeField> {
+    coefficients: Vec<F>,
+}
+
+impl<F: PrimeField> Polynomial<F> {
+    /// Polynomial with the given constant term and `degree` random coefficients
+    pub fn random<R: RngCore + CryptoRng>(secret: F, degree: usize, rng: &mut R) -> Self {
+        let mut coefficients = Vec::with_capacity(degree + 1);
+        coefficients.push(secret);
+        coefficients.extend((0..degree).map(|_| F::random(rng)));
+        Polynomial { coefficients }
+    }
+
+    pub fn from_coefficients(coefficients: Vec<F>) -> Self {
+        Polynomial { coefficients }
+    }
+
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    pub fn coefficients(&self) -> &[F] {
+        &self.coefficients
+    }
+
+    pub fn evaluate(&self, x: F) -> F {
+        evaluate_polynomial(&self.coefficients, x)
+    }
+
+    /// Shares P(1), …, P(total_shares) keyed by share id
+    pub fn shares(&self, total_shares: u32) -> HashMap<u32, F> {
+        (1..=total_shares)
+            .map(|x| (x, self.evaluate(F::from_u64(x as u64))))
+            .collect()
+    }
+}
+
+impl<F: PrimeField> Zeroize for Polynomial<F> {
+    /// Overwrites every coefficient in place, keeping the degree
+    fn zeroize(&mut self) {
+        self.coefficients.iter_mut().for_each(Zeroize::zeroize);
+    }
+}
+
+impl<F: PrimeField> Drop for Polynomial<F> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<F: PrimeField> ZeroizeOnDrop for Polynomial<F> {}
+
+/// Reconstruct secret using Lagrange interpolation over the prime field
+/// S = ∑ y_i * L_i(0) mod p
+pub fn threshold_decrypt_modular<F: PrimeField>(
+    shares: &HashMap<u32, F>,
+    threshold: usize,
+) -> Option<F> {
+    if shares.len() < threshold {
+        return None; // Need at least threshold shares
+    }
+
+    let points: Vec<(F, F)> = shares
+        .iter()
+        .take(threshold)
+        .map(|(&id, &share)| (F::from_u64(id as u64), share))
+        .collect();
+
+    interpolate_at(&points, F::ZERO).ok()
+}
+
+/// Shamir's Secret Sharing with Modular Arithmetic
+pub struct ShamirSchemeModular<F: PrimeField = FieldElement> {
+    pub threshold: u32,
+    pub total_shares: u32,
+    _field: PhantomData<F>,
+}
+
+impl<F: PrimeField> ShamirSchemeModular<F> {
+    /// k-of-n scheme; needs 1 ≤ k ≤ n
+    pub fn new(threshold: u32, total_shares: u32) -> Result<Self, String> {
+        if threshold == 0 {
+            return Err("Threshold must be at least 1".to_string());
+        }
+        if threshold > total_shares {
+            return Err(format!(
+                "Threshold ({}) cannot exceed total shares ({})",
+                threshold, total_shares
+            ));
+        }
+        Ok(ShamirSchemeModular {
+            threshold,
+            total_shares,
+            _field: PhantomData,
+        })
+    }
+
+    /// Split `secret` with coefficients drawn from `rng`
+    ///
+    /// Tests pass a seeded ChaCha20 RNG for reproducible shares.
+    pub fn deal<R: RngCore + CryptoRng>(&self, secret: F, rng: &mut R) -> HashMap<u32, F> {
+        let polynomial = Polynomial::random(secret, self.threshold as usize - 1, rng);
+        polynomial.shares(self.total_shares)
+    }
+
+    /// Split `secret` with coefficients drawn from OS entropy
+    pub fn generate_shares(&self, secret: F) -> HashMap<u32, F> {
+        self.deal(secret, &mut OsRng)
+    }
+
+    pub fn recover_secret(&self, shares: &HashMap<u32, F>) -> Option<F> {
+        threshold_decrypt_modular(shares, self.threshold as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::{Goldilocks, Mersenne127, Scalar25519};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    fn deal<F: PrimeField>(secret: i64, threshold: u32) -> (ShamirSchemeModular<F>, HashMap<u32, F>) {
+        let scheme = ShamirSchemeModular::new(threshold, 5).unwrap();
+        let shares = scheme.deal(F::from_i64(secret), &mut ChaCha20Rng::seed_from_u64(7));
+        (scheme, shares)
+    }
+
+    fn take<F: PrimeField>(shares: &HashMap<u32, F>, ids: &[u32]) -> HashMap<u32, F> {
+        ids.iter().map(|id| (*id, shares[id])).collect()
+    }
+
+    /// Runs a generic test body once per supported field
+    macro_rules! for_each_field {
+        ($body:ident) => {
+            $body::<FieldElement>();
+            $body::<Goldilocks>();
+            $body::<Mersenne127>();
+            $body::<Scalar25519>();
+        };
+    }
+
+    fn shamir_modular_basic<F: PrimeField>() {
+        let (_, shares) = deal::<F>(42, 3);
+        assert_eq!(shares.len(), 5);
+
+        // Shares lie on one degree-2 polynomial with P(0) = 42
+        let points: Vec<(F, F)> = [1u32, 2, 3]
+            .iter()
+            .map(|&id| (F::from_u64(id as u64), shares[&id]))
+            .collect();
+        for (&id, &share) in &shares {
+            assert_eq!(
+                interpolate_at(&points, F::from_u64(id as u64)).unwrap(),
+                share,
+                "Share {} mismatch",
+                id
+            );
+        }
+        assert_eq!(interpolate_at(&points, F::ZERO).unwrap(), F::from_u64(42));
+    }
+
+    #[test]
+    fn test_shamir_modular_basic() {
+        for_each_field!(shamir_modular_basic);
+    }
+
+    fn shamir_modular_recovery<F: PrimeField>() {
+        let (scheme, shares) = deal::<F>(42, 3);
+
+        // Use any 3 shares to recover secret
+        let recovered = scheme.recover_secret(&take(&shares, &[1, 2, 3]));
+        assert_eq!(recovered, Some(F::from_u64(42)));
+    }
+
+    #[test]
+    fn test_shamir_modular_recovery() {
+        for_each_field!(shamir_modular_recovery);
+    }
+
+    fn shamir_modular_recovery_different_combinations<F: PrimeField>() {
+        let (scheme, shares) = deal::<F>(100, 3);
+
+        for ids in [[1, 2, 3], [1, 2, 4], [2, 3, 4], [3, 4, 5]] {
+            let recovered = scheme.recover_secret(&take(&shares, &ids));
+            assert_eq!(recovered, Some(F::from_u64(100)), "Failed for combination {:?}", ids);
+        }
+    }
+
+    #[test]
+    fn test_shamir_modular_recovery_different_combinations() {
+        for_each_field!(shamir_modular_recovery_different_combinations);
+    }
+
+    fn shamir_modular_insufficient_shares<F: PrimeField>() {
+        let (scheme, shares) = deal::<F>(42, 3);
+        assert_eq!(scheme.recover_secret(&take(&shares, &[1, 2])), None);
+
+        // Interpolating k − 1 shares yields an unrelated value, not the secret
+        let points: Vec<(F, F)> = [1u32, 2]
+            .iter()
+            .map(|&id| (F::from_u64(id as u64), shares[&id]))
+            .collect();
+        assert_ne!(interpolate_at(&points, F::ZERO).unwrap(), F::from_u64(42));
+    }
+
+    #[test]
+    fn test_shamir_modular_insufficient_shares() {
+        for_each_field!(shamir_modular_insufficient_shares);
+    }
+
+    fn shamir_modular_large_numbers<F: PrimeField>() {
+        let (scheme, shares) = deal::<F>(-999_999_999, 3);
+
+        let recovered = scheme.recover_secret(&take(&shares, &[2, 4, 5]));
+        assert_eq!(recovered, Some(F::from_i64(-999_999_999)));
+    }
+
+    #[test]
+    fn test_shamir_modular_large_numbers() {
+        for_each_field!(shamir_modular_large_numbers);
+    }
+
+    fn shamir_modular_with_different_thresholds<F: PrimeField>() {
+        for threshold in 1..=5u32 {
+            let (scheme, shares) = deal::<F>(200, threshold);
+            let ids: Vec<u32> = (1..=5).rev().take(threshold as usize).collect();
+            assert_eq!(scheme.recover_secret(&take(&shares, &ids)), Some(F::from_u64(200)));
+        }
+    }
+
+    #[test]
+    fn test_shamir_modular_with_different_thresholds() {
+        for_each_field!(shamir_modular_with_different_thresholds);
+    }
+
+    #[test]
+    fn test_scheme_rejects_invalid_thresholds() {
+        assert!(ShamirSchemeModular::<FieldElement>::new(0, 5).is_err());
+        assert!(ShamirSchemeModular::<FieldElement>::new(6, 5).is_err());
+        assert!(ShamirSchemeModular::<FieldElement>::new(5, 5).is_ok());
+    }
+
+    #[test]
+    fn test_seeded_dealing_is_reproducible_and_entropy_is_not() {
+        let scheme = ShamirSchemeModular::<Mersenne127>::new(3, 5).unwrap();
+        let secret = Mersenne127::from(42u64);
+
+        let a = scheme.deal(secret, &mut ChaCha20Rng::seed_from_u64(1));
+        let b = scheme.deal(secret, &mut ChaCha20Rng::seed_from_u64(1));
+        let c = scheme.deal(secret, &mut ChaCha20Rng::seed_from_u64(2));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+
+        let d = scheme.generate_shares(secret);
+        assert_ne!(a, d);
+        assert_eq!(scheme.recover_secret(&d), Some(secret));
+    }
+
+    #[test]
+    fn test_polynomial_degree_and_zeroize() {
+        let mut rng = ChaCha20Rng::seed_from_u64(3);
+        let mut polynomial = Polynomial::random(FieldElement::new(42), 4, &mut rng);
+        assert_eq!(polynomial.degree(), 4);
+        assert_eq!(polynomial.coefficients()[0], FieldElement::new(42));
+        assert!(polynomial.coefficients()[1..].iter().all(|c| !c.is_zero()));
+
+        polynomial.zeroize();
+        assert_eq!(polynomial.coefficients().len(), 5);
+        assert!(polynomial.coefficients().iter().all(|c| c.is_zero()));
+    }
+
+    #[test]
+    fn test_shamir_secrets_beyond_small_field() {
+        let mut rng = ChaCha20Rng::seed_from_u64(5);
+
+        // A 64-bit aggregate does not fit GF(10^9 + 7) but does fit GF(2^127 − 1)
+        let scheme = ShamirSchemeModular::<Mersenne127>::new(2, 3).unwrap();
+        let secret = Mersenne127::from(u64::MAX);
+        let shares = scheme.deal(secret, &mut rng);
+        assert_eq!(scheme.recover_secret(&take(&shares, &[2, 3])), Some(secret));
+
+        // 126-bit key material
+        let key = Mersenne127::new(0x3ab1_c0de_d00d_f00d_1234_5678_9abc_def0);
+        let shares = scheme.deal(key, &mut rng);
+        assert_eq!(scheme.recover_secret(&take(&shares, &[1, 3])), Some(key));
+
+        // Full-size Ristretto255 scalar
+        let scheme = ShamirSchemeModular::<Scalar25519>::new(2, 3).unwrap();
+        let scalar = Scalar25519::from_bytes_mod_order([0xa5; 32]);
+        let shares = scheme.deal(scalar, &mut rng);
+        assert_eq!(scheme.recover_secret(&take(&shares, &[1, 2])), Some(scalar));
+    }
+}
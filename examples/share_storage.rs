@@ -0,0 +1,968 @@
+//! Share Encoding and Encrypted-at-Rest Storage
+//!
+//! Versioned binary and JSON encodings for a decryptor's VSS share, the
+//! dealer's public commitment, the group public key, the public result of
+//! a DKG run, identity signing keys and LWE key shares. Binary records start with `BSFO`, a format version and a kind
+//! byte; JSON records carry the same version and kind as fields, with group
+//! elements and scalars in hex.
+//!
+//! A decryptor keeps its shares, and its identity signing key, in
+//! passphrase-encrypted files: Argon2id derives a key from the passphrase
+//! and ChaCha20-Poly1305 seals the binary record, with the file header as
+//! associated data. The header is read before
+//! it can be authenticated, so its Argon2 costs are bounded before use.
+//! Loading checks the share against the published commitment and rejects it
+//! if it does not verify.
+
+use crate::dkg::DkgOutput;
+use crate::field::Scalar25519;
+use crate::secret::Secret;
+use crate::threshold_lwe::{field_from_bytes, field_to_bytes, LweKeyShare};
+use crate::threshold_signature::SigningKey;
+use crate::vss::{FeldmanCommitment, PedersenCommitment, VssCommitment, VssMode, VssShare};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use zeroize::{Zeroize, Zeroizing};
+
+/// Current encoding version for records and keystore files
+pub const ENCODING_VERSION: u8 = 1;
+
+const RECORD_MAGIC: &[u8; 4] = b"BSFO";
+const KEYSTORE_MAGIC: &[u8; 4] = b"BSKS";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+/// magic ‖ version ‖ m_cost ‖ t_cost ‖ p_cost ‖ salt ‖ nonce
+const KEYSTORE_HEADER_LEN: usize = 4 + 1 + 12 + SALT_LEN + NONCE_LEN;
+
+const KIND_SHARE: u8 = 1;
+const KIND_COMMITMENT: u8 = 2;
+const KIND_GROUP_PUBLIC_KEY: u8 = 3;
+const KIND_DKG_COMMITMENT: u8 = 4;
+const KIND_SIGNING_KEY: u8 = 5;
+const KIND_LWE_KEY_SHARE: u8 = 6;
+
+/// Versioned binary and JSON encoding
+pub trait ShareEncoding: Sized {
+    fn encode(&self) -> Vec<u8>;
+    fn decode(bytes: &[u8]) -> Result<Self, String>;
+    fn to_json(&self) -> String;
+    fn from_json(json: &str) -> Result<Self, String>;
+}
+
+/// A decryptor's share together with its id; the share wipes itself on drop
+#[derive(Debug, PartialEq, Eq)]
+pub struct StoredShare {
+    pub id: u32,
+    pub share: VssShare,
+}
+
+impl Zeroize for StoredShare {
+    fn zeroize(&mut self) {
+        self.share.zeroize();
+    }
+}
+
+impl StoredShare {
+    /// A DKG key share as a Feldman share of the joint polynomial; a
+    /// deliberate copy, meant to be sealed right away
+    pub fn from_dkg_output(output: &DkgOutput) -> Self {
+        StoredShare {
+            id: output.id,
+            share: VssShare::feldman(*output.key_share.expose()),
+        }
+    }
+}
+
+/// Group public key Y = x·G from the DKG
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GroupPublicKey(pub RistrettoPoint);
+
+/// Public result of a DKG run: the joint commitment ∑_{i∈QUAL} a_ik·G and
+/// QUAL. Key shares from the run verify against it like Feldman shares.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DkgCommitment {
+    pub commitment: FeldmanCommitment,
+    pub qualified: Vec<u32>,
+}
+
+impl DkgCommitment {
+    pub fn from_output(output: &DkgOutput) -> Self {
+        DkgCommitment {
+            commitment: output.commitment.clone(),
+            qualified: output.qualified.clone(),
+        }
+    }
+
+    pub fn public_key(&self) -> GroupPublicKey {
+        GroupPublicKey(self.commitment.public_secret())
+    }
+
+    /// Feldman form, for checking a stored key share
+    pub fn vss_commitment(&self) -> VssCommitment {
+        VssCommitment::Feldman(self.commitment.clone())
+    }
+
+    /// A decryptor's signing key from its stored share, which must verify
+    /// against this commitment
+    pub fn key_share(&self, stored: &StoredShare) -> Result<DkgOutput, String> {
+        if stored.share.blinding.is_some() || !self.commitment.verify_share(stored.id, stored.share.value) {
+            return Err(format!("Share {} is not a key share of this DKG", stored.id));
+        }
+        Ok(DkgOutput {
+            id: stored.id,
+            key_share: Secret::new(stored.share.value),
+            public_key: self.public_key().0,
+            commitment: self.commitment.clone(),
+            qualified: self.qualified.clone(),
+        })
+    }
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub(crate) fn from_hex(hex: &str) -> Result<Vec<u8>, String> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return Err(format!("Invalid hex {:?}", hex));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| format!("Invalid hex {:?}", hex)))
+        .collect()
+}
+
+pub(crate) fn from_hex32(hex: &str) -> Result<[u8; 32], String> {
+    if hex.len() != 64 {
+        return Err(format!("Expected 64 hex digits, got {:?}", hex));
+    }
+    Ok(from_hex(hex)?.try_into().unwrap())
+}
+
+pub(crate) fn decode_scalar(bytes: [u8; 32]) -> Result<Scalar25519, String> {
+    Option::<Scalar>::from(Scalar::from_canonical_bytes(bytes))
+        .map(Scalar25519::new)
+        .ok_or_else(|| "Scalar is not canonical".to_string())
+}
+
+pub(crate) fn decode_point(bytes: [u8; 32]) -> Result<RistrettoPoint, String> {
+    CompressedRistretto(bytes)
+        .decompress()
+        .ok_or_else(|| "Bytes are not a valid Ristretto point".to_string())
+}
+
+fn record_header(kind: u8) -> Vec<u8> {
+    let mut bytes = RECORD_MAGIC.to_vec();
+    bytes.push(ENCODING_VERSION);
+    bytes.push(kind);
+    bytes
+}
+
+/// Strip and check the record header, returning the payload
+fn record_payload(bytes: &[u8], kind: u8) -> Result<&[u8], String> {
+    if bytes.len() < 6 || &bytes[..4] != RECORD_MAGIC {
+        return Err("Not an encoded record".to_string());
+    }
+    if bytes[4] != ENCODING_VERSION {
+        return Err(format!("Unsupported encoding version {}", bytes[4]));
+    }
+    if bytes[5] != kind {
+        return Err(format!("Expected record kind {}, found {}", kind, bytes[5]));
+    }
+    Ok(&bytes[6..])
+}
+
+/// Fixed-size reads from a payload
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        if self.0.len() < n {
+            return Err("Record is truncated".to_string());
+        }
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn array32(&mut self) -> Result<[u8; 32], String> {
+        Ok(self.take(32)?.try_into().unwrap())
+    }
+
+    fn finish(self) -> Result<(), String> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("{} trailing bytes after record", self.0.len()))
+        }
+    }
+}
+
+/// JSON form of every record kind
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum JsonRecord {
+    Share {
+        id: u32,
+        value: String,
+        blinding: Option<String>,
+    },
+    Commitment {
+        mode: String,
+        points: Vec<String>,
+    },
+    GroupPublicKey {
+        point: String,
+    },
+    DkgCommitment {
+        points: Vec<String>,
+        qualified: Vec<u32>,
+    },
+    SigningKey {
+        secret: String,
+    },
+    LweKeyShare {
+        id: u32,
+        coefficients: Vec<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonEnvelope {
+    version: u8,
+    #[serde(flatten)]
+    record: JsonRecord,
+}
+
+fn write_json(record: JsonRecord) -> String {
+    serde_json::to_string(&JsonEnvelope {
+        version: ENCODING_VERSION,
+        record,
+    })
+    .expect("records always serialize")
+}
+
+fn read_json(json: &str) -> Result<JsonRecord, String> {
+    let envelope: JsonEnvelope = serde_json::from_str(json).map_err(|e| format!("Invalid JSON record: {}", e))?;
+    if envelope.version != ENCODING_VERSION {
+        return Err(format!("Unsupported encoding version {}", envelope.version));
+    }
+    Ok(envelope.record)
+}
+
+impl ShareEncoding for StoredShare {
+    /// id (u32 BE) ‖ value ‖ 0 | 1 ‖ blinding
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = record_header(KIND_SHARE);
+        bytes.extend_from_slice(&self.id.to_be_bytes());
+        bytes.extend_from_slice(&self.share.value.to_bytes());
+        match self.share.blinding {
+            Some(blinding) => {
+                bytes.push(1);
+                bytes.extend_from_slice(&blinding.to_bytes());
+            }
+            None => bytes.push(0),
+        }
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = Reader(record_payload(bytes, KIND_SHARE)?);
+        let id = u32::from_be_bytes(reader.take(4)?.try_into().unwrap());
+        let value = decode_scalar(reader.array32()?)?;
+        let blinding = match reader.take(1)?[0] {
+            0 => None,
+            1 => Some(decode_scalar(reader.array32()?)?),
+            flag => return Err(format!("Invalid blinding flag {}", flag)),
+        };
+        reader.finish()?;
+        Ok(StoredShare {
+            id,
+            share: VssShare { value, blinding },
+        })
+    }
+
+    fn to_json(&self) -> String {
+        write_json(JsonRecord::Share {
+            id: self.id,
+            value: to_hex(&self.share.value.to_bytes()),
+            blinding: self.share.blinding.map(|b| to_hex(&b.to_bytes())),
+        })
+    }
+
+    fn from_json(json: &str) -> Result<Self, String> {
+        match read_json(json)? {
+            JsonRecord::Share { id, value, blinding } => Ok(StoredShare {
+                id,
+                share: VssShare {
+                    value: decode_scalar(from_hex32(&value)?)?,
+                    blinding: blinding
+                        .map(|b| decode_scalar(from_hex32(&b)?))
+                        .transpose()?,
+                },
+            }),
+            _ => Err("JSON record is not a share".to_string()),
+        }
+    }
+}
+
+fn mode_byte(mode: VssMode) -> u8 {
+    match mode {
+        VssMode::Feldman => 0,
+        VssMode::Pedersen => 1,
+    }
+}
+
+fn commitment_from_points(mode: VssMode, points: Vec<RistrettoPoint>) -> Result<VssCommitment, String> {
+    if points.is_empty() {
+        return Err("Commitment has no points".to_string());
+    }
+    Ok(match mode {
+        VssMode::Feldman => VssCommitment::Feldman(FeldmanCommitment { points }),
+        VssMode::Pedersen => VssCommitment::Pedersen(PedersenCommitment { points }),
+    })
+}
+
+impl ShareEncoding for VssCommitment {
+    /// mode (0 Feldman, 1 Pedersen) ‖ count (u16 BE) ‖ compressed points
+    fn encode(&self) -> Vec<u8> {
+        let points = self.to_bytes();
+        let mut bytes = record_header(KIND_COMMITMENT);
+        bytes.push(mode_byte(self.mode()));
+        bytes.extend_from_slice(&(points.len() as u16).to_be_bytes());
+        for point in points {
+            bytes.extend_from_slice(&point);
+        }
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = Reader(record_payload(bytes, KIND_COMMITMENT)?);
+        let mode = match reader.take(1)?[0] {
+            0 => VssMode::Feldman,
+            1 => VssMode::Pedersen,
+            other => return Err(format!("Unknown commitment mode {}", other)),
+        };
+        let count = u16::from_be_bytes(reader.take(2)?.try_into().unwrap());
+        let points = (0..count)
+            .map(|_| decode_point(reader.array32()?))
+            .collect::<Result<Vec<_>, String>>()?;
+        reader.finish()?;
+        commitment_from_points(mode, points)
+    }
+
+    fn to_json(&self) -> String {
+        let mode = match self.mode() {
+            VssMode::Feldman => "feldman",
+            VssMode::Pedersen => "pedersen",
+        };
+        write_json(JsonRecord::Commitment {
+            mode: mode.to_string(),
+            points: self.to_bytes().iter().map(|p| to_hex(p)).collect(),
+        })
+    }
+
+    fn from_json(json: &str) -> Result<Self, String> {
+        match read_json(json)? {
+            JsonRecord::Commitment { mode, points } => {
+                let mode = match mode.as_str() {
+                    "feldman" => VssMode::Feldman,
+                    "pedersen" => VssMode::Pedersen,
+                    other => return Err(format!("Unknown commitment mode {:?}", other)),
+                };
+                let points = points
+                    .iter()
+                    .map(|p| decode_point(from_hex32(p)?))
+                    .collect::<Result<Vec<_>, String>>()?;
+                commitment_from_points(mode, points)
+            }
+            _ => Err("JSON record is not a commitment".to_string()),
+        }
+    }
+}
+
+impl ShareEncoding for GroupPublicKey {
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = record_header(KIND_GROUP_PUBLIC_KEY);
+        bytes.extend_from_slice(self.0.compress().as_bytes());
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = Reader(record_payload(bytes, KIND_GROUP_PUBLIC_KEY)?);
+        let point = decode_point(reader.array32()?)?;
+        reader.finish()?;
+        Ok(GroupPublicKey(point))
+    }
+
+    fn to_json(&self) -> String {
+        write_json(JsonRecord::GroupPublicKey {
+            point: to_hex(self.0.compress().as_bytes()),
+        })
+    }
+
+    fn from_json(json: &str) -> Result<Self, String> {
+        match read_json(json)? {
+            JsonRecord::GroupPublicKey { point } => Ok(GroupPublicKey(decode_point(from_hex32(&point)?)?)),
+            _ => Err("JSON record is not a group public key".to_string()),
+        }
+    }
+}
+
+impl ShareEncoding for DkgCommitment {
+    /// count (u16 BE) ‖ compressed points ‖ count (u16 BE) ‖ QUAL ids (u32 BE)
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = record_header(KIND_DKG_COMMITMENT);
+        bytes.extend_from_slice(&(self.commitment.points.len() as u16).to_be_bytes());
+        for point in self.commitment.to_bytes() {
+            bytes.extend_from_slice(&point);
+        }
+        bytes.extend_from_slice(&(self.qualified.len() as u16).to_be_bytes());
+        for id in &self.qualified {
+            bytes.extend_from_slice(&id.to_be_bytes());
+        }
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = Reader(record_payload(bytes, KIND_DKG_COMMITMENT)?);
+        let count = u16::from_be_bytes(reader.take(2)?.try_into().unwrap());
+        let points = (0..count)
+            .map(|_| decode_point(reader.array32()?))
+            .collect::<Result<Vec<_>, String>>()?;
+        let count = u16::from_be_bytes(reader.take(2)?.try_into().unwrap());
+        let qualified = (0..count)
+            .map(|_| Ok(u32::from_be_bytes(reader.take(4)?.try_into().unwrap())))
+            .collect::<Result<Vec<_>, String>>()?;
+        reader.finish()?;
+        dkg_commitment(points, qualified)
+    }
+
+    fn to_json(&self) -> String {
+        write_json(JsonRecord::DkgCommitment {
+            points: self.commitment.to_bytes().iter().map(|p| to_hex(p)).collect(),
+            qualified: self.qualified.clone(),
+        })
+    }
+
+    fn from_json(json: &str) -> Result<Self, String> {
+        match read_json(json)? {
+            JsonRecord::DkgCommitment { points, qualified } => {
+                let points = points
+                    .iter()
+                    .map(|p| decode_point(from_hex32(p)?))
+                    .collect::<Result<Vec<_>, String>>()?;
+                dkg_commitment(points, qualified)
+            }
+            _ => Err("JSON record is not a DKG commitment".to_string()),
+        }
+    }
+}
+
+/// A node's identity key, e.g. for signing its decryption responses
+impl ShareEncoding for SigningKey {
+    /// secret scalar
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = record_header(KIND_SIGNING_KEY);
+        bytes.extend_from_slice(&self.secret().expose().to_bytes());
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = Reader(record_payload(bytes, KIND_SIGNING_KEY)?);
+        let secret = decode_scalar(reader.array32()?)?;
+        reader.finish()?;
+        Ok(SigningKey::from_secret(Secret::new(secret)))
+    }
+
+    fn to_json(&self) -> String {
+        write_json(JsonRecord::SigningKey {
+            secret: to_hex(&self.secret().expose().to_bytes()),
+        })
+    }
+
+    fn from_json(json: &str) -> Result<Self, String> {
+        match read_json(json)? {
+            JsonRecord::SigningKey { secret } => Ok(SigningKey::from_secret(Secret::new(decode_scalar(
+                from_hex32(&secret)?,
+            )?))),
+            _ => Err("JSON record is not a signing key".to_string()),
+        }
+    }
+}
+
+impl ShareEncoding for LweKeyShare {
+    /// id ‖ u32 count ‖ 16-byte little-endian coordinates
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = record_header(KIND_LWE_KEY_SHARE);
+        bytes.extend_from_slice(&self.id.to_be_bytes());
+        bytes.extend_from_slice(&(self.coefficients().len() as u32).to_be_bytes());
+        for &coefficient in self.coefficients() {
+            bytes.extend_from_slice(&field_to_bytes(coefficient));
+        }
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = Reader(record_payload(bytes, KIND_LWE_KEY_SHARE)?);
+        let id = u32::from_be_bytes(reader.take(4)?.try_into().unwrap());
+        let count = u32::from_be_bytes(reader.take(4)?.try_into().unwrap());
+        let coefficients = (0..count)
+            .map(|_| field_from_bytes(reader.take(16)?.try_into().unwrap()))
+            .collect::<Result<Vec<_>, String>>()?;
+        reader.finish()?;
+        Ok(LweKeyShare::from_coefficients(id, coefficients))
+    }
+
+    fn to_json(&self) -> String {
+        write_json(JsonRecord::LweKeyShare {
+            id: self.id,
+            coefficients: self.coefficients().iter().map(|&c| to_hex(&field_to_bytes(c))).collect(),
+        })
+    }
+
+    fn from_json(json: &str) -> Result<Self, String> {
+        match read_json(json)? {
+            JsonRecord::LweKeyShare { id, coefficients } => {
+                let coefficients = coefficients
+                    .iter()
+                    .map(|hex| {
+                        let bytes: [u8; 16] = from_hex(hex)?
+                            .try_into()
+                            .map_err(|_| format!("Expected 32 hex digits, got {:?}", hex))?;
+                        field_from_bytes(bytes)
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+                Ok(LweKeyShare::from_coefficients(id, coefficients))
+            }
+            _ => Err("JSON record is not an LWE key share".to_string()),
+        }
+    }
+}
+
+fn dkg_commitment(points: Vec<RistrettoPoint>, qualified: Vec<u32>) -> Result<DkgCommitment, String> {
+    if points.is_empty() {
+        return Err("Commitment has no points".to_string());
+    }
+    if qualified.len() < points.len() {
+        return Err(format!(
+            "{} qualified dealers cannot make up a threshold of {}",
+            qualified.len(),
+            points.len()
+        ));
+    }
+    Ok(DkgCommitment {
+        commitment: FeldmanCommitment { points },
+        qualified,
+    })
+}
+
+/// Argon2id cost parameters, stored in the keystore header
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KdfParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for KdfParams {
+    /// OWASP minimum for Argon2id: 19 MiB, 2 passes, 1 lane
+    fn default() -> Self {
+        KdfParams {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+impl KdfParams {
+    /// Largest costs a keystore may ask for: 1 GiB, 16 passes, 8 lanes
+    pub const MAX: KdfParams = KdfParams {
+        memory_kib: 1024 * 1024,
+        iterations: 16,
+        parallelism: 8,
+    };
+
+    /// Reject costs above [`KdfParams::MAX`], so a tampered header cannot
+    /// make loading exhaust memory or CPU before authentication fails
+    pub fn check_bounds(&self) -> Result<(), String> {
+        let max = KdfParams::MAX;
+        if self.memory_kib > max.memory_kib || self.iterations > max.iterations || self.parallelism > max.parallelism {
+            return Err(format!(
+                "KDF parameters {:?} exceed the maximum {:?}",
+                self, max
+            ));
+        }
+        Ok(())
+    }
+
+    fn derive_key(&self, passphrase: &[u8], salt: &[u8]) -> Result<Zeroizing<[u8; 32]>, String> {
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, Some(32))
+            .map_err(|e| format!("Invalid KDF parameters: {}", e))?;
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase, salt, key.as_mut())
+            .map_err(|e| format!("Key derivation failed: {}", e))?;
+        Ok(key)
+    }
+}
+
+/// Encrypt a record under a passphrase
+///
+/// Layout: `BSKS` ‖ version ‖ m_cost ‖ t_cost ‖ p_cost (u32 BE) ‖ salt ‖
+/// nonce ‖ AEAD ciphertext of the binary record, authenticated with the header.
+pub fn seal_record<T: ShareEncoding, R: RngCore + CryptoRng>(
+    record: &T,
+    passphrase: &[u8],
+    params: &KdfParams,
+    rng: &mut R,
+) -> Result<Vec<u8>, String> {
+    params.check_bounds()?;
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut salt);
+    rng.fill_bytes(&mut nonce);
+
+    let mut header = KEYSTORE_MAGIC.to_vec();
+    header.push(ENCODING_VERSION);
+    for value in [params.memory_kib, params.iterations, params.parallelism] {
+        header.extend_from_slice(&value.to_be_bytes());
+    }
+    header.extend_from_slice(&salt);
+    header.extend_from_slice(&nonce);
+
+    let key = params.derive_key(passphrase, &salt)?;
+    let plaintext = Zeroizing::new(record.encode());
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(key.as_ref()))
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &plaintext,
+                aad: &header,
+            },
+        )
+        .map_err(|_| "Encryption failed".to_string())?;
+
+    header.extend_from_slice(&ciphertext);
+    Ok(header)
+}
+
+/// Decrypt a sealed record
+pub fn open_record<T: ShareEncoding>(sealed: &[u8], passphrase: &[u8]) -> Result<T, String> {
+    if sealed.len() < KEYSTORE_HEADER_LEN || &sealed[..4] != KEYSTORE_MAGIC {
+        return Err("Not a share keystore".to_string());
+    }
+    if sealed[4] != ENCODING_VERSION {
+        return Err(format!("Unsupported keystore version {}", sealed[4]));
+    }
+    let (header, ciphertext) = sealed.split_at(KEYSTORE_HEADER_LEN);
+    let word = |i: usize| u32::from_be_bytes(header[5 + 4 * i..9 + 4 * i].try_into().unwrap());
+    let params = KdfParams {
+        memory_kib: word(0),
+        iterations: word(1),
+        parallelism: word(2),
+    };
+    params.check_bounds()?;
+    let salt = &header[17..17 + SALT_LEN];
+    let nonce = &header[17 + SALT_LEN..];
+
+    let key = params.derive_key(passphrase, salt)?;
+    let plaintext = Zeroizing::new(
+        ChaCha20Poly1305::new(Key::from_slice(key.as_ref()))
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: header,
+                },
+            )
+            .map_err(|_| "Wrong passphrase or corrupted keystore".to_string())?,
+    );
+    T::decode(&plaintext)
+}
+
+/// Encrypt a share under a passphrase, see [`seal_record`]
+pub fn seal_share<R: RngCore + CryptoRng>(
+    share: &StoredShare,
+    passphrase: &[u8],
+    params: &KdfParams,
+    rng: &mut R,
+) -> Result<Vec<u8>, String> {
+    seal_record(share, passphrase, params, rng)
+}
+
+/// Decrypt a sealed share and check it against the published commitment
+pub fn open_share(sealed: &[u8], passphrase: &[u8], commitment: &VssCommitment) -> Result<StoredShare, String> {
+    let share: StoredShare = open_record(sealed, passphrase)?;
+    if !commitment.verify_share(share.id, &share.share) {
+        return Err(format!(
+            "Stored share {} does not verify against the published commitment",
+            share.id
+        ));
+    }
+    Ok(share)
+}
+
+/// Seal a record and write it with owner-only permissions
+pub fn save_record<T: ShareEncoding, R: RngCore + CryptoRng>(
+    path: &Path,
+    record: &T,
+    passphrase: &[u8],
+    params: &KdfParams,
+    rng: &mut R,
+) -> Result<(), String> {
+    let sealed = seal_record(record, passphrase, params, rng)?;
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+    file.write_all(&sealed)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+}
+
+/// Read and decrypt a sealed record
+pub fn load_record<T: ShareEncoding>(path: &Path, passphrase: &[u8]) -> Result<T, String> {
+    let sealed = fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    open_record(&sealed, passphrase)
+}
+
+/// Seal a share and write it with owner-only permissions
+pub fn save_share<R: RngCore + CryptoRng>(
+    path: &Path,
+    share: &StoredShare,
+    passphrase: &[u8],
+    params: &KdfParams,
+    rng: &mut R,
+) -> Result<(), String> {
+    save_record(path, share, passphrase, params, rng)
+}
+
+/// Read, decrypt and verify a stored share
+pub fn load_share(path: &Path, passphrase: &[u8], commitment: &VssCommitment) -> Result<StoredShare, String> {
+    let sealed = fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    open_share(&sealed, passphrase, commitment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dkg::run_dkg;
+    use crate::field::PrimeField;
+    use crate::vss::vss_deal;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use std::collections::HashMap;
+
+    /// Cheap parameters so unoptimized test builds stay fast
+    const TEST_KDF: KdfParams = KdfParams {
+        memory_kib: 64,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    fn dealt(mode: VssMode, seed: u64) -> (VssCommitment, Vec<StoredShare>, ChaCha20Rng) {
+        let mut rng = ChaCha20Rng::seed_from_u64(seed);
+        let (commitment, shares) = vss_deal(mode, Scalar25519::from(42u64), 3, 5, &mut rng).unwrap();
+        let mut shares: Vec<StoredShare> = shares
+            .into_iter()
+            .map(|(id, share)| StoredShare { id, share })
+            .collect();
+        shares.sort_unstable_by_key(|s| s.id);
+        (commitment, shares, rng)
+    }
+
+    #[test]
+    fn test_binary_and_json_round_trips() {
+        for mode in [VssMode::Feldman, VssMode::Pedersen] {
+            let (commitment, shares, _) = dealt(mode, 1);
+            let share = &shares[2];
+
+            assert_eq!(&StoredShare::decode(&share.encode()).unwrap(), share);
+            assert_eq!(&StoredShare::from_json(&share.to_json()).unwrap(), share);
+            assert_eq!(VssCommitment::decode(&commitment.encode()).unwrap(), commitment);
+            assert_eq!(VssCommitment::from_json(&commitment.to_json()).unwrap(), commitment);
+        }
+
+        let outputs = run_dkg(&[1, 2, 3], 2, &HashMap::new(), &mut ChaCha20Rng::seed_from_u64(2)).unwrap();
+        let key = GroupPublicKey(outputs[&1].public_key);
+        assert_eq!(GroupPublicKey::decode(&key.encode()).unwrap(), key);
+        assert_eq!(GroupPublicKey::from_json(&key.to_json()).unwrap(), key);
+    }
+
+    #[test]
+    fn test_encoding_is_versioned_and_typed() {
+        let (commitment, shares, _) = dealt(VssMode::Feldman, 3);
+        let bytes = shares[0].encode();
+        assert_eq!(&bytes[..6], b"BSFO\x01\x01");
+
+        let mut future = bytes.clone();
+        future[4] = 2;
+        assert!(StoredShare::decode(&future).unwrap_err().contains("version"));
+        assert!(VssCommitment::decode(&bytes).is_err());
+        assert!(StoredShare::decode(&bytes[..bytes.len() - 1]).is_err());
+
+        let json = commitment.to_json();
+        assert!(json.contains(r#""version":1"#) && json.contains(r#""kind":"commitment""#));
+        assert!(StoredShare::from_json(&json).is_err());
+        assert!(StoredShare::from_json(&shares[0].to_json().replace(r#""version":1"#, r#""version":9"#)).is_err());
+    }
+
+    #[test]
+    fn test_non_canonical_scalar_is_rejected() {
+        let (_, shares, _) = dealt(VssMode::Feldman, 4);
+        let mut bytes = shares[0].encode();
+        // value occupies bytes 10..42; set it to 2^256 − 1 > ℓ
+        bytes[10..42].fill(0xff);
+        assert!(StoredShare::decode(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_sealed_share_round_trip_and_wrong_passphrase() {
+        let (commitment, shares, mut rng) = dealt(VssMode::Pedersen, 5);
+        let sealed = seal_share(&shares[1], b"correct horse", &TEST_KDF, &mut rng).unwrap();
+
+        assert_eq!(open_share(&sealed, b"correct horse", &commitment).unwrap(), shares[1]);
+        assert!(open_share(&sealed, b"wrong", &commitment).is_err());
+
+        // Header is authenticated: weakening the KDF cost is detected
+        let mut tampered = sealed.clone();
+        tampered[8] ^= 1;
+        assert!(open_share(&tampered, b"correct horse", &commitment).is_err());
+    }
+
+    #[test]
+    fn test_load_rejects_share_failing_the_commitment() {
+        let (commitment, mut shares, mut rng) = dealt(VssMode::Feldman, 6);
+        shares[0].share.value += Scalar25519::ONE;
+        let sealed = seal_share(&shares[0], b"pw", &TEST_KDF, &mut rng).unwrap();
+        assert!(open_share(&sealed, b"pw", &commitment).unwrap_err().contains("does not verify"));
+
+        // A valid share checked against some other dealing is rejected too
+        let (other, _, _) = dealt(VssMode::Feldman, 7);
+        let sealed = seal_share(&shares[1], b"pw", &TEST_KDF, &mut rng).unwrap();
+        assert!(open_share(&sealed, b"pw", &other).is_err());
+    }
+
+    #[test]
+    fn test_oversized_kdf_header_is_rejected_before_derivation() {
+        let (commitment, shares, mut rng) = dealt(VssMode::Feldman, 9);
+        let sealed = seal_share(&shares[0], b"pw", &TEST_KDF, &mut rng).unwrap();
+
+        // m_cost occupies bytes 5..9; 4 TiB would stall any node
+        let mut tampered = sealed.clone();
+        tampered[5..9].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(open_share(&tampered, b"pw", &commitment).unwrap_err().contains("exceed"));
+
+        let greedy = KdfParams {
+            parallelism: 64,
+            ..TEST_KDF
+        };
+        assert!(seal_share(&shares[0], b"pw", &greedy, &mut rng).is_err());
+        assert!(KdfParams::default().check_bounds().is_ok());
+    }
+
+    #[test]
+    fn test_dkg_share_loads_against_joint_commitment() {
+        let mut rng = ChaCha20Rng::seed_from_u64(10);
+        let outputs = run_dkg(&[1, 2, 3, 4, 5], 3, &HashMap::new(), &mut rng).unwrap();
+        let published = DkgCommitment::from_output(&outputs[&2]);
+
+        assert_eq!(DkgCommitment::decode(&published.encode()).unwrap(), published);
+        assert_eq!(DkgCommitment::from_json(&published.to_json()).unwrap(), published);
+        assert_eq!(published.public_key(), GroupPublicKey(outputs[&2].public_key));
+
+        let sealed = seal_share(&StoredShare::from_dkg_output(&outputs[&4]), b"pw", &TEST_KDF, &mut rng).unwrap();
+        let loaded = open_share(&sealed, b"pw", &published.vss_commitment()).unwrap();
+        assert_eq!(loaded.id, 4);
+        assert_eq!(loaded.share.value, *outputs[&4].key_share.expose());
+        let key = published.key_share(&loaded).unwrap();
+        assert_eq!(key.verification_share(), outputs[&4].verification_share());
+        assert_eq!((key.public_key, key.qualified), (outputs[&4].public_key, outputs[&4].qualified.clone()));
+        let mut wrong = StoredShare::from_dkg_output(&outputs[&4]);
+        wrong.id = 3;
+        assert!(published.key_share(&wrong).is_err());
+
+        // A single dealer's commitment does not match a joint key share
+        let (single, _, _) = dealt(VssMode::Feldman, 11);
+        assert!(open_share(&sealed, b"pw", &single).is_err());
+
+        let truncated = DkgCommitment {
+            qualified: vec![1, 2],
+            ..published
+        };
+        assert!(DkgCommitment::decode(&truncated.encode()).is_err());
+    }
+
+    #[test]
+    fn test_save_and_load_file() {
+        let (commitment, shares, mut rng) = dealt(VssMode::Feldman, 8);
+        let path = std::env::temp_dir().join(format!("blocksense-share-{}.bsks", std::process::id()));
+
+        save_share(&path, &shares[4], b"pw", &TEST_KDF, &mut rng).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        let loaded = load_share(&path, b"pw", &commitment);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), shares[4]);
+    }
+
+    #[test]
+    fn test_sealed_identity_key_round_trip() {
+        let mut rng = ChaCha20Rng::seed_from_u64(12);
+        let key = SigningKey::generate(&mut rng);
+        assert_eq!(SigningKey::decode(&key.encode()).unwrap().public_key(), key.public_key());
+        assert_eq!(SigningKey::from_json(&key.to_json()).unwrap().public_key(), key.public_key());
+
+        let sealed = seal_record(&key, b"pw", &TEST_KDF, &mut rng).unwrap();
+        let opened: SigningKey = open_record(&sealed, b"pw").unwrap();
+        assert_eq!(opened.public_key(), key.public_key());
+        assert!(open_record::<StoredShare>(&sealed, b"pw").unwrap_err().contains("kind"));
+        assert!(open_record::<SigningKey>(&sealed, b"wrong").is_err());
+    }
+
+    #[test]
+    fn test_sealed_lwe_key_share_round_trip() {
+        use crate::threshold_lwe::{LweParams, LweSecretKey};
+
+        let mut rng = ChaCha20Rng::seed_from_u64(13);
+        let params = LweParams {
+            dimension: 32,
+            ..LweParams::default()
+        };
+        let key = LweSecretKey::generate(&params, &mut rng);
+        let share = key.deal_shares(2, 3, &mut rng).unwrap().remove(1);
+        let decoded = LweKeyShare::decode(&share.encode()).unwrap();
+        assert_eq!((decoded.id, decoded.coefficients()), (2, share.coefficients()));
+        let from_json = LweKeyShare::from_json(&share.to_json()).unwrap();
+        assert_eq!(from_json.coefficients(), share.coefficients());
+
+        let sealed = seal_record(&share, b"pw", &TEST_KDF, &mut rng).unwrap();
+        let opened: LweKeyShare = open_record(&sealed, b"pw").unwrap();
+        assert_eq!(opened.coefficients(), share.coefficients());
+        assert!(open_record::<SigningKey>(&sealed, b"pw").unwrap_err().contains("kind"));
+
+        let mut corrupt = share.encode();
+        let last = corrupt.len() - 1;
+        corrupt[last] = 0xff;
+        assert!(LweKeyShare::decode(&corrupt).is_err());
+    }
+}
@@ -1,100 +1,130 @@
-/// Share Verification with Commitments
-/// Verifiable Secret Sharing (VSS) to detect corrupted shares
+//! Share Verification with Commitments
+//! Verifiable Secret Sharing (VSS) to detect corrupted shares
+//! Feldman (a_j·G) or Pedersen (a_j·G + b_j·H) commitments over Ristretto255
+use blocksense_examples::field::{interpolate_at, PrimeField, Scalar25519};
+use blocksense_examples::robust::{robust_reconstruct, RobustReconstruction};
+use blocksense_examples::secret::Secret;
+use blocksense_examples::vss::{vss_deal, VssCommitment, VssMode, VssShare};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
 use std::collections::HashMap;
 
-/// Public commitment for share verification
-#[derive(Clone, Debug)]
-pub struct Commitment {
-    pub coefficients: Vec<i64>,
-}
-
-impl Commitment {
-    pub fn new(secret: i64, a1: i64) -> Self {
-        Commitment {
-            coefficients: vec![secret, a1],
-        }
-    }
-
-    /// Verify if a share is valid against this commitment
-    /// V = C0 + C1*x + C2*x^2 + ...
-    pub fn verify_share(&self, x: i64, share_value: i64) -> bool {
-        let mut result = 0i64;
-        let mut x_power = 1i64;
-
-        for coeff in &self.coefficients {
-            result = result.wrapping_add(coeff.wrapping_mul(x_power));
-            x_power = x_power.wrapping_mul(x);
-        }
-
-        result == share_value
-    }
-}
-
 /// Enhanced SecretShare with verification capability
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub struct VerifiableSecretShare {
     pub id: u32,
-    pub share_value: i64,
-    pub commitment: Option<Commitment>,
+    pub share_value: Secret<Scalar25519>,
+    /// Pedersen blinding share r_i; None in Feldman mode
+    pub blinding: Option<Secret<Scalar25519>>,
+    pub commitment: Option<VssCommitment>,
     pub is_verified: bool,
 }
 
 impl VerifiableSecretShare {
-    pub fn new(id: u32, share_value: i64) -> Self {
+    pub fn new(id: u32, share: impl Into<VssShare>) -> Self {
+        let share = share.into();
         VerifiableSecretShare {
             id,
-            share_value,
+            share_value: Secret::new(share.value),
+            blinding: share.blinding.map(Secret::new),
             commitment: None,
             is_verified: false,
         }
     }
 
-    pub fn with_commitment(mut self, commitment: Commitment) -> Self {
-        self.commitment = Some(commitment.clone());
-        self.is_verified = commitment.verify_share(self.id as i64, self.share_value);
+    pub fn vss_share(&self) -> VssShare {
+        VssShare {
+            value: *self.share_value.expose(),
+            blinding: self.blinding.as_ref().map(|b| *b.expose()),
+        }
+    }
+
+    pub fn with_commitment(mut self, commitment: VssCommitment) -> Self {
+        self.is_verified = commitment.verify_share(self.id, &self.vss_share());
+        self.commitment = Some(commitment);
         self
     }
 
-    pub fn verify_against_commitment(&mut self, commitment: &Commitment) -> bool {
-        self.is_verified = commitment.verify_share(self.id as i64, self.share_value);
+    pub fn verify_against_commitment(&mut self, commitment: &VssCommitment) -> bool {
+        self.is_verified = commitment.verify_share(self.id, &self.vss_share());
         self.is_verified
     }
 }
 
 /// Verifiable Secret Sharing Scheme with Byzantine-resistant properties
 pub struct VerifiableSecretSharingScheme {
-    pub secret: i64,
-    pub a1: i64,
     pub shares: HashMap<u32, VerifiableSecretShare>,
-    pub commitment: Commitment,
+    pub commitment: VssCommitment,
     pub threshold: u32,
     pub total_shares: u32,
 }
 
 impl VerifiableSecretSharingScheme {
-    /// Create a new VSS scheme with polynomial P(x) = secret + a1*x
-    pub fn new(secret: i64, a1: i64, threshold: u32, total_shares: u32) -> Self {
-        let commitment = Commitment::new(secret, a1);
-
-        VerifiableSecretSharingScheme {
-            secret,
-            a1,
-            shares: HashMap::new(),
+    /// Deal a k-of-n Feldman VSS of `secret` with coefficients from OS entropy
+    pub fn new(secret: Scalar25519, threshold: u32, total_shares: u32) -> Result<Self, String> {
+        Self::new_with_rng(secret, threshold, total_shares, &mut OsRng)
+    }
+
+    /// Deal Feldman verifiable shares from a random polynomial of degree k-1
+    pub fn new_with_rng<R: RngCore + CryptoRng>(
+        secret: Scalar25519,
+        threshold: u32,
+        total_shares: u32,
+        rng: &mut R,
+    ) -> Result<Self, String> {
+        Self::new_with_mode(VssMode::Feldman, secret, threshold, total_shares, rng)
+    }
+
+    /// Deal verifiable shares in Feldman or Pedersen mode
+    /// The polynomials are zeroized once shares and commitment are derived
+    pub fn new_with_mode<R: RngCore + CryptoRng>(
+        mode: VssMode,
+        secret: Scalar25519,
+        threshold: u32,
+        total_shares: u32,
+        rng: &mut R,
+    ) -> Result<Self, String> {
+        let (commitment, dealt) = vss_deal(mode, secret, threshold, total_shares, rng)?;
+
+        let shares = dealt
+            .into_iter()
+            .map(|(id, value)| {
+                let share = VerifiableSecretShare::new(id, value).with_commitment(commitment.clone());
+                (id, share)
+            })
+            .collect();
+
+        Ok(VerifiableSecretSharingScheme {
+            shares,
             commitment,
             threshold,
             total_shares,
-        }
+        })
     }
 
-    /// Generate verifiable shares
-    pub fn generate_verifiable_shares(&mut self) {
-        for x in 1..=self.total_shares as i64 {
-            let share_value = self.secret.wrapping_add(self.a1.wrapping_mul(x));
-            let mut verifiable_share = VerifiableSecretShare::new(x as u32, share_value);
-            verifiable_share = verifiable_share.with_commitment(self.commitment.clone());
-
-            self.shares.insert(x as u32, verifiable_share);
+    /// Reconstruct the secret from the first `threshold` verified shares
+    pub fn recover_secret(&self) -> Option<Scalar25519> {
+        let points: Vec<(Scalar25519, Scalar25519)> = self
+            .get_verified_shares()
+            .iter()
+            .take(self.threshold as usize)
+            .map(|s| (Scalar25519::from(s.id), *s.share_value.expose()))
+            .collect();
+        if points.len() < self.threshold as usize {
+            return None;
         }
+        interpolate_at(&points, Scalar25519::ZERO).ok()
+    }
+
+    /// Reconstruct from every share without consulting the commitments
+    /// Corrects up to (n - k)/2 corrupted shares and reports their ids
+    pub fn recover_secret_robust(&self) -> Result<RobustReconstruction<Scalar25519>, String> {
+        let shares: Vec<(u32, Scalar25519)> = self
+            .shares
+            .values()
+            .map(|s| (s.id, *s.share_value.expose()))
+            .collect();
+        robust_reconstruct(&shares, self.threshold as usize)
     }
 
     /// Get all verified shares
@@ -121,56 +151,172 @@ impl VerifiableSecretSharingScheme {
     }
 
     /// Verify a single share (for incoming shares)
-    pub fn verify_share(&self, share_id: u32, share_value: i64) -> bool {
-        self.commitment.verify_share(share_id as i64, share_value)
+    /// Feldman shares may be passed as a bare scalar
+    pub fn verify_share(&self, share_id: u32, share: impl Into<VssShare>) -> bool {
+        self.commitment.verify_share(share_id, &share.into())
     }
 
     /// Simulate Byzantine attack - corrupt a share
     pub fn simulate_corruption(&mut self, share_id: u32, corruption: i64) {
         if let Some(share) = self.shares.get_mut(&share_id) {
-            share.share_value = share.share_value.wrapping_add(corruption);
+            *share.share_value.expose_mut() += Scalar25519::from(corruption);
             share.is_verified = false;
         }
     }
 }
 
+fn main() {
+    println!("╔════════════════════════════════════════════╗");
+    println!("║  Share Verification (VSS) Demonstration   ║");
+    println!("╚════════════════════════════════════════════╝\n");
+
+    // Create 3-of-5 VSS scheme: P(x) = 100 + a1*x + a2*x^2, random a1, a2
+    let mut vss = match VerifiableSecretSharingScheme::new(Scalar25519::from(100u64), 3, 5) {
+        Ok(vss) => vss,
+        Err(e) => {
+            println!("✗ Error: {}", e);
+            return;
+        }
+    };
+
+    println!("✓ Generated 5 shares from secret 100 using a random degree-2 polynomial");
+    println!("✓ Published Feldman commitments C_j = a_j·G:");
+    for (j, bytes) in vss.commitment.to_bytes().iter().enumerate() {
+        let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        println!("  C_{}: {}", j, hex);
+    }
+    println!();
+
+    // Display all shares
+    println!("📋 All Shares:");
+    for (id, share) in &vss.shares {
+        println!("  Share {}: verified={}", id, share.is_verified);
+    }
+    println!();
+
+    // Detect Byzantine attack
+    println!("🔴 Simulating Byzantine Attack:");
+    println!("  Attacker corrupts Share 2...\n");
+    vss.simulate_corruption(2, 999);
+
+    let (verified, corrupted) = vss.detect_byzantine_shares();
+    println!("✓ Detection Results:");
+    println!("  Verified shares:  {}", verified);
+    println!("  Corrupted shares: {}\n", corrupted);
+
+    println!("📋 Shares after corruption:");
+    for (id, share) in &vss.shares {
+        let status = if share.is_verified { "✓" } else { "✗" };
+        println!("  {} Share {}: verified={}", status, id, share.is_verified);
+    }
+    println!();
+
+    // Show honest shares can be used for recovery
+    let honest_shares = vss.get_verified_shares();
+    println!("✓ Honest Shares (safe for reconstruction):");
+    for share in &honest_shares {
+        println!("  Share {}", share.id);
+    }
+    println!("\n✓ With {} honest shares (threshold={}), secret is recoverable!",
+             honest_shares.len(),
+             vss.threshold);
+    if let Some(secret) = vss.recover_secret() {
+        println!("  Recovered secret matches C_0: {}", secret == Scalar25519::from(100u64));
+    }
+
+    // Pedersen mode: commitments are perfectly hiding
+    println!("\n🔒 Pedersen VSS (commitments safe to publish on-chain):");
+    let mut pedersen = match VerifiableSecretSharingScheme::new_with_mode(
+        VssMode::Pedersen,
+        Scalar25519::from(100u64),
+        3,
+        5,
+        &mut OsRng,
+    ) {
+        Ok(vss) => vss,
+        Err(e) => {
+            println!("✗ Error: {}", e);
+            return;
+        }
+    };
+    pedersen.simulate_corruption(4, 1);
+    let (verified, corrupted) = pedersen.detect_byzantine_shares();
+    println!("  Verified shares:  {}", verified);
+    println!("  Corrupted shares: {}", corrupted);
+    if let Some(secret) = pedersen.recover_secret() {
+        println!("  Recovered secret matches: {}", secret == Scalar25519::from(100u64));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use blocksense_examples::shamir::Polynomial;
+    use blocksense_examples::vss::FeldmanCommitment;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    fn s(value: i64) -> Scalar25519 {
+        Scalar25519::from(value)
+    }
+
+    /// Feldman commitment to a fixed public polynomial
+    fn commitment(coefficients: &[i64]) -> VssCommitment {
+        VssCommitment::Feldman(FeldmanCommitment::from_polynomial(&Polynomial::from_coefficients(
+            coefficients.iter().map(|&c| s(c)).collect(),
+        )))
+    }
+
+    /// Seeded 3-of-5 Pedersen dealing of `secret`
+    fn pedersen(secret: i64) -> VerifiableSecretSharingScheme {
+        VerifiableSecretSharingScheme::new_with_mode(
+            VssMode::Pedersen,
+            s(secret),
+            3,
+            5,
+            &mut ChaCha20Rng::seed_from_u64(42),
+        )
+        .unwrap()
+    }
+
+    /// Seeded 3-of-5 dealing of `secret`
+    fn vss(secret: i64) -> VerifiableSecretSharingScheme {
+        VerifiableSecretSharingScheme::new_with_rng(s(secret), 3, 5, &mut ChaCha20Rng::seed_from_u64(42))
+            .unwrap()
+    }
 
     #[test]
     fn test_commitment_verification() {
         // P(x) = 42 + 10x
-        let commitment = Commitment::new(42, 10);
+        let commitment = commitment(&[42, 10]);
 
         // Verify shares: P(1) = 52, P(2) = 62, P(3) = 72
-        assert!(commitment.verify_share(1, 52));
-        assert!(commitment.verify_share(2, 62));
-        assert!(commitment.verify_share(3, 72));
+        assert!(commitment.verify_share(1, &s(52).into()));
+        assert!(commitment.verify_share(2, &s(62).into()));
+        assert!(commitment.verify_share(3, &s(72).into()));
 
         // Invalid shares should fail
-        assert!(!commitment.verify_share(1, 50));
-        assert!(!commitment.verify_share(2, 60));
+        assert!(!commitment.verify_share(1, &s(50).into()));
+        assert!(!commitment.verify_share(2, &s(60).into()));
+
+        // Higher degree: P(x) = 1 + 2x + 3x^2, P(2) = 17
+        assert!(self::commitment(&[1, 2, 3]).verify_share(2, &s(17).into()));
     }
 
     #[test]
     fn test_verifiable_secret_share() {
-        let commitment = Commitment::new(42, 10);
-        let share = VerifiableSecretShare::new(1, 52).with_commitment(commitment);
+        let share = VerifiableSecretShare::new(1, s(52)).with_commitment(commitment(&[42, 10]));
 
         assert!(share.is_verified);
-        assert_eq!(share.share_value, 52);
+        assert_eq!(*share.share_value.expose(), s(52));
 
-        let invalid_share = VerifiableSecretShare::new(1, 50).with_commitment(
-            Commitment::new(42, 10)
-        );
+        let invalid_share = VerifiableSecretShare::new(1, s(50)).with_commitment(commitment(&[42, 10]));
         assert!(!invalid_share.is_verified);
     }
 
     #[test]
     fn test_vss_scheme_generation() {
-        let mut vss = VerifiableSecretSharingScheme::new(42, 10, 3, 5);
-        vss.generate_verifiable_shares();
+        let vss = vss(42);
 
         // All shares should be verified
         let verified = vss.get_verified_shares();
@@ -179,18 +325,30 @@ mod tests {
         let corrupted = vss.get_corrupted_shares();
         assert_eq!(corrupted.len(), 0);
 
-        // Verify specific shares
-        assert!(vss.verify_share(1, 52));  // 42 + 10*1 = 52
-        assert!(vss.verify_share(2, 62));  // 42 + 10*2 = 62
-        assert!(vss.verify_share(3, 72));  // 42 + 10*3 = 72
-        assert!(vss.verify_share(4, 82));  // 42 + 10*4 = 82
-        assert!(vss.verify_share(5, 92));  // 42 + 10*5 = 92
+        // Degree k-1 = 2 polynomial committed as group elements only
+        assert_eq!(vss.commitment.threshold(), 3);
+        match &vss.commitment {
+            VssCommitment::Feldman(c) => {
+                let VssCommitment::Feldman(expected) = commitment(&[42]) else { unreachable!() };
+                assert_eq!(c.public_secret(), expected.public_secret());
+            }
+            VssCommitment::Pedersen(_) => panic!("expected Feldman commitment"),
+        }
+        assert_eq!(vss.commitment.mode(), VssMode::Feldman);
+        for (id, share) in &vss.shares {
+            assert!(vss.verify_share(*id, *share.share_value.expose()));
+        }
+    }
+
+    #[test]
+    fn test_vss_rejects_invalid_threshold() {
+        assert!(VerifiableSecretSharingScheme::new(s(42), 0, 5).is_err());
+        assert!(VerifiableSecretSharingScheme::new(s(42), 6, 5).is_err());
     }
 
     #[test]
     fn test_byzantine_detection() {
-        let mut vss = VerifiableSecretSharingScheme::new(42, 10, 3, 5);
-        vss.generate_verifiable_shares();
+        let mut vss = vss(42);
 
         // Initially no corruption
         let (verified, corrupted) = vss.detect_byzantine_shares();
@@ -206,6 +364,7 @@ mod tests {
 
         // Corrupted share should not pass verification
         assert!(!vss.shares.get(&2).unwrap().is_verified);
+        assert!(!vss.verify_share(2, *vss.shares[&2].share_value.expose()));
 
         // Other shares still verified
         assert!(vss.shares.get(&1).unwrap().is_verified);
@@ -214,8 +373,7 @@ mod tests {
 
     #[test]
     fn test_multiple_byzantine_attacks() {
-        let mut vss = VerifiableSecretSharingScheme::new(42, 10, 3, 5);
-        vss.generate_verifiable_shares();
+        let mut vss = vss(42);
 
         // Attacker tries to corrupt 2 shares (still below threshold)
         vss.simulate_corruption(1, 100);
@@ -226,41 +384,39 @@ mod tests {
         assert_eq!(corrupted, 2);
 
         // With 3 honest shares, we can still reconstruct correctly
-        // (threshold = 3, so even with 2 corrupted, we have enough)
+        assert_eq!(vss.recover_secret(), Some(s(42)));
     }
 
     #[test]
     fn test_incoming_share_verification() {
-        let vss = VerifiableSecretSharingScheme::new(42, 10, 3, 5);
+        let vss = vss(42);
+        let received_share = *vss.shares[&1].share_value.expose();
 
         // Honest node receives share from provider
-        let received_share = 52;  // Correct: P(1) = 42 + 10*1 = 52
         assert!(vss.verify_share(1, received_share));
 
         // Corrupted share from Byzantine node
-        let corrupted_share = 50;  // Wrong value
-        assert!(!vss.verify_share(1, corrupted_share));
+        assert!(!vss.verify_share(1, received_share + s(1)));
+        // A valid share presented under the wrong id is rejected too
+        assert!(!vss.verify_share(2, received_share));
     }
 
     #[test]
     fn test_vss_with_field_arithmetic() {
-        // Test with larger numbers (simulating real cryptographic use)
-        let mut vss = VerifiableSecretSharingScheme::new(1234567890, 9876543210, 3, 5);
-        vss.generate_verifiable_shares();
+        // Full-size scalar secret (simulating real cryptographic use)
+        let secret = Scalar25519::from_bytes_mod_order([0x5a; 32]);
+        let vss = VerifiableSecretSharingScheme::new_with_rng(secret, 3, 5, &mut ChaCha20Rng::seed_from_u64(7))
+            .unwrap();
 
         let (verified, corrupted) = vss.detect_byzantine_shares();
         assert_eq!(verified, 5);
         assert_eq!(corrupted, 0);
-
-        // Even with large numbers, verification should work
-        assert!(vss.verify_share(1, 1234567890i64.wrapping_add(9876543210i64.wrapping_mul(1))));
-        assert!(vss.verify_share(2, 1234567890i64.wrapping_add(9876543210i64.wrapping_mul(2))));
+        assert_eq!(vss.recover_secret(), Some(secret));
     }
 
     #[test]
     fn test_recovery_with_corrupted_shares() {
-        let mut vss = VerifiableSecretSharingScheme::new(42, 10, 3, 5);
-        vss.generate_verifiable_shares();
+        let mut vss = vss(42);
 
         // Corrupt 2 shares
         vss.simulate_corruption(4, 1000);
@@ -276,62 +432,73 @@ mod tests {
         assert!(share_ids.contains(&2));
         assert!(share_ids.contains(&3));
 
-        // Verify their values match expected from polynomial
-        for share in verified {
-            let expected = 42 + 10 * (share.id as i64);
-            assert_eq!(share.share_value, expected);
-        }
-    }
-}
+        // Verified shares reconstruct the secret
+        assert_eq!(vss.recover_secret(), Some(s(42)));
 
-fn main() {
-    println!("╔════════════════════════════════════════════╗");
-    println!("║  Share Verification (VSS) Demonstration   ║");
-    println!("╚════════════════════════════════════════════╝\n");
+        // Fewer than threshold verified shares cannot
+        vss.simulate_corruption(3, 1);
+        assert_eq!(vss.recover_secret(), None);
+    }
 
-    // Create VSS scheme: P(x) = 100 + 20x
-    let mut vss = VerifiableSecretSharingScheme::new(100, 20, 3, 5);
-    vss.generate_verifiable_shares();
+    #[test]
+    fn test_pedersen_byzantine_detection() {
+        let mut vss = pedersen(42);
+        assert_eq!(vss.commitment.mode(), VssMode::Pedersen);
+        assert_eq!(vss.detect_byzantine_shares(), (5, 0));
 
-    println!("✓ Generated 5 shares from secret 100 using P(x) = 100 + 20x\n");
+        // Same verify_share API, with the blinding share alongside the value
+        let share = vss.shares[&1].vss_share();
+        assert!(vss.verify_share(1, share.clone_secret()));
+        assert!(!vss.verify_share(1, share.value));
+        assert!(!vss.verify_share(1, VssShare::pedersen(share.value + s(1), share.blinding.unwrap())));
 
-    // Display all shares
-    println!("📋 All Shares:");
-    for (id, share) in &vss.shares {
-        println!(
-            "  Share {}: value={}, verified={}",
-            id, share.share_value, share.is_verified
-        );
+        vss.simulate_corruption(2, 999);
+        vss.simulate_corruption(5, 1);
+        assert_eq!(vss.detect_byzantine_shares(), (3, 2));
+        assert_eq!(vss.recover_secret(), Some(s(42)));
     }
-    println!();
 
-    // Detect Byzantine attack
-    println!("🔴 Simulating Byzantine Attack:");
-    println!("  Attacker corrupts Share 2...\n");
-    vss.simulate_corruption(2, 999);
+    #[test]
+    fn test_pedersen_commitments_differ_for_same_secret() {
+        let a = pedersen(42);
+        let b = VerifiableSecretSharingScheme::new_with_mode(
+            VssMode::Pedersen,
+            s(42),
+            3,
+            5,
+            &mut ChaCha20Rng::seed_from_u64(43),
+        )
+        .unwrap();
+        assert_ne!(a.commitment.to_bytes()[0], b.commitment.to_bytes()[0]);
+        assert_eq!(a.recover_secret(), b.recover_secret());
+    }
 
-    let (verified, corrupted) = vss.detect_byzantine_shares();
-    println!("✓ Detection Results:");
-    println!("  Verified shares:  {}", verified);
-    println!("  Corrupted shares: {}\n", corrupted);
+    #[test]
+    fn test_robust_recovery_matches_byzantine_detection() {
+        // 3-of-5 with one corrupted share: decoding alone finds it
+        let mut vss = vss(42);
+        vss.simulate_corruption(2, 999);
+        let result = vss.recover_secret_robust().unwrap();
+        assert_eq!(result.secret, s(42));
+        assert_eq!(result.faulty_ids, vec![2]);
+        let detected: Vec<u32> = vss.get_corrupted_shares().iter().map(|s| s.id).collect();
+        assert_eq!(result.faulty_ids, detected);
 
-    println!("📋 Shares after corruption:");
-    for (id, share) in &vss.shares {
-        let status = if share.is_verified { "✓" } else { "✗" };
-        println!(
-            "  {} Share {}: value={}, verified={}",
-            status, id, share.share_value, share.is_verified
-        );
+        // Two corrupted shares exceed the 3-of-5 decoding radius
+        vss.simulate_corruption(4, 200);
+        assert!(vss.recover_secret_robust().is_err());
     }
-    println!();
 
-    // Show honest shares can be used for recovery
-    let honest_shares = vss.get_verified_shares();
-    println!("✓ Honest Shares (safe for reconstruction):");
-    for share in &honest_shares {
-        println!("  Share {}: {}", share.id, share.share_value);
+    #[test]
+    fn test_robust_recovery_with_multiple_byzantine_attacks() {
+        // 3-of-7 corrects two corrupted shares
+        let mut rng = ChaCha20Rng::seed_from_u64(9);
+        let mut vss = VerifiableSecretSharingScheme::new_with_rng(s(42), 3, 7, &mut rng).unwrap();
+        vss.simulate_corruption(1, 100);
+        vss.simulate_corruption(6, 200);
+
+        let result = vss.recover_secret_robust().unwrap();
+        assert_eq!(result.secret, s(42));
+        assert_eq!(result.faulty_ids, vec![1, 6]);
     }
-    println!("\n✓ With {} honest shares (threshold={}), secret is recoverable!",
-             honest_shares.len(),
-             vss.threshold);
 }
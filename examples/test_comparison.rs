@@ -1,12 +1,9 @@
-/// Threshold Comparison Tests
-///
-/// Comprehensive test suite for homomorphic threshold comparison
-/// Verifies correctness of encrypted comparison operations
+//! Threshold Comparison Tests
+//!
+//! Comprehensive test suite for homomorphic threshold comparison
+//! Verifies correctness of encrypted comparison operations
 
-mod fhe_module;
-mod aggregation;
-
-use fhe_module::{MockFhe, FheBackend};
+use blocksense_examples::fhe_module::{MockFhe, FheBackend};
 
 fn main() {
     println!("\n╔═══════════════════════════════════════════════════════════╗");
@@ -1,5 +1,5 @@
-/// Simple TFHE Example
-/// Demonstrates basic encryption/decryption with TFHE-rs
+//! Simple TFHE Example
+//! Demonstrates basic encryption/decryption with TFHE-rs
 
 fn main() {
     println!("\n╔═══════════════════════════════════════════════════════════╗");
@@ -1,23 +1,61 @@
-/// Week 4: Threshold Decryption Module
-///
-/// Implements Shamir's Secret Sharing for distributed decryption
-/// Requirements:
-/// - 3-of-5 threshold scheme: Any 3 of 5 decryptors can decrypt
-/// - Key shares generation
-/// - Lagrange interpolation
-/// - Threshold decryption
-
+//! Week 4: Threshold Decryption Module
+//!
+//! Implements Shamir's Secret Sharing for distributed decryption
+//! Requirements:
+//! - 3-of-5 threshold scheme: Any 3 of 5 decryptors can decrypt
+//! - Key shares generation
+//! - Lagrange interpolation
+//! - Threshold decryption
+
+use crate::field::{interpolate_at, lagrange_coefficients, FieldElement, PrimeField};
+use crate::participant_selection::{FirstResponders, SelectionStrategy};
+use crate::proactive::{refresh_shares, reshare_shares};
+use crate::robust::{correctable_errors, robust_reconstruct};
+use crate::secret::Secret;
+use crate::shamir::Polynomial;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use zeroize::Zeroize;
 
 /// Shamir's Secret Share
 /// Represents one share of a secret split among multiple parties
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub struct SecretShare {
     pub id: u32,                    // Share ID (1 to n)
-    pub share_value: i64,           // Actual share value
+    pub share_value: Secret<i64>,   // Actual share value, wiped on drop
     pub public_commitment: Vec<u8>, // Public commitment for verification
 }
 
+impl SecretShare {
+    pub fn new(id: u32, share_value: i64) -> Self {
+        SecretShare {
+            id,
+            share_value: Secret::new(share_value),
+            public_commitment: vec![],
+        }
+    }
+
+    /// Deliberate copy of the share
+    pub fn clone_secret(&self) -> Self {
+        SecretShare {
+            id: self.id,
+            share_value: self.share_value.clone_secret(),
+            public_commitment: self.public_commitment.clone(),
+        }
+    }
+
+    fn to_field(&self) -> FieldElement {
+        FieldElement::from(*self.share_value.expose())
+    }
+}
+
 /// Threshold Decryption Configuration
 #[derive(Clone, Debug)]
 pub struct ThresholdConfig {
@@ -27,12 +65,31 @@ pub struct ThresholdConfig {
 }
 
 impl ThresholdConfig {
+    /// k-of-n Shamir configuration with the scheme name derived from k and n
+    pub fn new(threshold: u32, total_shares: u32) -> Result<Self, String> {
+        let config = ThresholdConfig {
+            total_shares,
+            threshold,
+            scheme: Self::scheme_name(threshold, total_shares),
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn new_2_of_3() -> Self {
+        Self::new(2, 3).expect("2-of-3 is a valid configuration")
+    }
+
     pub fn new_3_of_5() -> Self {
-        ThresholdConfig {
-            total_shares: 5,
-            threshold: 3,
-            scheme: "shamir_3_of_5".to_string(),
-        }
+        Self::new(3, 5).expect("3-of-5 is a valid configuration")
+    }
+
+    pub fn new_5_of_9() -> Self {
+        Self::new(5, 9).expect("5-of-9 is a valid configuration")
+    }
+
+    fn scheme_name(threshold: u32, total_shares: u32) -> String {
+        format!("shamir_{}_of_{}", threshold, total_shares)
     }
 
     pub fn validate(&self) -> Result<(), String> {
@@ -45,12 +102,111 @@ impl ThresholdConfig {
         if self.threshold < 2 {
             return Err("Threshold must be at least 2".to_string());
         }
+        let expected = Self::scheme_name(self.threshold, self.total_shares);
+        if self.scheme != expected {
+            return Err(format!(
+                "Scheme name '{}' does not match {}-of-{} (expected '{}')",
+                self.scheme, self.threshold, self.total_shares, expected
+            ));
+        }
+        // Key shares are dealt over FieldElement
+        self.validate_for_field::<FieldElement>()
+    }
+
+    /// Largest committee whose share ids 1..=n are distinct nonzero
+    /// elements of `F`, capped at `u32::MAX`
+    pub fn max_committee_size<F: PrimeField>() -> u32 {
+        // Every id below 2^(MODULUS_BITS - 1) is smaller than the modulus
+        let bits = F::MODULUS_BITS.saturating_sub(1).min(32);
+        if bits == 32 {
+            u32::MAX
+        } else {
+            (1u32 << bits) - 1
+        }
+    }
+
+    /// Check that every share id fits in the field `F`
+    pub fn validate_for_field<F: PrimeField>(&self) -> Result<(), String> {
+        let max = Self::max_committee_size::<F>();
+        if self.total_shares > max {
+            return Err(format!(
+                "Committee of {} exceeds the maximum of {} for a {}-bit field",
+                self.total_shares,
+                max,
+                F::MODULUS_BITS
+            ));
+        }
+        Ok(())
+    }
+
+    /// Corrupted shares tolerated while keeping the key secret (k − 1)
+    pub fn max_corruptions(&self) -> u32 {
+        self.threshold.saturating_sub(1)
+    }
+
+    /// Faulty shares Berlekamp–Welch can correct with the full committee
+    pub fn max_correctable_faults(&self) -> u32 {
+        correctable_errors(self.total_shares as usize, self.threshold as usize) as u32
+    }
+
+    /// Honest-majority check for DKG: the k − 1 parties the threshold
+    /// tolerates must be a strict minority, i.e. n ≥ 2k − 1
+    pub fn validate_for_dkg(&self) -> Result<(), String> {
+        self.validate()?;
+        if 2 * self.max_corruptions() >= self.total_shares {
+            return Err(format!(
+                "DKG needs an honest majority: {}-of-{} tolerates {} corrupt parties, \
+                 which is not a strict minority (need n >= {})",
+                self.threshold,
+                self.total_shares,
+                self.max_corruptions(),
+                2 * self.threshold - 1
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check that robust reconstruction over the full committee can correct
+    /// `faults` bad shares, i.e. n ≥ k + 2·faults
+    pub fn validate_for_robust_reconstruction(&self, faults: u32) -> Result<(), String> {
+        self.validate()?;
+        if faults > self.max_correctable_faults() {
+            return Err(format!(
+                "{}-of-{} corrects at most {} faulty shares, {} requested (need n >= {})",
+                self.threshold,
+                self.total_shares,
+                self.max_correctable_faults(),
+                faults,
+                self.threshold as u64 + 2 * faults as u64
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check that `ids` are nonzero, unique and no more than the committee size
+    pub fn validate_ids(&self, ids: &[u32]) -> Result<(), String> {
+        if ids.len() > self.total_shares as usize {
+            return Err(format!(
+                "{} decryptor ids given for a committee of {}",
+                ids.len(),
+                self.total_shares
+            ));
+        }
+        let mut seen = HashSet::with_capacity(ids.len());
+        for &id in ids {
+            if id == 0 {
+                return Err("Decryptor id 0 is reserved for the secret".to_string());
+            }
+            if !seen.insert(id) {
+                return Err(format!("Duplicate decryptor id {}", id));
+            }
+        }
         Ok(())
     }
 }
 
 /// Decryptor node that holds one key share
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub struct Decryptor {
     pub id: u32,
     pub name: String,
@@ -65,6 +221,15 @@ impl Decryptor {
             key_share: share,
         }
     }
+
+    /// Deliberate copy of the decryptor and its key share
+    pub fn clone_secret(&self) -> Self {
+        Decryptor {
+            id: self.id,
+            name: self.name.clone(),
+            key_share: self.key_share.clone_secret(),
+        }
+    }
 }
 
 /// Threshold Decryption Scheme
@@ -90,6 +255,15 @@ impl ThresholdScheme {
                 self.config.total_shares
             ));
         }
+        if decryptor.id != decryptor.key_share.id {
+            return Err(format!(
+                "Decryptor {} holds the key share for id {}",
+                decryptor.id, decryptor.key_share.id
+            ));
+        }
+        let mut ids: Vec<u32> = self.decryptors.iter().map(|d| d.id).collect();
+        ids.push(decryptor.id);
+        self.config.validate_ids(&ids)?;
         self.decryptors.push(decryptor);
         Ok(())
     }
@@ -99,12 +273,17 @@ impl ThresholdScheme {
         (self.decryptors.len() as u32) >= self.config.threshold
     }
 
-    /// Get participating decryptors (first threshold of them)
+    /// Get participating decryptors (first threshold of them, in
+    /// registration order)
     pub fn get_participants(&self) -> Vec<&Decryptor> {
-        self.decryptors
-            .iter()
-            .take(self.config.threshold as usize)
-            .collect()
+        self.select_participants(&FirstResponders)
+    }
+
+    /// The first `threshold` decryptors in the strategy's order
+    pub fn select_participants<S: SelectionStrategy + ?Sized>(&self, strategy: &S) -> Vec<&Decryptor> {
+        let mut ordered = strategy.order(&self.decryptors);
+        ordered.truncate(self.config.threshold as usize);
+        ordered
     }
 }
 
@@ -113,41 +292,64 @@ impl ThresholdScheme {
 pub fn lagrange_coefficient(
     decryptor_id: u32,
     participants: &[u32],
-    modulus: i64,
-) -> Result<i64, String> {
-    // L_i(0) = product of (-x_j / (x_i - x_j)) for all j != i
-    
-    let mut numerator = 1i64;
-    let mut denominator = 1i64;
+) -> Result<FieldElement, String> {
+    // L_i(0) = product of (-x_j / (x_i - x_j)) for all j != i, over GF(p)
+    let xs: Vec<FieldElement> = participants.iter().map(|&id| FieldElement::from(id)).collect();
+    let index = participants
+        .iter()
+        .position(|&id| id == decryptor_id)
+        .ok_or_else(|| format!("Decryptor {} is not a participant", decryptor_id))?;
 
-    for &participant_id in participants {
-        if participant_id != decryptor_id {
-            // numerator *= -participant_id
-            numerator = numerator.wrapping_mul(-i64::from(participant_id));
-            
-            // denominator *= (decryptor_id - participant_id)
-            let diff = i64::from(decryptor_id) - i64::from(participant_id);
-            denominator = denominator.wrapping_mul(diff);
-        }
-    }
+    Ok(lagrange_coefficients(&xs, FieldElement::ZERO)?[index])
+}
 
-    // Compute modular inverse of denominator
-    // For now, using simple computation (in production, use mod_inverse)
-    if denominator == 0 {
-        return Err("Division by zero in Lagrange coefficient".to_string());
-    }
+/// Threshold decryption result with the decryptors found faulty
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThresholdDecryption {
+    pub value: i64,
+    /// Decryptor ids whose shares were combined, in the order asked
+    pub participants: Vec<u32>,
+    /// Decryptor ids whose shares were corrected during decoding
+    pub faulty_decryptors: Vec<u32>,
+    /// Decryptors that were asked but did not deliver a share
+    pub unavailable: Vec<(u32, ParticipantFailure)>,
+}
+
+/// Why a selected decryptor did not contribute its share
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParticipantFailure {
+    Failed(String),
+    TimedOut,
+}
 
-    let coefficient = (numerator as f64 / denominator as f64) as i64;
-    Ok(coefficient % modulus)
+impl fmt::Display for ParticipantFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParticipantFailure::Failed(reason) => write!(f, "failed: {}", reason),
+            ParticipantFailure::TimedOut => write!(f, "timed out"),
+        }
+    }
 }
 
 /// Perform threshold decryption using Shamir's Secret Sharing
-/// Combines shares from threshold decryptors to recover the result
+/// Combines shares from threshold decryptors to recover the result.
+/// Shares carry no proof of correctness; decoding every registered share
+/// corrects and names up to (n − k)/2 wrong ones instead.
 pub fn threshold_decrypt(
     scheme: &ThresholdScheme,
     encrypted_value: i64,
-    modulus: i64,
 ) -> Result<i64, String> {
+    threshold_decrypt_robust(scheme, encrypted_value).map(|result| result.value)
+}
+
+/// Threshold decryption over every registered share
+///
+/// Uses Reed–Solomon decoding, so up to (n - k)/2 corrupted shares are
+/// corrected and reported instead of silently producing a wrong value.
+pub fn threshold_decrypt_robust(
+    scheme: &ThresholdScheme,
+    _encrypted_value: i64,
+) -> Result<ThresholdDecryption, String> {
     if !scheme.can_decrypt() {
         return Err(format!(
             "Need {} decryptors, only have {}",
@@ -156,60 +358,241 @@ pub fn threshold_decrypt(
         ));
     }
 
-    let participants: Vec<u32> = scheme
-        .get_participants()
+    let shares: Vec<(u32, FieldElement)> = scheme
+        .decryptors
         .iter()
-        .map(|d| d.id)
+        .map(|d| (d.id, d.key_share.to_field()))
         .collect();
 
-    let mut result = 0i64;
+    let result = robust_reconstruct(&shares, scheme.config.threshold as usize)?;
+    Ok(ThresholdDecryption {
+        value: result.secret.value() as i64,
+        participants: shares.iter().map(|&(id, _)| id).collect(),
+        faulty_decryptors: result.faulty_ids,
+        unavailable: Vec::new(),
+    })
+}
 
-    for participant in scheme.get_participants() {
-        let coeff = lagrange_coefficient(participant.id, &participants, modulus)?;
-        let contribution = participant.key_share.share_value.wrapping_mul(coeff);
-        result = result.wrapping_add(contribution);
+/// Threshold decryption asking decryptors in the strategy's order
+///
+/// `request` fetches the share of the decryptor with the given id. Requests
+/// run concurrently on worker threads, as many as are still needed (or all
+/// of them, if the strategy asks all), and shares are taken in the order
+/// they arrive. Each request is abandoned once `timeout` passes since it
+/// was sent, so a hung decryptor cannot stall the reveal; the abandoned
+/// thread finishes on its own. A decryptor that fails or times out is
+/// recorded as unavailable and the next decryptor in the order is asked
+/// instead.
+///
+/// Collects k + 2·`faults` shares and decodes them with
+/// [`robust_reconstruct`], so up to `faults` wrong shares are corrected
+/// and reported. With `faults` = 0 the k shares are interpolated as given
+/// and a wrong share goes undetected.
+pub fn threshold_decrypt_with<S, F>(
+    scheme: &ThresholdScheme,
+    _encrypted_value: i64,
+    strategy: &S,
+    timeout: Duration,
+    faults: u32,
+    request: F,
+) -> Result<ThresholdDecryption, String>
+where
+    S: SelectionStrategy + ?Sized,
+    F: Fn(u32, Duration) -> Result<i64, ParticipantFailure> + Send + Sync + 'static,
+{
+    scheme.config.validate_for_robust_reconstruction(faults)?;
+    let threshold = scheme.config.threshold as usize;
+    let wanted = threshold + 2 * faults as usize;
+    let request = Arc::new(request);
+    let mut shares: Vec<(u32, FieldElement)> = Vec::with_capacity(wanted);
+    let mut unavailable = Vec::new();
+
+    let mut order = strategy.order(&scheme.decryptors).into_iter().map(|d| d.id);
+    let (sender, receiver) = mpsc::channel();
+    // Requests still awaited, with the time each one is abandoned at
+    let mut in_flight: Vec<(u32, Instant)> = Vec::new();
+    while shares.len() < wanted {
+        let width = if strategy.ask_all() { usize::MAX } else { wanted - shares.len() };
+        while in_flight.len() < width {
+            let Some(id) = order.next() else { break };
+            let (sender, worker) = (sender.clone(), Arc::clone(&request));
+            thread::spawn(move || {
+                let outcome = panic::catch_unwind(AssertUnwindSafe(|| worker(id, timeout)))
+                    .unwrap_or_else(|_| Err(ParticipantFailure::Failed("request panicked".to_string())));
+                // The receiver is gone if the reveal already finished
+                let _ = sender.send((id, outcome));
+            });
+            in_flight.push((id, Instant::now() + timeout));
+        }
+        let Some(first_deadline) = in_flight.iter().map(|&(_, deadline)| deadline).min() else {
+            break;
+        };
+        match receiver.recv_timeout(first_deadline.saturating_duration_since(Instant::now())) {
+            Ok((id, outcome)) => {
+                // A late answer from an abandoned request is dropped
+                let Some(index) = in_flight.iter().position(|&(asked, _)| asked == id) else {
+                    continue;
+                };
+                in_flight.remove(index);
+                match outcome {
+                    Ok(value) => shares.push((id, FieldElement::from(value))),
+                    Err(failure) => unavailable.push((id, failure)),
+                }
+            }
+            Err(_) => {
+                let now = Instant::now();
+                in_flight.retain(|&(id, deadline)| {
+                    let expired = deadline <= now;
+                    if expired {
+                        unavailable.push((id, ParticipantFailure::TimedOut));
+                    }
+                    !expired
+                });
+            }
+        }
     }
 
-    // Normalize result to modulus
-    result = result % modulus;
-    if result < 0 {
-        result += modulus;
+    if shares.len() < wanted {
+        let reasons: Vec<String> = unavailable
+            .iter()
+            .map(|(id, failure)| format!("decryptor {} {}", id, failure))
+            .collect();
+        return Err(format!(
+            "Need {} shares, only {} delivered ({})",
+            wanted,
+            shares.len(),
+            reasons.join(", ")
+        ));
     }
 
-    Ok(result)
+    let participants = shares.iter().map(|&(id, _)| id).collect();
+    if faults == 0 {
+        // Exactly k shares leave no redundancy to decode, so plain
+        // constant-time interpolation recovers the same value
+        let points: Vec<(FieldElement, FieldElement)> = shares
+            .iter()
+            .map(|&(id, share)| (FieldElement::from(id), share))
+            .collect();
+        let secret = interpolate_at(&points, FieldElement::ZERO)?;
+        return Ok(ThresholdDecryption {
+            value: secret.value() as i64,
+            participants,
+            faulty_decryptors: Vec::new(),
+            unavailable,
+        });
+    }
+
+    let result = robust_reconstruct(&shares, threshold)?;
+    Ok(ThresholdDecryption {
+        value: result.secret.value() as i64,
+        participants,
+        faulty_decryptors: result.faulty_ids,
+        unavailable,
+    })
 }
 
-/// Distributed key generation (mock version)
-/// In production, this uses DKG (Distributed Key Generation) protocol
+/// Trusted-dealer key generation (mock version)
+/// The dealer knows the whole secret; see `crate::dkg` for the dealer-free
+/// protocol run among the decryptors themselves
 pub fn generate_key_shares(
     secret: i64,
     config: &ThresholdConfig,
 ) -> Result<Vec<SecretShare>, String> {
-    config.validate()?;
+    generate_key_shares_with_rng(secret, config, &mut OsRng)
+}
 
-    // Mock polynomial: P(x) = secret + a1*x + a2*x^2 + ... (simplified)
-    // For now, generate pseudo-random shares
-    let mut shares = Vec::new();
+/// Deal key shares from P(x) = secret + a1*x + ... + a_{k-1}*x^{k-1}
+/// with the k-1 coefficients drawn from `rng`
+pub fn generate_key_shares_with_rng<R: RngCore + CryptoRng>(
+    secret: i64,
+    config: &ThresholdConfig,
+    rng: &mut R,
+) -> Result<Vec<SecretShare>, String> {
+    config.validate()?;
 
-    for i in 1..=config.total_shares {
-        let share_id = i;
-        // Mock: simple linear scheme P(x) = secret + x*coeff
-        let coefficient = 42i64; // In production: use random coefficients
-        let share_value = secret + (i as i64) * coefficient;
+    // Zeroized when dropped at the end of this function
+    let polynomial = Polynomial::random(
+        FieldElement::from(secret),
+        config.threshold as usize - 1,
+        rng,
+    );
 
-        shares.push(SecretShare {
+    let shares = (1..=config.total_shares)
+        .map(|share_id| SecretShare {
             id: share_id,
-            share_value,
+            share_value: Secret::new(polynomial.evaluate(FieldElement::from(share_id)).value() as i64),
             public_commitment: vec![], // In production: commitment to polynomial coefficients
-        });
-    }
+        })
+        .collect();
 
     Ok(shares)
 }
 
+fn shares_to_field(shares: &[SecretShare]) -> HashMap<u32, FieldElement> {
+    shares.iter().map(|s| (s.id, s.to_field())).collect()
+}
+
+/// Convert back and wipe the field copies
+fn shares_from_field(mut field_shares: HashMap<u32, FieldElement>) -> Vec<SecretShare> {
+    let mut shares: Vec<SecretShare> = field_shares
+        .iter()
+        .map(|(&id, value)| SecretShare::new(id, value.value() as i64))
+        .collect();
+    wipe(&mut field_shares);
+    shares.sort_unstable_by_key(|s| s.id);
+    shares
+}
+
+fn wipe(field_shares: &mut HashMap<u32, FieldElement>) {
+    field_shares.values_mut().for_each(Zeroize::zeroize);
+}
+
+/// Proactive refresh: re-randomize every key share, keeping the key
+///
+/// Run once per epoch so shares stolen in different epochs are useless together.
+pub fn refresh_key_shares<R: RngCore + CryptoRng>(
+    shares: &[SecretShare],
+    config: &ThresholdConfig,
+    rng: &mut R,
+) -> Result<Vec<SecretShare>, String> {
+    config.validate()?;
+    let mut current = shares_to_field(shares);
+    let refreshed = refresh_shares(&current, config.threshold, rng);
+    wipe(&mut current);
+    Ok(shares_from_field(refreshed?))
+}
+
+/// Hand the key from the committee holding `shares` to a new committee
+/// with ids 1..=n′ of `new_config`; ciphertexts need no re-encryption
+pub fn reshare_key_shares<R: RngCore + CryptoRng>(
+    shares: &[SecretShare],
+    config: &ThresholdConfig,
+    new_config: &ThresholdConfig,
+    rng: &mut R,
+) -> Result<Vec<SecretShare>, String> {
+    config.validate()?;
+    new_config.validate()?;
+    let new_ids: Vec<u32> = (1..=new_config.total_shares).collect();
+    let mut current = shares_to_field(shares);
+    let reshared = reshare_shares(&current, config.threshold, new_config.threshold, &new_ids, rng);
+    wipe(&mut current);
+    Ok(shares_from_field(reshared?))
+}
+
+impl fmt::Display for ThresholdConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Threshold Scheme: {}/{} ({})",
+            self.threshold, self.total_shares, self.scheme
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Instant;
 
     #[test]
     fn test_threshold_config_validation() {
@@ -224,29 +607,273 @@ mod tests {
         assert!(bad_config.validate().is_err());
     }
 
+    #[test]
+    fn test_new_derives_scheme_name() {
+        let config = ThresholdConfig::new(4, 7).unwrap();
+        assert_eq!(config.scheme, "shamir_4_of_7");
+        assert_eq!(ThresholdConfig::new_5_of_9().scheme, "shamir_5_of_9");
+        assert!(ThresholdConfig::new(5, 3).is_err());
+        assert!(ThresholdConfig::new(1, 3).is_err());
+
+        let mislabelled = ThresholdConfig {
+            scheme: "shamir_3_of_5".to_string(),
+            ..ThresholdConfig::new_2_of_3()
+        };
+        assert!(mislabelled.validate().is_err());
+    }
+
+    #[test]
+    fn test_policy_checks() {
+        use crate::field::{Goldilocks, Scalar25519};
+
+        assert!(ThresholdConfig::new_3_of_5().validate_for_dkg().is_ok());
+        assert!(ThresholdConfig::new(3, 4).unwrap().validate_for_dkg().is_err());
+        assert!(ThresholdConfig::new(3, 3).unwrap().validate_for_dkg().is_err());
+
+        let config = ThresholdConfig::new_3_of_5();
+        assert_eq!(config.max_correctable_faults(), 1);
+        assert!(config.validate_for_robust_reconstruction(1).is_ok());
+        assert!(config.validate_for_robust_reconstruction(2).is_err());
+        assert!(ThresholdConfig::new_5_of_9().validate_for_robust_reconstruction(2).is_ok());
+
+        assert_eq!(ThresholdConfig::max_committee_size::<FieldElement>(), (1 << 29) - 1);
+        assert_eq!(ThresholdConfig::max_committee_size::<Goldilocks>(), u32::MAX);
+        assert!(config.validate_for_field::<Scalar25519>().is_ok());
+        assert!(ThresholdConfig::new(2, 1 << 29).is_err());
+    }
+
+    #[test]
+    fn test_register_rejects_duplicate_ids() {
+        let config = ThresholdConfig::new_3_of_5();
+        let shares = generate_key_shares(42, &config).unwrap();
+        let mut scheme = ThresholdScheme::new(config.clone()).unwrap();
+
+        scheme.register_decryptor(Decryptor::new(1, "a", shares[0].clone_secret())).unwrap();
+        assert!(scheme.register_decryptor(Decryptor::new(1, "b", shares[0].clone_secret())).is_err());
+        assert!(scheme.register_decryptor(Decryptor::new(2, "c", shares[0].clone_secret())).is_err());
+        assert_eq!(scheme.decryptors.len(), 1);
+
+        assert!(config.validate_ids(&[1, 2, 3]).is_ok());
+        assert!(config.validate_ids(&[1, 2, 1]).is_err());
+        assert!(config.validate_ids(&[0, 1]).is_err());
+        assert!(config.validate_ids(&[1, 2, 3, 4, 5, 6]).is_err());
+    }
+
     #[test]
     fn test_decryptor_registration() {
         let config = ThresholdConfig::new_3_of_5();
         let mut scheme = ThresholdScheme::new(config).unwrap();
 
-        let share = SecretShare {
-            id: 1,
-            share_value: 100,
-            public_commitment: vec![],
-        };
+        let share = SecretShare::new(1, 100);
         let decryptor = Decryptor::new(1, "Decryptor 1", share);
 
         assert!(scheme.register_decryptor(decryptor).is_ok());
         assert!(!scheme.can_decrypt()); // Need 3 out of 5
     }
-}
 
-impl fmt::Display for ThresholdConfig {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "Threshold Scheme: {}/{} ({})",
-            self.threshold, self.total_shares, self.scheme
-        )
+    #[test]
+    fn test_lagrange_coefficient_in_field() {
+        let participants = [1, 2, 3];
+        assert_eq!(lagrange_coefficient(1, &participants).unwrap(), FieldElement::new(3));
+        assert_eq!(lagrange_coefficient(2, &participants).unwrap(), -FieldElement::new(3));
+        assert_eq!(lagrange_coefficient(3, &participants).unwrap(), FieldElement::ONE);
+        assert!(lagrange_coefficient(4, &participants).is_err());
+    }
+
+    #[test]
+    fn test_threshold_decrypt_recovers_secret() {
+        let config = ThresholdConfig::new_3_of_5();
+        let shares = generate_key_shares(42, &config).unwrap();
+
+        // Any 3 of the 5 shares reconstruct the secret
+        for ids in [[0, 1, 2], [0, 2, 4], [1, 3, 4]] {
+            let mut scheme = ThresholdScheme::new(config.clone()).unwrap();
+            for i in ids {
+                let decryptor = Decryptor::new(shares[i].id, "decryptor", shares[i].clone_secret());
+                scheme.register_decryptor(decryptor).unwrap();
+            }
+            assert_eq!(threshold_decrypt(&scheme, 0), Ok(42));
+        }
+    }
+
+    #[test]
+    fn test_key_shares_use_random_degree_k_minus_1_polynomial() {
+        use crate::field::interpolate_at;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let config = ThresholdConfig::new_3_of_5();
+        let a = generate_key_shares_with_rng(42, &config, &mut ChaCha20Rng::seed_from_u64(9)).unwrap();
+        let b = generate_key_shares_with_rng(42, &config, &mut ChaCha20Rng::seed_from_u64(9)).unwrap();
+        let values = |shares: &[SecretShare]| shares.iter().map(|s| *s.share_value.expose()).collect::<Vec<_>>();
+        assert_eq!(values(&a), values(&b));
+
+        // No longer the fixed linear P(x) = secret + 42x
+        assert_ne!(*a[0].share_value.expose(), 84);
+
+        // Two shares of a degree-2 polynomial do not determine the secret
+        let points: Vec<_> = a[..2]
+            .iter()
+            .map(|s| (FieldElement::from(s.id), s.to_field()))
+            .collect();
+        assert_ne!(interpolate_at(&points, FieldElement::ZERO).unwrap(), FieldElement::new(42));
+    }
+
+    #[test]
+    fn test_threshold_decrypt_corrects_and_reports_bad_share() {
+        let config = ThresholdConfig::new_3_of_5();
+        let mut shares = generate_key_shares(42, &config).unwrap();
+        *shares[3].share_value.expose_mut() += 1000;
+
+        let mut scheme = ThresholdScheme::new(config).unwrap();
+        for share in shares {
+            scheme.register_decryptor(Decryptor::new(share.id, "decryptor", share)).unwrap();
+        }
+
+        let result = threshold_decrypt_robust(&scheme, 0).unwrap();
+        assert_eq!(result.value, 42);
+        assert_eq!(result.faulty_decryptors, vec![4]);
+        assert_eq!(threshold_decrypt(&scheme, 0), Ok(42));
+    }
+
+    fn full_scheme(shares: Vec<SecretShare>, config: ThresholdConfig) -> ThresholdScheme {
+        let mut scheme = ThresholdScheme::new(config).unwrap();
+        for share in shares {
+            scheme.register_decryptor(Decryptor::new(share.id, "decryptor", share)).unwrap();
+        }
+        scheme
+    }
+
+    fn share_values(shares: &[SecretShare]) -> HashMap<u32, i64> {
+        shares.iter().map(|s| (s.id, *s.share_value.expose())).collect()
+    }
+
+    #[test]
+    fn test_decrypt_falls_back_past_failed_participants() {
+        use crate::participant_selection::SeededRandom;
+
+        let config = ThresholdConfig::new_3_of_5();
+        let shares = generate_key_shares(42, &config).unwrap();
+        let values = share_values(&shares);
+        let scheme = full_scheme(shares, config);
+
+        let strategy = SeededRandom::new([3; 32]);
+        let order: Vec<u32> = strategy.order(&scheme.decryptors).iter().map(|d| d.id).collect();
+        let (down, slow) = (order[0], order[2]);
+        let timeout = Duration::from_millis(50);
+
+        let started = Instant::now();
+        let answers = values.clone();
+        let result = threshold_decrypt_with(&scheme, 0, &strategy, timeout, 0, move |id, _| {
+            if id == down {
+                Err(ParticipantFailure::Failed("connection refused".to_string()))
+            } else if id == slow {
+                // Hangs far past the deadline; the reveal must not wait
+                thread::sleep(Duration::from_secs(5));
+                Ok(answers[&id])
+            } else {
+                Ok(answers[&id])
+            }
+        })
+        .unwrap();
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        assert_eq!(result.value, 42);
+        let mut participants = result.participants.clone();
+        participants.sort_unstable();
+        let mut expected = vec![order[1], order[3], order[4]];
+        expected.sort_unstable();
+        assert_eq!(participants, expected);
+        assert_eq!(
+            result.unavailable,
+            vec![
+                (down, ParticipantFailure::Failed("connection refused".to_string())),
+                (slow, ParticipantFailure::TimedOut),
+            ]
+        );
+
+        let err = threshold_decrypt_with(&scheme, 0, &FirstResponders, timeout, 0, move |id, _| {
+            if id <= 3 {
+                Err(ParticipantFailure::TimedOut)
+            } else {
+                Ok(values[&id])
+            }
+        })
+        .unwrap_err();
+        assert!(err.contains("only 2 delivered"));
+    }
+
+    #[test]
+    fn test_first_responders_are_the_fastest() {
+        let config = ThresholdConfig::new_3_of_5();
+        let shares = generate_key_shares(42, &config).unwrap();
+        let values = share_values(&shares);
+        let scheme = full_scheme(shares, config);
+
+        // The first two registered decryptors are slow but within the timeout
+        let started = Instant::now();
+        let result = threshold_decrypt_with(&scheme, 0, &FirstResponders, Duration::from_secs(5), 0, move |id, _| {
+            if id <= 2 {
+                thread::sleep(Duration::from_millis(1000));
+            } else {
+                thread::sleep(Duration::from_millis(50 * u64::from(6 - id)));
+            }
+            Ok(values[&id])
+        })
+        .unwrap();
+        assert!(started.elapsed() < Duration::from_millis(800));
+        assert_eq!(result.value, 42);
+        assert_eq!(result.participants, vec![5, 4, 3]);
+        assert!(result.unavailable.is_empty());
+    }
+
+    #[test]
+    fn test_decrypt_with_redundancy_reports_wrong_share() {
+        let config = ThresholdConfig::new_3_of_5();
+        let shares = generate_key_shares(42, &config).unwrap();
+        let mut values = share_values(&shares);
+        *values.get_mut(&2).unwrap() += 1000;
+        let scheme = full_scheme(shares, config);
+        let timeout = Duration::from_secs(1);
+
+        let answers = values.clone();
+        let result =
+            threshold_decrypt_with(&scheme, 0, &FirstResponders, timeout, 1, move |id, _| Ok(answers[&id]))
+                .unwrap();
+        assert_eq!(result.value, 42);
+        let mut participants = result.participants.clone();
+        participants.sort_unstable();
+        assert_eq!(participants, vec![1, 2, 3, 4, 5]);
+        assert_eq!(result.faulty_decryptors, vec![2]);
+
+        // 3-of-5 cannot also correct two faults
+        assert!(
+            threshold_decrypt_with(&scheme, 0, &FirstResponders, timeout, 2, move |id, _| Ok(values[&id]))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_refresh_and_reshare_keep_the_key() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let mut rng = ChaCha20Rng::seed_from_u64(11);
+        let config = ThresholdConfig::new_3_of_5();
+        let shares = generate_key_shares_with_rng(42, &config, &mut rng).unwrap();
+
+        let refreshed = refresh_key_shares(&shares, &config, &mut rng).unwrap();
+        assert!(refreshed.iter().zip(&shares).all(|(a, b)| a.share_value != b.share_value));
+        assert!(!format!("{:?}", refreshed[0]).contains(&refreshed[0].share_value.expose().to_string()));
+
+        let new_config = ThresholdConfig::new_2_of_3();
+        let reshared = reshare_key_shares(&refreshed, &config, &new_config, &mut rng).unwrap();
+        assert_eq!(reshared.len(), 3);
+
+        let mut scheme = ThresholdScheme::new(new_config).unwrap();
+        for share in reshared.into_iter().skip(1) {
+            scheme.register_decryptor(Decryptor::new(share.id, "decryptor", share)).unwrap();
+        }
+        assert_eq!(threshold_decrypt(&scheme, 0), Ok(42));
     }
 }
@@ -0,0 +1,1131 @@
+//! Threshold Decryption of LWE Ciphertexts
+//!
+//! LWE over GF(2^127 − 1): a fresh ciphertext (a, b) encrypts m as
+//! b = ⟨a, s⟩ + Δ·m + e for a binary secret s and small noise e.
+//! The dealer Shamir-shares every coordinate of s among the decryptors.
+//!
+//! The mask a of a fresh ciphertext is expanded from a random seed with
+//! SHA-256, and every other ciphertext is kept as a small integer
+//! combination ∑ cⱼ·(a(seedⱼ), bⱼ) of fresh ones. A decryptor rebuilds the
+//! mask from the seeds itself and computes the noise bound ∑ |cⱼ|·e_max from
+//! the parameters. Nothing a requester sends can pick the mask or claim a
+//! smaller noise. A chosen mask such as a = 2^k·eⱼ would otherwise make each
+//! partial decryption leak the high bits of one key-share coordinate.
+//!
+//! To decrypt, the combiner fixes a quorum S. Decryptor i ∈ S returns
+//! dᵢ = λᵢ·⟨a, sᵢ⟩ + eᵢ, where λᵢ is its Lagrange coefficient for S and eᵢ is
+//! smudging noise that statistically hides the key share. The combiner rounds
+//! b − ∑ dᵢ = Δ·m + e − ∑ eᵢ to m; the key itself is never rebuilt.
+//!
+//! Decryptors apply λᵢ themselves: λᵢ·eᵢ is a full-size field element and
+//! would swamp Δ if the combiner multiplied the noisy partials.
+//!
+//! Partials carry no proof: a DLEQ proof does not fit LWE, and a lattice
+//! proof system is out of scope. A decryptor that adds c·Δ to its partial
+//! shifts the result by c without any one quorum noticing. The committee's
+//! reveal therefore decodes with every quorum and accepts only a value that
+//! all quorums agree on once at most (n − k)/2 decryptors are set aside.
+//! Those set aside are reported as faulty. With exactly k + 2f responders
+//! no smaller family of quorums can tell f liars apart, so the work and
+//! the partials sent grow as C(n, k); committees are capped at
+//! [`MAX_QUORUMS`] quorums, which admits any threshold up to n = 10.
+//!
+//! Plaintexts are limited to ±2^57, which holds a one-day TWAP of an
+//! 8-decimal price at total weight 3. That leaves room for 2^62 smudging
+//! noise, enough to hide ciphertext noise up to 2^22: fresh noise 8 scaled by
+//! a TWAP's Δt·w coefficients summing to 2^19. Parameters are sized for these
+//! workloads, not tuned to a security level.
+
+use crate::field::{lagrange_coefficients, Mersenne127, PrimeField, MERSENNE_127_PRIME};
+#[cfg(any(test, feature = "insecure"))]
+use crate::fhe_module::FheBackend;
+use crate::fhe_module::{FheCiphertext, ResultDecryptor};
+use crate::proactive::{refresh_shares, reshare_shares};
+use crate::shamir::Polynomial;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, Rng, RngCore};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Plaintext scaling factor Δ = 2^68
+const DELTA_BITS: u32 = 68;
+
+/// Plaintexts are signed integers of this many bits: |m| < 2^57
+pub const PLAINTEXT_BITS: u32 = 58;
+
+/// Smudging noise must exceed ciphertext noise by this many bits
+pub const STATISTICAL_SECURITY_BITS: u32 = 40;
+
+const MASK_LABEL: &[u8] = b"bsfo-lwe-mask-v1";
+
+/// LWE parameters shared by the dealer, evaluator and decryptors
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LweParams {
+    /// Length n of the secret key and of the mask a
+    pub dimension: usize,
+    /// Fresh encryption noise is uniform in [−noise_bound, noise_bound]
+    pub noise_bound: u64,
+    /// Smudging noise per partial decryption is uniform in ±2^smudging_bits
+    pub smudging_bits: u32,
+}
+
+impl Default for LweParams {
+    fn default() -> Self {
+        LweParams {
+            dimension: 1024,
+            noise_bound: 8,
+            smudging_bits: 62,
+        }
+    }
+}
+
+impl LweParams {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.dimension == 0 {
+            return Err("LWE dimension must be positive".to_string());
+        }
+        if self.smudging_bits >= DELTA_BITS - 1 {
+            return Err(format!(
+                "Smudging noise 2^{} exceeds the noise budget 2^{}",
+                self.smudging_bits,
+                DELTA_BITS - 1
+            ));
+        }
+        Ok(())
+    }
+
+    /// Largest ciphertext noise a partial decryption can still smudge
+    pub fn max_ciphertext_noise(&self) -> u128 {
+        1u128 << self.smudging_bits.saturating_sub(STATISTICAL_SECURITY_BITS)
+    }
+}
+
+fn delta() -> Mersenne127 {
+    Mersenne127::new(1 << DELTA_BITS)
+}
+
+fn sample_noise<R: RngCore + CryptoRng>(bound: u64, rng: &mut R) -> Mersenne127 {
+    let bound = bound.min(i64::MAX as u64) as i64;
+    Mersenne127::from_i64(rng.gen_range(-bound..=bound))
+}
+
+fn inner_product(a: &[Mersenne127], s: &[Mersenne127]) -> Mersenne127 {
+    a.iter().zip(s).map(|(&x, &y)| x * y).sum()
+}
+
+fn from_i128(value: i128) -> Mersenne127 {
+    let magnitude = Mersenne127::new(value.unsigned_abs());
+    if value < 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// Expand a seed into a uniformly random mask of length `dimension`
+fn expand_mask(seed: &[u8; 32], dimension: usize) -> Vec<Mersenne127> {
+    let mut mask = Vec::with_capacity(dimension);
+    let mut counter = 0u64;
+    while mask.len() < dimension {
+        let block = Sha256::new()
+            .chain_update(MASK_LABEL)
+            .chain_update(seed)
+            .chain_update(counter.to_le_bytes())
+            .finalize();
+        counter += 1;
+        for half in block.chunks_exact(16) {
+            let candidate = u128::from_le_bytes(half.try_into().unwrap()) >> 1;
+            if candidate < MERSENNE_127_PRIME && mask.len() < dimension {
+                mask.push(Mersenne127::new(candidate));
+            }
+        }
+    }
+    mask
+}
+
+/// Round Δ·m + e back to m, reading the field element as a centered integer
+fn decode(phase: Mersenne127) -> i64 {
+    let half = MERSENNE_127_PRIME / 2;
+    let centered = if phase.value() > half {
+        -((MERSENNE_127_PRIME - phase.value()) as i128)
+    } else {
+        phase.value() as i128
+    };
+    let delta = 1i128 << DELTA_BITS;
+    (centered + delta / 2).div_euclid(delta) as i64
+}
+
+/// A field element as 16 little-endian bytes
+pub(crate) fn field_to_bytes(value: Mersenne127) -> [u8; 16] {
+    value.value().to_le_bytes()
+}
+
+/// Inverse of [`field_to_bytes`]; rejects non-canonical encodings
+pub(crate) fn field_from_bytes(bytes: [u8; 16]) -> Result<Mersenne127, String> {
+    let value = u128::from_le_bytes(bytes);
+    if value >= MERSENNE_127_PRIME {
+        return Err("Bytes are not a field element".to_string());
+    }
+    Ok(Mersenne127::new(value))
+}
+
+fn check_plaintext(plaintext: i64) -> Result<(), String> {
+    let limit = 1i64 << (PLAINTEXT_BITS - 1);
+    if !(-limit..limit).contains(&plaintext) {
+        return Err(format!("Plaintext {} outside the LWE range ±2^{}", plaintext, PLAINTEXT_BITS - 1));
+    }
+    Ok(())
+}
+
+/// Binary LWE secret key s ∈ {0, 1}^n
+pub struct LweSecretKey {
+    coefficients: Vec<Mersenne127>,
+}
+
+impl LweSecretKey {
+    pub fn generate<R: RngCore + CryptoRng>(params: &LweParams, rng: &mut R) -> Self {
+        LweSecretKey {
+            coefficients: (0..params.dimension)
+                .map(|_| Mersenne127::from_u64(rng.gen_range(0..=1)))
+                .collect(),
+        }
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.coefficients.len()
+    }
+
+    /// Fresh encryption under a random mask seed; |plaintext| < 2^57
+    pub fn encrypt<R: RngCore + CryptoRng>(
+        &self,
+        params: &LweParams,
+        plaintext: i64,
+        rng: &mut R,
+    ) -> Result<LweCiphertext, String> {
+        check_plaintext(plaintext)?;
+        let mut seed = [0u8; 32];
+        rng.fill_bytes(&mut seed);
+        let a = expand_mask(&seed, self.dimension());
+        let b = inner_product(&a, &self.coefficients)
+            + delta() * Mersenne127::from_i64(plaintext)
+            + sample_noise(params.noise_bound, rng);
+        Ok(LweCiphertext {
+            terms: vec![LweTerm {
+                seed,
+                b,
+                coefficient: 1,
+            }],
+        })
+    }
+
+    /// Full-key decryption, for the dealer and tests
+    pub fn decrypt(&self, ct: &LweCiphertext) -> i64 {
+        let (a, b) = ct.expand(self.dimension());
+        decode(b - inner_product(&a, &self.coefficients))
+    }
+
+    /// Shamir-share every key coordinate with threshold k among ids 1..=n
+    pub fn deal_shares<R: RngCore + CryptoRng>(
+        &self,
+        threshold: u32,
+        total_shares: u32,
+        rng: &mut R,
+    ) -> Result<Vec<LweKeyShare>, String> {
+        if threshold == 0 || threshold > total_shares {
+            return Err(format!(
+                "Invalid threshold {} for {} shares",
+                threshold, total_shares
+            ));
+        }
+        let mut shares: Vec<LweKeyShare> = (1..=total_shares)
+            .map(|id| LweKeyShare {
+                id,
+                coefficients: Vec::with_capacity(self.dimension()),
+            })
+            .collect();
+        for &coefficient in &self.coefficients {
+            let polynomial = Polynomial::random(coefficient, threshold as usize - 1, rng);
+            for share in shares.iter_mut() {
+                share
+                    .coefficients
+                    .push(polynomial.evaluate(Mersenne127::from_u64(share.id as u64)));
+            }
+        }
+        Ok(shares)
+    }
+}
+
+impl Zeroize for LweSecretKey {
+    fn zeroize(&mut self) {
+        self.coefficients.zeroize();
+    }
+}
+
+impl Drop for LweSecretKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for LweSecretKey {}
+
+/// A fresh ciphertext (a(seed), b) scaled by an integer coefficient
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LweTerm {
+    pub seed: [u8; 32],
+    pub b: Mersenne127,
+    pub coefficient: i128,
+}
+
+/// LWE ciphertext ∑ cⱼ·(a(seedⱼ), bⱼ): an integer combination of fresh
+/// ciphertexts, so anyone can rebuild the mask and bound the noise
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LweCiphertext {
+    terms: Vec<LweTerm>,
+}
+
+impl LweCiphertext {
+    pub fn terms(&self) -> &[LweTerm] {
+        &self.terms
+    }
+
+    /// Merge `other`'s terms scaled by `factor`; equal fresh ciphertexts
+    /// share one term. Coefficients saturate, and a saturated ciphertext
+    /// exceeds every noise budget.
+    fn combine(&self, other: &Self, factor: i128) -> Self {
+        let mut terms = self.terms.clone();
+        for term in &other.terms {
+            let scaled = term.coefficient.saturating_mul(factor);
+            match terms.iter_mut().find(|t| t.seed == term.seed && t.b == term.b) {
+                Some(existing) => existing.coefficient = existing.coefficient.saturating_add(scaled),
+                None => terms.push(LweTerm {
+                    coefficient: scaled,
+                    ..*term
+                }),
+            }
+        }
+        terms.retain(|t| t.coefficient != 0);
+        LweCiphertext { terms }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        self.combine(other, 1)
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        self.combine(other, -1)
+    }
+
+    pub fn scalar_mul(&self, scalar: i64) -> Self {
+        LweCiphertext { terms: Vec::new() }.combine(self, scalar as i128)
+    }
+
+    /// Worst-case noise ∑ |cⱼ|·e_max, from the parameters alone
+    pub fn noise_bound(&self, params: &LweParams) -> u128 {
+        self.terms.iter().fold(0u128, |bound, term| {
+            bound.saturating_add(term.coefficient.unsigned_abs().saturating_mul(params.noise_bound as u128))
+        })
+    }
+
+    /// The mask ∑ cⱼ·a(seedⱼ) and body ∑ cⱼ·bⱼ
+    pub fn expand(&self, dimension: usize) -> (Vec<Mersenne127>, Mersenne127) {
+        let mut a = vec![Mersenne127::ZERO; dimension];
+        let mut b = Mersenne127::ZERO;
+        for term in &self.terms {
+            let c = from_i128(term.coefficient);
+            for (x, y) in a.iter_mut().zip(expand_mask(&term.seed, dimension)) {
+                *x += c * y;
+            }
+            b += c * term.b;
+        }
+        (a, b)
+    }
+
+    /// Layout: per term, seed ‖ b ‖ coefficient, with b a little-endian u128
+    /// and the coefficient a little-endian i128
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(64 * self.terms.len());
+        for term in &self.terms {
+            bytes.extend_from_slice(&term.seed);
+            bytes.extend_from_slice(&field_to_bytes(term.b));
+            bytes.extend_from_slice(&term.coefficient.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if !bytes.len().is_multiple_of(64) {
+            return Err(format!("Malformed LWE ciphertext ({} bytes)", bytes.len()));
+        }
+        let terms = bytes
+            .chunks_exact(64)
+            .map(|chunk| {
+                let b = field_from_bytes(chunk[32..48].try_into().unwrap())
+                    .map_err(|_| "LWE ciphertext body is not a field element".to_string())?;
+                Ok(LweTerm {
+                    seed: chunk[..32].try_into().unwrap(),
+                    b,
+                    coefficient: i128::from_le_bytes(chunk[48..].try_into().unwrap()),
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(LweCiphertext { terms })
+    }
+}
+
+impl From<&LweCiphertext> for FheCiphertext {
+    fn from(ct: &LweCiphertext) -> Self {
+        FheCiphertext { data: ct.to_bytes() }
+    }
+}
+
+impl TryFrom<&FheCiphertext> for LweCiphertext {
+    type Error = String;
+
+    fn try_from(ct: &FheCiphertext) -> Result<Self, String> {
+        LweCiphertext::from_bytes(&ct.data)
+    }
+}
+
+/// One decryptor's Shamir share of every secret key coordinate
+pub struct LweKeyShare {
+    pub id: u32,
+    coefficients: Vec<Mersenne127>,
+}
+
+impl LweKeyShare {
+    /// A share from its key coordinates, as unsealed from storage
+    pub(crate) fn from_coefficients(id: u32, coefficients: Vec<Mersenne127>) -> Self {
+        LweKeyShare { id, coefficients }
+    }
+
+    /// Key coordinates, for sealing the share at rest
+    pub(crate) fn coefficients(&self) -> &[Mersenne127] {
+        &self.coefficients
+    }
+
+    /// dᵢ = λᵢ·⟨a, sᵢ⟩ + eᵢ for the given quorum
+    ///
+    /// Rebuilds the mask from the ciphertext's seeds and refuses ciphertexts
+    /// whose noise, computed from `params`, the smudging cannot hide.
+    pub fn partial_decrypt<R: RngCore + CryptoRng>(
+        &self,
+        params: &LweParams,
+        ct: &LweCiphertext,
+        quorum: &[u32],
+        rng: &mut R,
+    ) -> Result<PartialDecryption, String> {
+        if params.dimension != self.coefficients.len() {
+            return Err(format!(
+                "Parameter dimension {} does not match key dimension {}",
+                params.dimension,
+                self.coefficients.len()
+            ));
+        }
+        let noise = ct.noise_bound(params);
+        if noise > params.max_ciphertext_noise() {
+            return Err(format!(
+                "Ciphertext noise {} too large to smudge with 2^{}",
+                noise, params.smudging_bits
+            ));
+        }
+        let position = quorum
+            .iter()
+            .position(|&id| id == self.id)
+            .ok_or_else(|| format!("Decryptor {} is not in the quorum", self.id))?;
+        let xs: Vec<Mersenne127> = quorum.iter().map(|&id| Mersenne127::from_u64(id as u64)).collect();
+        let lambda = lagrange_coefficients(&xs, Mersenne127::ZERO)?[position];
+
+        let (a, _) = ct.expand(params.dimension);
+        let smudging = sample_noise(1u64 << params.smudging_bits, rng);
+        Ok(PartialDecryption {
+            id: self.id,
+            value: lambda * inner_product(&a, &self.coefficients) + smudging,
+        })
+    }
+}
+
+impl fmt::Debug for LweKeyShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LweKeyShare({}, {} coefficients)", self.id, self.coefficients.len())
+    }
+}
+
+impl Zeroize for LweKeyShare {
+    fn zeroize(&mut self) {
+        self.coefficients.zeroize();
+    }
+}
+
+impl Drop for LweKeyShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for LweKeyShare {}
+
+/// Decryptor i's contribution λᵢ·⟨a, sᵢ⟩ + eᵢ
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PartialDecryption {
+    pub id: u32,
+    pub value: Mersenne127,
+}
+
+/// Combine partial decryptions from one quorum of at least `threshold`
+///
+/// Every partial must have been computed for exactly this set of ids.
+pub fn combine_partial_decryptions(
+    params: &LweParams,
+    ct: &LweCiphertext,
+    partials: &[PartialDecryption],
+    threshold: u32,
+) -> Result<i64, String> {
+    if partials.len() < threshold as usize {
+        return Err(format!(
+            "Need {} partial decryptions, only have {}",
+            threshold,
+            partials.len()
+        ));
+    }
+    let ids: HashSet<u32> = partials.iter().map(|p| p.id).collect();
+    if ids.len() != partials.len() {
+        return Err("Duplicate partial decryptions".to_string());
+    }
+    let total_noise = ct
+        .noise_bound(params)
+        .saturating_add((partials.len() as u128) << params.smudging_bits);
+    if total_noise >= 1u128 << (DELTA_BITS - 1) {
+        return Err(format!(
+            "Noise bound {} exceeds the decryption budget",
+            total_noise
+        ));
+    }
+
+    let (_, b) = ct.expand(0);
+    let masked: Mersenne127 = partials.iter().map(|p| p.value).sum();
+    Ok(decode(b - masked))
+}
+
+/// Threshold LWE decryption with the decryptors found faulty
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LweDecryption {
+    pub value: i64,
+    /// Decryptors whose partials disagreed with the accepted value (sorted)
+    pub faulty_decryptors: Vec<u32>,
+}
+
+/// Most quorums C(n, k) a committee may have
+///
+/// A reveal decodes every quorum of its responders, and each decryptor
+/// sends a partial for every quorum it belongs to.
+pub const MAX_QUORUMS: usize = 256;
+
+/// Number of quorums C(n, k), saturating
+pub fn quorum_count(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    (0..k.min(n - k)).fold(1usize, |acc, i| acc.saturating_mul(n - i) / (i + 1))
+}
+
+/// Check a k-of-n committee is small enough to cross-check
+pub fn check_committee_size(n: usize, threshold: u32) -> Result<(), String> {
+    let threshold = threshold as usize;
+    if threshold == 0 || threshold > n {
+        return Err(format!("Invalid threshold {} for {} decryptors", threshold, n));
+    }
+    let count = quorum_count(n, threshold);
+    if count > MAX_QUORUMS {
+        return Err(format!(
+            "A {}-of-{} committee has {} quorums, more than the {} a reveal can cross-check",
+            threshold, n, count, MAX_QUORUMS
+        ));
+    }
+    Ok(())
+}
+
+/// Every quorum of `threshold` decryptors among `ids`, in the order of `ids`
+pub fn quorums(ids: &[u32], threshold: u32) -> Vec<Vec<u32>> {
+    subsets(ids, threshold as usize)
+}
+
+/// Every set of `size` ids drawn from `ids`, keeping the order of `ids`
+fn subsets(ids: &[u32], size: usize) -> Vec<Vec<u32>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+    if ids.len() < size {
+        return Vec::new();
+    }
+    let mut with_first = subsets(&ids[1..], size - 1);
+    for subset in with_first.iter_mut() {
+        subset.insert(0, ids[0]);
+    }
+    with_first.extend(subsets(&ids[1..], size));
+    with_first
+}
+
+/// Decode with every quorum and accept the value all quorums agree on once
+/// at most `faults` decryptors are set aside
+///
+/// `quorum_partials` holds one set of partials per quorum of `threshold`
+/// among the participating decryptors, each computed for that quorum. With
+/// n ≥ k + 2·faults participants, every set of n − faults decryptors holds
+/// an honest quorum, so no faulty minority can make a wrong value agree.
+/// The fewest decryptors that explain the disagreement are reported. At
+/// most [`MAX_QUORUMS`] quorums are decoded.
+pub fn combine_cross_checked(
+    params: &LweParams,
+    ct: &LweCiphertext,
+    quorum_partials: &[Vec<PartialDecryption>],
+    threshold: u32,
+    faults: u32,
+) -> Result<LweDecryption, String> {
+    let mut ids: Vec<u32> = quorum_partials.iter().flatten().map(|p| p.id).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    let threshold = threshold as usize;
+    if threshold == 0 || ids.len() < threshold + 2 * faults as usize {
+        return Err(format!(
+            "Cross-checking {} faults needs {} decryptors, only have {}",
+            faults,
+            threshold + 2 * faults as usize,
+            ids.len()
+        ));
+    }
+    check_committee_size(ids.len(), threshold as u32)?;
+
+    let mut decoded: HashMap<Vec<u32>, Option<i64>> = HashMap::new();
+    for partials in quorum_partials {
+        let mut quorum: Vec<u32> = partials.iter().map(|p| p.id).collect();
+        quorum.sort_unstable();
+        if quorum.len() != threshold {
+            return Err(format!("Quorum {:?} does not have {} members", quorum, threshold));
+        }
+        let value = combine_partial_decryptions(params, ct, partials, threshold as u32).ok();
+        if decoded.insert(quorum.clone(), value).is_some() {
+            return Err(format!("Quorum {:?} given twice", quorum));
+        }
+    }
+    let quorums = subsets(&ids, threshold);
+    if let Some(missing) = quorums.iter().find(|q| !decoded.contains_key(*q)) {
+        return Err(format!("No partials for quorum {:?}", missing));
+    }
+
+    for excluded in 0..=faults as usize {
+        for set_aside in subsets(&ids, excluded) {
+            let mut values = quorums
+                .iter()
+                .filter(|q| q.iter().all(|id| !set_aside.contains(id)))
+                .map(|q| decoded[q]);
+            let first = values.next().flatten();
+            if let Some(value) = first {
+                if values.all(|v| v == Some(value)) {
+                    return Ok(LweDecryption {
+                        value,
+                        faulty_decryptors: set_aside,
+                    });
+                }
+            }
+        }
+    }
+    Err(format!(
+        "Partial decryptions disagree beyond {} faulty decryptors",
+        faults
+    ))
+}
+
+/// Key-holding LWE evaluator: a trusted stand-in for bootstrapping
+///
+/// Linear operations are homomorphic. LWE alone cannot multiply or compare,
+/// so `mul` and `gt` decrypt with the full key and re-encrypt, the same
+/// shortcut `MockFhe` takes, and secret-key LWE needs the key to encrypt.
+/// Whoever runs it holds the key the committee's shares were dealt from and
+/// can decrypt anything, so it is only built for tests and with the
+/// `insecure` feature; real TFHE would evaluate with bootstrapping keys
+/// instead. It is deliberately not a [`ResultDecryptor`]: reveals go
+/// through [`LweDecryptionCommittee`].
+#[cfg(any(test, feature = "insecure"))]
+pub struct LweFhe {
+    params: LweParams,
+    key: LweSecretKey,
+}
+
+#[cfg(any(test, feature = "insecure"))]
+impl LweFhe {
+    pub fn new(params: LweParams, key: LweSecretKey) -> Result<Self, String> {
+        params.validate()?;
+        if key.dimension() != params.dimension {
+            return Err("Key dimension does not match parameters".to_string());
+        }
+        Ok(LweFhe { params, key })
+    }
+
+    pub fn params(&self) -> &LweParams {
+        &self.params
+    }
+
+    fn lwe(ct: &FheCiphertext) -> LweCiphertext {
+        LweCiphertext::try_from(ct).expect("LweFhe received a non-LWE ciphertext")
+    }
+
+    /// Panics if the plaintext is outside ±2^57, as `MockFhe` panics on
+    /// i64 overflow
+    fn fresh(&self, plaintext: i64) -> FheCiphertext {
+        let ct = self
+            .key
+            .encrypt(&self.params, plaintext, &mut OsRng)
+            .expect("plaintext outside the LWE range");
+        (&ct).into()
+    }
+}
+
+#[cfg(any(test, feature = "insecure"))]
+impl FheBackend for LweFhe {
+    fn encrypt(&self, plaintext: i64) -> FheCiphertext {
+        self.fresh(plaintext)
+    }
+
+    fn decrypt(&self, ct: &FheCiphertext) -> i64 {
+        self.key.decrypt(&Self::lwe(ct))
+    }
+
+    fn add(&self, ct_a: &FheCiphertext, ct_b: &FheCiphertext) -> FheCiphertext {
+        (&Self::lwe(ct_a).add(&Self::lwe(ct_b))).into()
+    }
+
+    fn scalar_mul(&self, ct: &FheCiphertext, scalar: i64) -> FheCiphertext {
+        (&Self::lwe(ct).scalar_mul(scalar)).into()
+    }
+
+    fn mul(&self, ct_a: &FheCiphertext, ct_b: &FheCiphertext) -> FheCiphertext {
+        self.fresh(self.decrypt(ct_a).wrapping_mul(self.decrypt(ct_b)))
+    }
+
+    fn sub(&self, ct_a: &FheCiphertext, ct_b: &FheCiphertext) -> FheCiphertext {
+        (&Self::lwe(ct_a).sub(&Self::lwe(ct_b))).into()
+    }
+
+    fn gt(&self, ct_a: &FheCiphertext, ct_b: &FheCiphertext) -> FheCiphertext {
+        self.fresh((self.decrypt(ct_a) > self.decrypt(ct_b)) as i64)
+    }
+}
+
+/// Decryptors holding LWE key shares, run in one process
+///
+/// [`LweDecryptionCommittee::decrypt_lwe`] uses the first `threshold`
+/// decryptors as the quorum; reveals cross-check every quorum instead.
+#[derive(Debug)]
+pub struct LweDecryptionCommittee {
+    params: LweParams,
+    threshold: u32,
+    shares: Vec<LweKeyShare>,
+}
+
+impl LweDecryptionCommittee {
+    pub fn new(params: LweParams, threshold: u32, shares: Vec<LweKeyShare>) -> Result<Self, String> {
+        params.validate()?;
+        if threshold == 0 || shares.len() < threshold as usize {
+            return Err(format!(
+                "Need {} key shares, only have {}",
+                threshold,
+                shares.len()
+            ));
+        }
+        check_committee_size(shares.len(), threshold)?;
+        Ok(LweDecryptionCommittee {
+            params,
+            threshold,
+            shares,
+        })
+    }
+
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    pub fn decrypt_lwe(&self, ct: &LweCiphertext) -> Result<i64, String> {
+        let quorum: Vec<u32> = self.shares[..self.threshold as usize]
+            .iter()
+            .map(|share| share.id)
+            .collect();
+        let partials = self.shares[..self.threshold as usize]
+            .iter()
+            .map(|share| share.partial_decrypt(&self.params, ct, &quorum, &mut OsRng))
+            .collect::<Result<Vec<_>, _>>()?;
+        combine_partial_decryptions(&self.params, ct, &partials, self.threshold)
+    }
+
+    /// Decrypt with every quorum of the first k + 2·`faults` decryptors,
+    /// tolerating up to `faults` whose partials are wrong
+    pub fn decrypt_cross_checked(&self, ct: &LweCiphertext, faults: u32) -> Result<LweDecryption, String> {
+        let participants = (self.threshold + 2 * faults) as usize;
+        if participants > self.shares.len() {
+            return Err(format!(
+                "Cross-checking {} faults needs {} decryptors, only have {}",
+                faults,
+                participants,
+                self.shares.len()
+            ));
+        }
+        let shares = &self.shares[..participants];
+        let ids: Vec<u32> = shares.iter().map(|share| share.id).collect();
+        let quorum_partials = subsets(&ids, self.threshold as usize)
+            .into_iter()
+            .map(|quorum| {
+                shares
+                    .iter()
+                    .filter(|share| quorum.contains(&share.id))
+                    .map(|share| share.partial_decrypt(&self.params, ct, &quorum, &mut OsRng))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        combine_cross_checked(&self.params, ct, &quorum_partials, self.threshold, faults)
+    }
+
+    /// Faulty decryptors a reveal tolerates: ⌊(n − k)/2⌋
+    pub fn max_correctable_faults(&self) -> u32 {
+        (self.shares.len() as u32 - self.threshold) / 2
+    }
+
+    pub fn ids(&self) -> Vec<u32> {
+        self.shares.iter().map(|share| share.id).collect()
+    }
+
+    /// Proactive refresh of every key share, coordinate by coordinate
+    pub fn refresh<R: RngCore + CryptoRng>(&mut self, rng: &mut R) -> Result<(), String> {
+        for j in 0..self.params.dimension {
+            let coordinate = self.coordinate(j);
+            let refreshed = refresh_shares(&coordinate, self.threshold, rng)?;
+            for share in self.shares.iter_mut() {
+                share.coefficients[j] = refreshed[&share.id];
+            }
+        }
+        Ok(())
+    }
+
+    /// Hand the key to a k′-of-n′ committee on `new_ids`; existing
+    /// ciphertexts stay decryptable by the new committee
+    pub fn reshare<R: RngCore + CryptoRng>(
+        &self,
+        new_threshold: u32,
+        new_ids: &[u32],
+        rng: &mut R,
+    ) -> Result<LweDecryptionCommittee, String> {
+        check_committee_size(new_ids.len(), new_threshold)?;
+        let mut shares: Vec<LweKeyShare> = new_ids
+            .iter()
+            .map(|&id| LweKeyShare {
+                id,
+                coefficients: Vec::with_capacity(self.params.dimension),
+            })
+            .collect();
+        for j in 0..self.params.dimension {
+            let reshared = reshare_shares(&self.coordinate(j), self.threshold, new_threshold, new_ids, rng)?;
+            for share in shares.iter_mut() {
+                share.coefficients.push(reshared[&share.id]);
+            }
+        }
+        LweDecryptionCommittee::new(self.params, new_threshold, shares)
+    }
+
+    fn coordinate(&self, j: usize) -> HashMap<u32, Mersenne127> {
+        self.shares
+            .iter()
+            .map(|share| (share.id, share.coefficients[j]))
+            .collect()
+    }
+}
+
+impl ResultDecryptor for LweDecryptionCommittee {
+    fn decrypt_result(&self, ct: &FheCiphertext) -> Result<i64, String> {
+        let ct = LweCiphertext::try_from(ct)?;
+        Ok(self.decrypt_cross_checked(&ct, self.max_correctable_faults())?.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregation::{
+        homomorphic_twap_consensus, oracle_aggregation_workflow, reveal_oracle_result, reveal_twap,
+        ProviderSubmission, SubmissionWindow,
+    };
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    fn params() -> LweParams {
+        LweParams {
+            dimension: 256,
+            ..LweParams::default()
+        }
+    }
+
+    fn setup(threshold: u32, total: u32, seed: u64) -> (LweSecretKey, Vec<LweKeyShare>, ChaCha20Rng) {
+        let mut rng = ChaCha20Rng::seed_from_u64(seed);
+        let key = LweSecretKey::generate(&params(), &mut rng);
+        let shares = key.deal_shares(threshold, total, &mut rng).unwrap();
+        (key, shares, rng)
+    }
+
+    fn threshold_decrypt(
+        shares: &[&LweKeyShare],
+        ct: &LweCiphertext,
+        rng: &mut ChaCha20Rng,
+    ) -> Result<i64, String> {
+        let quorum: Vec<u32> = shares.iter().map(|s| s.id).collect();
+        let partials = shares
+            .iter()
+            .map(|s| s.partial_decrypt(&params(), ct, &quorum, rng))
+            .collect::<Result<Vec<_>, _>>()?;
+        combine_partial_decryptions(&params(), ct, &partials, quorum.len() as u32)
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_full_plaintext_range() {
+        let (key, _, mut rng) = setup(3, 5, 1);
+        let limit = 1i64 << (PLAINTEXT_BITS - 1);
+        for m in [0, 1, -1, 5000, 325050000000, limit - 1, -limit] {
+            let ct = key.encrypt(&params(), m, &mut rng).unwrap();
+            assert_eq!(key.decrypt(&ct), m);
+        }
+        assert!(key.encrypt(&params(), limit, &mut rng).is_err());
+        assert!(key.encrypt(&params(), i64::MIN, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_any_quorum_decrypts_without_the_key() {
+        let (key, shares, mut rng) = setup(3, 5, 2);
+        let ct = key.encrypt(&params(), -123_456_789, &mut rng).unwrap();
+
+        for quorum in [[0, 1, 2], [0, 2, 4], [4, 3, 1], [1, 2, 3]] {
+            let members: Vec<&LweKeyShare> = quorum.iter().map(|&i| &shares[i]).collect();
+            assert_eq!(threshold_decrypt(&members, &ct, &mut rng).unwrap(), -123_456_789);
+        }
+        // A larger quorum works too
+        let all: Vec<&LweKeyShare> = shares.iter().collect();
+        assert_eq!(threshold_decrypt(&all, &ct, &mut rng).unwrap(), -123_456_789);
+    }
+
+    #[test]
+    fn test_below_threshold_quorum_decrypts_garbage() {
+        let (key, shares, mut rng) = setup(3, 5, 3);
+        let ct = key.encrypt(&params(), 42, &mut rng).unwrap();
+
+        let partials: Vec<PartialDecryption> = shares[..2]
+            .iter()
+            .map(|s| s.partial_decrypt(&params(), &ct, &[1, 2], &mut rng).unwrap())
+            .collect();
+        assert!(combine_partial_decryptions(&params(), &ct, &partials, 3).is_err());
+        // Forcing the combination with a lower threshold gives a wrong value
+        assert_ne!(combine_partial_decryptions(&params(), &ct, &partials, 2).unwrap(), 42);
+    }
+
+    #[test]
+    fn test_smudged_partials_differ_between_runs() {
+        let (key, shares, mut rng) = setup(2, 3, 4);
+        let ct = key.encrypt(&params(), 7, &mut rng).unwrap();
+        let first = shares[0].partial_decrypt(&params(), &ct, &[1, 2], &mut rng).unwrap();
+        let second = shares[0].partial_decrypt(&params(), &ct, &[1, 2], &mut rng).unwrap();
+        assert_ne!(first.value, second.value);
+    }
+
+    #[test]
+    fn test_homomorphic_linear_ops_track_noise() {
+        let (key, shares, mut rng) = setup(2, 3, 5);
+        let a = key.encrypt(&params(), 5000, &mut rng).unwrap();
+        let b = key.encrypt(&params(), 4900, &mut rng).unwrap();
+
+        // Terms of the same fresh ciphertext merge: 2·a + b
+        let combined = a.scalar_mul(3).add(&b).sub(&a);
+        assert_eq!(combined.terms().len(), 2);
+        assert_eq!(combined.noise_bound(&params()), 8 * 2 + 8);
+        let members: Vec<&LweKeyShare> = shares[1..].iter().collect();
+        assert_eq!(threshold_decrypt(&members, &combined, &mut rng).unwrap(), 2 * 5000 + 4900);
+
+        // Noise the smudging cannot hide is refused
+        let noisy = a.scalar_mul(1 << 20);
+        assert!(threshold_decrypt(&members, &noisy, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_partial_decrypt_rejects_bad_input() {
+        let (key, shares, mut rng) = setup(2, 3, 6);
+        let ct = key.encrypt(&params(), 1, &mut rng).unwrap();
+        assert!(shares[2].partial_decrypt(&params(), &ct, &[1, 2], &mut rng).is_err());
+
+        let wrong_dimension = LweParams {
+            dimension: 128,
+            ..params()
+        };
+        assert!(shares[0].partial_decrypt(&wrong_dimension, &ct, &[1, 2], &mut rng).is_err());
+        assert!(key.deal_shares(4, 3, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_requester_cannot_choose_mask_or_noise() {
+        let (key, shares, mut rng) = setup(2, 3, 10);
+        let ct = key.encrypt(&params(), 1, &mut rng).unwrap();
+
+        // The mask is rebuilt from the seed, so editing b alone shifts the
+        // plaintext but never exposes ⟨a, sᵢ⟩ for an attacker-chosen a
+        let mut forged = ct.to_bytes();
+        forged[0] ^= 1;
+        let forged = LweCiphertext::from_bytes(&forged).unwrap();
+        let (a, _) = forged.expand(params().dimension);
+        assert_ne!(a, ct.expand(params().dimension).0);
+
+        // A huge coefficient cannot be passed off as low noise
+        let mut scaled = ct.to_bytes();
+        scaled[48..].copy_from_slice(&(1i128 << 100).to_le_bytes());
+        let scaled = LweCiphertext::from_bytes(&scaled).unwrap();
+        assert!(shares[0].partial_decrypt(&params(), &scaled, &[1, 2], &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_cross_check_sets_aside_a_shifting_decryptor() {
+        let (key, shares, mut rng) = setup(3, 5, 12);
+        let ct = key.encrypt(&params(), 5000, &mut rng).unwrap();
+        let ids: Vec<u32> = shares.iter().map(|s| s.id).collect();
+
+        // Decryptor 2 adds 7·Δ, shifting every quorum it joins by 7
+        let quorum_partials: Vec<Vec<PartialDecryption>> = subsets(&ids, 3)
+            .iter()
+            .map(|quorum| {
+                shares
+                    .iter()
+                    .filter(|s| quorum.contains(&s.id))
+                    .map(|s| {
+                        let mut partial = s.partial_decrypt(&params(), &ct, quorum, &mut rng).unwrap();
+                        if s.id == 2 {
+                            partial.value -= delta() * Mersenne127::from_u64(7);
+                        }
+                        partial
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let result = combine_cross_checked(&params(), &ct, &quorum_partials, 3, 1).unwrap();
+        assert_eq!(result.value, 5000);
+        assert_eq!(result.faulty_decryptors, vec![2]);
+
+        // Without room to set anyone aside the disagreement is an error
+        assert!(combine_cross_checked(&params(), &ct, &quorum_partials, 3, 0).is_err());
+        // 3-of-5 cannot tolerate two faults
+        assert!(combine_cross_checked(&params(), &ct, &quorum_partials, 3, 2).is_err());
+        // Every quorum must be present
+        assert!(combine_cross_checked(&params(), &ct, &quorum_partials[1..], 3, 1).is_err());
+    }
+
+    #[test]
+    fn test_committee_size_is_capped_by_quorum_count() {
+        assert_eq!(quorum_count(5, 3), 10);
+        assert_eq!(quorum_count(10, 5), 252);
+        assert_eq!(quorum_count(3, 4), 0);
+        assert!(quorum_count(200, 100) > MAX_QUORUMS);
+
+        for k in 1..=10 {
+            assert!(check_committee_size(10, k).is_ok());
+        }
+        assert!(check_committee_size(11, 5).is_err());
+        assert!(check_committee_size(30, 2).is_err());
+        assert!(check_committee_size(5, 0).is_err());
+        assert!(check_committee_size(5, 6).is_err());
+
+        // Dealing to a committee past the cap is fine; using it is not
+        let (_, shares, mut rng) = setup(5, 11, 13);
+        assert!(LweDecryptionCommittee::new(params(), 5, shares).is_err());
+        let (_, shares, _) = setup(3, 5, 14);
+        let committee = LweDecryptionCommittee::new(params(), 3, shares).unwrap();
+        let too_many: Vec<u32> = (1..=11).collect();
+        assert!(committee.reshare(5, &too_many, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_ciphertext_bytes_round_trip() {
+        let (key, _, mut rng) = setup(2, 3, 7);
+        let ct = key.encrypt(&params(), 99, &mut rng).unwrap();
+        let wrapped = FheCiphertext::from(&ct);
+        assert_eq!(LweCiphertext::try_from(&wrapped).unwrap(), ct);
+        assert!(LweCiphertext::from_bytes(&wrapped.data[..40]).is_err());
+
+        let mut not_a_field_element = wrapped.data.clone();
+        not_a_field_element[32..48].copy_from_slice(&u128::MAX.to_le_bytes());
+        assert!(LweCiphertext::from_bytes(&not_a_field_element).is_err());
+    }
+
+    #[test]
+    fn test_oracle_reveal_by_committee() {
+        let (key, shares, _) = setup(3, 5, 8);
+        let committee = LweDecryptionCommittee::new(params(), 3, shares).unwrap();
+        let backend = LweFhe::new(params(), key).unwrap();
+
+        let subs: Vec<ProviderSubmission> = [("A", 5000, 2), ("B", 4900, 1), ("C", 5200, 3)]
+            .iter()
+            .map(|&(id, value, weight)| ProviderSubmission {
+                provider_id: id.to_string(),
+                quantized_value: value,
+                weight,
+                timestamp: 1729418400,
+            })
+            .collect();
+        let result = oracle_aggregation_workflow(&backend, &subs, 5000);
+
+        let decision = reveal_oracle_result(&committee, &result).unwrap();
+        assert_eq!(committee.max_correctable_faults(), 1);
+        assert_eq!(decision.aggregate_value, 5000 * 2 + 4900 + 5200 * 3);
+        assert_eq!(decision.aggregate_value, backend.decrypt(&result.aggregate_ciphertext));
+        assert_eq!(decision.decision, "YES");
+    }
+
+    #[test]
+    fn test_hour_twap_reveal_by_committee() {
+        let (key, shares, _) = setup(3, 5, 11);
+        let committee = LweDecryptionCommittee::new(params(), 3, shares).unwrap();
+        let backend = LweFhe::new(params(), key).unwrap();
+
+        // One hour of 8-decimal prices at total weight 3
+        let window = SubmissionWindow::new(0, 3600).unwrap();
+        let subs: Vec<ProviderSubmission> = [
+            ("A", 325050000000, 2, 0),
+            ("A", 325100000000, 2, 1800),
+            ("B", 324950000000, 1, 0),
+        ]
+        .iter()
+        .map(|&(id, value, weight, timestamp)| ProviderSubmission {
+            provider_id: id.to_string(),
+            quantized_value: value,
+            weight,
+            timestamp,
+        })
+        .collect();
+        let twap = homomorphic_twap_consensus(&backend, &subs, &window).unwrap();
+        let consensus = LweCiphertext::try_from(&twap.consensus_ciphertext).unwrap();
+        assert!(consensus.noise_bound(&params()) >= 8 * 3 * 3600);
+        assert!(consensus.noise_bound(&params()) <= params().max_ciphertext_noise());
+
+        // A: (325050000000 + 325100000000) / 2 = 325075000000, B: 324950000000
+        // Consensus = (2·325075000000 + 324950000000) / 3
+        assert_eq!(reveal_twap(&committee, &twap).unwrap(), 325033333333);
+    }
+
+    #[test]
+    fn test_committee_refresh_and_reshare_keep_ciphertexts_decryptable() {
+        let (key, shares, mut rng) = setup(3, 5, 9);
+        let ct = key.encrypt(&params(), 5000, &mut rng).unwrap();
+        let mut committee = LweDecryptionCommittee::new(params(), 3, shares).unwrap();
+
+        committee.refresh(&mut rng).unwrap();
+        assert_eq!(committee.decrypt_lwe(&ct).unwrap(), 5000);
+
+        // Decryptors 1, 2 leave; 6 and 7 join as a 2-of-3 committee
+        let next = committee.reshare(2, &[3, 6, 7], &mut rng).unwrap();
+        assert_eq!(next.ids(), vec![3, 6, 7]);
+        assert_eq!(next.threshold(), 2);
+        assert_eq!(next.decrypt_lwe(&ct).unwrap(), 5000);
+    }
+}
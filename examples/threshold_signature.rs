@@ -0,0 +1,529 @@
+//! FROST Threshold Schnorr Signatures over Ristretto255
+//!
+//! The decryptor committee signs the canonical oracle result with the key
+//! from `crate::dkg`, so a k-of-n signature verifies as a single Schnorr
+//! signature under the group public key Y.
+//!
+//! Two rounds (RFC 9591 shape, without the ciphersuite's exact hashing):
+//! 1. Each signer i publishes nonce commitments (Dᵢ, Eᵢ) = (dᵢ·G, eᵢ·G).
+//! 2. With binding factors ρᵢ = H(Y, m, B, i) over the commitment list B,
+//!    R = ∑ (Dᵢ + ρᵢ·Eᵢ) and c = H(R, Y, m), signer i returns
+//!    zᵢ = dᵢ + ρᵢ·eᵢ + λᵢ·xᵢ·c.
+//!
+//! The aggregator checks every zᵢ against the signer's verification share,
+//! names the signers whose shares fail, and outputs (R, z = ∑ zᵢ).
+
+use crate::dkg::{run_dkg, DkgOutput};
+use crate::field::{lagrange_coefficients, PrimeField, Scalar25519};
+use crate::secret::Secret;
+use crate::threshold_decryption::ThresholdConfig;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+const BINDING_LABEL: &[u8] = b"blocksense-fhe-oracle/frost/rho";
+const CHALLENGE_LABEL: &[u8] = b"blocksense-fhe-oracle/frost/chal";
+const ORACLE_RESULT_LABEL: &[u8] = b"blocksense-fhe-oracle/oracle-result/v1";
+
+fn hash_to_scalar(label: &[u8], parts: &[&[u8]]) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(label);
+    for part in parts {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part);
+    }
+    Scalar::from_hash(hasher)
+}
+
+/// c = H(R, Y, m)
+fn challenge(group_commitment: &RistrettoPoint, public_key: &RistrettoPoint, message: &[u8]) -> Scalar {
+    hash_to_scalar(
+        CHALLENGE_LABEL,
+        &[
+            group_commitment.compress().as_bytes(),
+            public_key.compress().as_bytes(),
+            message,
+        ],
+    )
+}
+
+/// Oracle result as signed by the committee
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OracleResult {
+    pub event_id: String,
+    pub aggregate_value: i64,
+    pub threshold: i64,
+    pub result: String,
+    /// Ids of the decryptors that produced the result
+    pub decryptor_set: Vec<u32>,
+}
+
+impl OracleResult {
+    /// Domain label, then each field length-prefixed; ids sorted ascending
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let mut ids = self.decryptor_set.clone();
+        ids.sort_unstable();
+
+        let mut bytes = ORACLE_RESULT_LABEL.to_vec();
+        for field in [self.event_id.as_bytes(), self.result.as_bytes()] {
+            bytes.extend_from_slice(&(field.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(field);
+        }
+        bytes.extend_from_slice(&self.aggregate_value.to_be_bytes());
+        bytes.extend_from_slice(&self.threshold.to_be_bytes());
+        bytes.extend_from_slice(&(ids.len() as u32).to_be_bytes());
+        for id in ids {
+            bytes.extend_from_slice(&id.to_be_bytes());
+        }
+        bytes
+    }
+}
+
+/// Schnorr signature (R, z) with z·G = R + H(R, Y, m)·Y
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Signature {
+    pub r: RistrettoPoint,
+    pub z: Scalar25519,
+}
+
+impl Signature {
+    /// R (compressed) ‖ z, 64 bytes
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(self.r.compress().as_bytes());
+        bytes[32..].copy_from_slice(&self.z.to_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8; 64]) -> Result<Self, String> {
+        let r = CompressedRistretto::from_slice(&bytes[..32])
+            .ok()
+            .and_then(|c| c.decompress())
+            .ok_or("Signature R is not a valid point")?;
+        let z: [u8; 32] = bytes[32..].try_into().unwrap();
+        let z = Option::<Scalar>::from(Scalar::from_canonical_bytes(z))
+            .ok_or("Signature z is not a canonical scalar")?;
+        Ok(Signature {
+            r,
+            z: Scalar25519::new(z),
+        })
+    }
+
+    pub fn to_hex(&self) -> String {
+        let hex: String = self.to_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+        format!("0x{}", hex)
+    }
+}
+
+/// Verify a signature under the group public key
+pub fn verify_signature(public_key: &RistrettoPoint, message: &[u8], signature: &Signature) -> bool {
+    let c = challenge(&signature.r, public_key, message);
+    RISTRETTO_BASEPOINT_POINT * signature.z.scalar() == signature.r + public_key * c
+}
+
+/// Verify a committee signature over an oracle result
+pub fn verify_oracle_result(public_key: &RistrettoPoint, result: &OracleResult, signature: &Signature) -> bool {
+    verify_signature(public_key, &result.canonical_bytes(), signature)
+}
+
+/// Single-party Schnorr key, e.g. the coordinator's or a decryptor's
+/// identity; never a threshold key share
+pub struct SigningKey {
+    secret: Secret<Scalar25519>,
+    public_key: RistrettoPoint,
+}
+
+impl SigningKey {
+    pub fn generate<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self::from_secret(Secret::new(Scalar25519::random(rng)))
+    }
+
+    pub fn from_secret(secret: Secret<Scalar25519>) -> Self {
+        let public_key = RISTRETTO_BASEPOINT_POINT * secret.expose().scalar();
+        SigningKey { secret, public_key }
+    }
+
+    /// For sealing the key at rest
+    pub(crate) fn secret(&self) -> &Secret<Scalar25519> {
+        &self.secret
+    }
+
+    pub fn public_key(&self) -> RistrettoPoint {
+        self.public_key
+    }
+
+    /// z = k + H(R, Y, m)·x with R = k·G; verifies with [`verify_signature`]
+    pub fn sign<R: RngCore + CryptoRng>(&self, message: &[u8], rng: &mut R) -> Signature {
+        let mut k = Scalar25519::random(rng);
+        let r = RISTRETTO_BASEPOINT_POINT * k.scalar();
+        let c = challenge(&r, &self.public_key, message);
+        let z = k + Scalar25519::new(c) * *self.secret.expose();
+        k.zeroize();
+        Signature { r, z }
+    }
+}
+
+impl fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SigningKey")
+            .field("secret", &self.secret)
+            .field("public_key", &self.public_key.compress())
+            .finish()
+    }
+}
+
+/// Round-1 secret nonces (dᵢ, eᵢ); consumed by [`sign_share`] so they
+/// cannot be reused
+pub struct SigningNonces {
+    hiding: Scalar25519,
+    binding: Scalar25519,
+}
+
+impl Zeroize for SigningNonces {
+    fn zeroize(&mut self) {
+        self.hiding.zeroize();
+        self.binding.zeroize();
+    }
+}
+
+impl Drop for SigningNonces {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SigningNonces {}
+
+/// Round-1 public commitments (Dᵢ, Eᵢ)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SigningCommitments {
+    pub id: u32,
+    pub hiding: RistrettoPoint,
+    pub binding: RistrettoPoint,
+}
+
+/// Round 1: fresh nonces and their commitments
+pub fn commit<R: RngCore + CryptoRng>(id: u32, rng: &mut R) -> (SigningNonces, SigningCommitments) {
+    let nonces = SigningNonces {
+        hiding: Scalar25519::random(rng),
+        binding: Scalar25519::random(rng),
+    };
+    let commitments = SigningCommitments {
+        id,
+        hiding: RISTRETTO_BASEPOINT_POINT * nonces.hiding.scalar(),
+        binding: RISTRETTO_BASEPOINT_POINT * nonces.binding.scalar(),
+    };
+    (nonces, commitments)
+}
+
+/// Signing round state shared by signers and aggregator: ρᵢ, λᵢ, R and c
+struct SigningPackage {
+    binding_factors: BTreeMap<u32, Scalar>,
+    lagrange: BTreeMap<u32, Scalar>,
+    group_commitment: RistrettoPoint,
+    challenge: Scalar,
+}
+
+impl SigningPackage {
+    fn new(public_key: &RistrettoPoint, message: &[u8], commitments: &[SigningCommitments]) -> Result<Self, String> {
+        let sorted: BTreeMap<u32, &SigningCommitments> = commitments.iter().map(|c| (c.id, c)).collect();
+        if sorted.len() != commitments.len() {
+            return Err("Duplicate signer commitments".to_string());
+        }
+
+        let mut encoded = Vec::with_capacity(68 * sorted.len());
+        for c in sorted.values() {
+            encoded.extend_from_slice(&c.id.to_be_bytes());
+            encoded.extend_from_slice(c.hiding.compress().as_bytes());
+            encoded.extend_from_slice(c.binding.compress().as_bytes());
+        }
+        let y = public_key.compress();
+        let binding_factors: BTreeMap<u32, Scalar> = sorted
+            .keys()
+            .map(|&id| {
+                let rho = hash_to_scalar(BINDING_LABEL, &[y.as_bytes(), message, &encoded, &id.to_be_bytes()]);
+                (id, rho)
+            })
+            .collect();
+
+        let group_commitment = sorted
+            .values()
+            .map(|c| c.hiding + c.binding * binding_factors[&c.id])
+            .sum();
+
+        let xs: Vec<Scalar25519> = sorted.keys().map(|&id| Scalar25519::from(id)).collect();
+        let lagrange = sorted
+            .keys()
+            .copied()
+            .zip(lagrange_coefficients(&xs, Scalar25519::ZERO)?.into_iter().map(|l| l.scalar()))
+            .collect();
+
+        Ok(SigningPackage {
+            binding_factors,
+            lagrange,
+            challenge: challenge(&group_commitment, public_key, message),
+            group_commitment,
+        })
+    }
+}
+
+/// Signer i's response zᵢ
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignatureShare {
+    pub id: u32,
+    pub z: Scalar25519,
+}
+
+/// Round 2: zᵢ = dᵢ + ρᵢ·eᵢ + λᵢ·xᵢ·c
+pub fn sign_share(
+    key: &DkgOutput,
+    nonces: SigningNonces,
+    message: &[u8],
+    commitments: &[SigningCommitments],
+) -> Result<SignatureShare, String> {
+    let package = SigningPackage::new(&key.public_key, message, commitments)?;
+    let (rho, lambda) = match (package.binding_factors.get(&key.id), package.lagrange.get(&key.id)) {
+        (Some(&rho), Some(&lambda)) => (rho, lambda),
+        _ => return Err(format!("Signer {} has no commitment in this round", key.id)),
+    };
+    let own = commitments.iter().find(|c| c.id == key.id);
+    let expected = (
+        RISTRETTO_BASEPOINT_POINT * nonces.hiding.scalar(),
+        RISTRETTO_BASEPOINT_POINT * nonces.binding.scalar(),
+    );
+    if own.map(|c| (c.hiding, c.binding)) != Some(expected) {
+        return Err(format!("Commitment for signer {} does not match its nonces", key.id));
+    }
+
+    let z = nonces.hiding.scalar()
+        + nonces.binding.scalar() * rho
+        + lambda * key.key_share.expose().scalar() * package.challenge;
+    Ok(SignatureShare {
+        id: key.id,
+        z: Scalar25519::new(z),
+    })
+}
+
+/// Check every share, then aggregate; invalid shares are reported by id
+pub fn aggregate(
+    public_key: &RistrettoPoint,
+    message: &[u8],
+    commitments: &[SigningCommitments],
+    shares: &[SignatureShare],
+    verification_shares: &HashMap<u32, RistrettoPoint>,
+) -> Result<Signature, String> {
+    let package = SigningPackage::new(public_key, message, commitments)?;
+    let by_id: HashMap<u32, &SigningCommitments> = commitments.iter().map(|c| (c.id, c)).collect();
+
+    let mut invalid: Vec<u32> = Vec::new();
+    for &id in by_id.keys() {
+        let share = shares.iter().find(|s| s.id == id);
+        let valid = match (share, verification_shares.get(&id)) {
+            (Some(share), Some(y)) => {
+                let c = by_id[&id];
+                let expected = c.hiding
+                    + c.binding * package.binding_factors[&id]
+                    + y * (package.challenge * package.lagrange[&id]);
+                RISTRETTO_BASEPOINT_POINT * share.z.scalar() == expected
+            }
+            _ => false,
+        };
+        if !valid {
+            invalid.push(id);
+        }
+    }
+    if !invalid.is_empty() {
+        invalid.sort_unstable();
+        return Err(format!("Invalid signature shares from {:?}", invalid));
+    }
+
+    let z = by_id
+        .keys()
+        .map(|id| shares.iter().find(|s| s.id == *id).unwrap().z)
+        .sum();
+    Ok(Signature {
+        r: package.group_commitment,
+        z,
+    })
+}
+
+/// Decryptor committee holding a DKG key for signing
+///
+/// Runs both rounds in one process, the way `run_dkg` runs the DKG.
+#[derive(Debug)]
+pub struct SigningCommittee {
+    pub threshold: u32,
+    pub public_key: RistrettoPoint,
+    pub verification_shares: HashMap<u32, RistrettoPoint>,
+    keys: HashMap<u32, DkgOutput>,
+}
+
+impl SigningCommittee {
+    /// Run a DKG among decryptors 1..=n with the config's threshold
+    pub fn generate<R: RngCore + CryptoRng>(config: &ThresholdConfig, rng: &mut R) -> Result<Self, String> {
+        config.validate_for_dkg()?;
+        let ids: Vec<u32> = (1..=config.total_shares).collect();
+        let keys = run_dkg(&ids, config.threshold, &HashMap::new(), rng)?;
+        Self::from_dkg(config.threshold, keys)
+    }
+
+    pub fn from_dkg(threshold: u32, keys: HashMap<u32, DkgOutput>) -> Result<Self, String> {
+        let public_key = keys
+            .values()
+            .next()
+            .map(|k| k.public_key)
+            .ok_or("Empty committee")?;
+        Ok(SigningCommittee {
+            threshold,
+            public_key,
+            verification_shares: keys.iter().map(|(&id, k)| (id, k.verification_share())).collect(),
+            keys,
+        })
+    }
+
+    /// Both FROST rounds among `signers`, who must number at least k
+    pub fn sign<R: RngCore + CryptoRng>(
+        &self,
+        signers: &[u32],
+        message: &[u8],
+        rng: &mut R,
+    ) -> Result<Signature, String> {
+        if signers.len() < self.threshold as usize {
+            return Err(format!(
+                "Need {} signers, only have {}",
+                self.threshold,
+                signers.len()
+            ));
+        }
+        let mut round_one = Vec::with_capacity(signers.len());
+        for &id in signers {
+            if !self.keys.contains_key(&id) {
+                return Err(format!("Unknown signer {}", id));
+            }
+            round_one.push(commit(id, rng));
+        }
+        let commitments: Vec<SigningCommitments> = round_one.iter().map(|(_, c)| *c).collect();
+        let shares = round_one
+            .into_iter()
+            .map(|(nonces, c)| sign_share(&self.keys[&c.id], nonces, message, &commitments))
+            .collect::<Result<Vec<_>, String>>()?;
+        aggregate(&self.public_key, message, &commitments, &shares, &self.verification_shares)
+    }
+
+    pub fn sign_oracle_result<R: RngCore + CryptoRng>(
+        &self,
+        signers: &[u32],
+        result: &OracleResult,
+        rng: &mut R,
+    ) -> Result<Signature, String> {
+        self.sign(signers, &result.canonical_bytes(), rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    fn committee(seed: u64) -> (SigningCommittee, ChaCha20Rng) {
+        let mut rng = ChaCha20Rng::seed_from_u64(seed);
+        let committee = SigningCommittee::generate(&ThresholdConfig::new_3_of_5(), &mut rng).unwrap();
+        (committee, rng)
+    }
+
+    fn oracle_result() -> OracleResult {
+        OracleResult {
+            event_id: "0xabc123".to_string(),
+            aggregate_value: 1_800_000,
+            threshold: 5500,
+            result: "YES".to_string(),
+            decryptor_set: vec![1, 3, 5],
+        }
+    }
+
+    #[test]
+    fn test_any_quorum_signs_under_the_group_key() {
+        let (committee, mut rng) = committee(1);
+        let result = oracle_result();
+        for signers in [[1, 2, 3], [5, 3, 1], [2, 4, 5]] {
+            let signature = committee.sign_oracle_result(&signers, &result, &mut rng).unwrap();
+            assert!(verify_oracle_result(&committee.public_key, &result, &signature));
+        }
+        let all = committee.sign(&[1, 2, 3, 4, 5], b"msg", &mut rng).unwrap();
+        assert!(verify_signature(&committee.public_key, b"msg", &all));
+    }
+
+    #[test]
+    fn test_single_party_signing_key() {
+        let mut rng = ChaCha20Rng::seed_from_u64(9);
+        let key = SigningKey::generate(&mut rng);
+        let signature = key.sign(b"decrypt round 1", &mut rng);
+        assert!(verify_signature(&key.public_key(), b"decrypt round 1", &signature));
+        assert!(!verify_signature(&key.public_key(), b"decrypt round 2", &signature));
+        assert!(format!("{:?}", key).contains("REDACTED"));
+    }
+
+    #[test]
+    fn test_signature_binds_every_field() {
+        let (committee, mut rng) = committee(2);
+        let result = oracle_result();
+        let signature = committee.sign_oracle_result(&[1, 2, 4], &result, &mut rng).unwrap();
+
+        let mut flipped = result.clone();
+        flipped.result = "NO".to_string();
+        assert!(!verify_oracle_result(&committee.public_key, &flipped, &signature));
+
+        let mut reordered = result.clone();
+        reordered.decryptor_set = vec![5, 1, 3];
+        assert!(verify_oracle_result(&committee.public_key, &reordered, &signature));
+
+        let (other, _) = super::tests::committee(3);
+        assert!(!verify_oracle_result(&other.public_key, &result, &signature));
+    }
+
+    #[test]
+    fn test_too_few_or_unknown_signers() {
+        let (committee, mut rng) = committee(4);
+        assert!(committee.sign(&[1, 2], b"msg", &mut rng).is_err());
+        assert!(committee.sign(&[1, 2, 9], b"msg", &mut rng).is_err());
+        assert!(committee.sign(&[1, 2, 2], b"msg", &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_aggregator_names_bad_signature_shares() {
+        let (committee, mut rng) = committee(5);
+        let signers = [1, 2, 3, 4];
+        let round_one: Vec<_> = signers.iter().map(|&id| commit(id, &mut rng)).collect();
+        let commitments: Vec<SigningCommitments> = round_one.iter().map(|(_, c)| *c).collect();
+        let mut shares: Vec<SignatureShare> = round_one
+            .into_iter()
+            .map(|(nonces, c)| sign_share(&committee.keys[&c.id], nonces, b"msg", &commitments).unwrap())
+            .collect();
+        shares[2].z += Scalar25519::ONE;
+
+        let err = aggregate(&committee.public_key, b"msg", &commitments, &shares, &committee.verification_shares)
+            .unwrap_err();
+        assert!(err.contains("[3]"));
+    }
+
+    #[test]
+    fn test_signature_bytes_round_trip() {
+        let (committee, mut rng) = committee(6);
+        let signature = committee.sign(&[2, 3, 4], b"msg", &mut rng).unwrap();
+        let parsed = Signature::from_bytes(&signature.to_bytes()).unwrap();
+        assert_eq!(parsed, signature);
+        assert!(signature.to_hex().starts_with("0x"));
+        assert_eq!(signature.to_hex().len(), 130);
+
+        let mut bad = signature.to_bytes();
+        bad[63] = 0xff;
+        assert!(Signature::from_bytes(&bad).is_err());
+    }
+}
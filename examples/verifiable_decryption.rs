@@ -0,0 +1,432 @@
+//! Verifiable Threshold Decryption
+//!
+//! Threshold ElGamal over Ristretto255 under a DKG key Y = x·G. Decryptor i
+//! holds xᵢ with public verification share Yᵢ = xᵢ·G and answers a
+//! ciphertext (C₁, C₂) with Dᵢ = xᵢ·C₁ plus a Chaum–Pedersen proof that
+//! log_G Yᵢ = log_C₁ Dᵢ, made non-interactive with Fiat–Shamir.
+//!
+//! The combiner checks every proof first, rejects and names the decryptors
+//! whose proofs fail, and interpolates x·C₁ = ∑ λᵢ·Dᵢ from valid ones only.
+//!
+//! This covers ElGamal reveals. LWE partial decryptions, which
+//! `crate::coordinator` collects, need a lattice proof system instead;
+//! `crate::threshold_lwe` cross-checks quorums, and Shamir
+//! `crate::threshold_decryption` decodes redundant shares. Both find wrong
+//! contributions without proofs.
+
+use crate::dkg::DkgOutput;
+use crate::field::{lagrange_coefficients, PrimeField, Scalar25519};
+use crate::secret::Secret;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::Identity;
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha512};
+use std::collections::{HashMap, HashSet};
+use std::ops::Add;
+
+/// Fiat–Shamir domain separation label
+const DLEQ_LABEL: &[u8] = b"blocksense-fhe-oracle/dleq/v1";
+
+/// Chaum–Pedersen proof that log_g₁ h₁ = log_g₂ h₂
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DleqProof {
+    pub challenge: Scalar25519,
+    pub response: Scalar25519,
+}
+
+fn dleq_challenge(context: &[u8], points: [&RistrettoPoint; 6]) -> Scalar25519 {
+    let mut hasher = Sha512::new();
+    hasher.update(DLEQ_LABEL);
+    hasher.update((context.len() as u64).to_le_bytes());
+    hasher.update(context);
+    for point in points {
+        hasher.update(point.compress().as_bytes());
+    }
+    Scalar25519::new(Scalar::from_hash(hasher))
+}
+
+impl DleqProof {
+    /// Prove knowledge of x with h₁ = x·g₁ and h₂ = x·g₂
+    ///
+    /// `context` is bound into the challenge, e.g. the prover id.
+    pub fn prove<R: RngCore + CryptoRng>(
+        x: Scalar25519,
+        g1: &RistrettoPoint,
+        g2: &RistrettoPoint,
+        context: &[u8],
+        rng: &mut R,
+    ) -> Self {
+        let (h1, h2) = (g1 * x.scalar(), g2 * x.scalar());
+        let k = Scalar25519::random(rng);
+        let (a1, a2) = (g1 * k.scalar(), g2 * k.scalar());
+        let challenge = dleq_challenge(context, [g1, &h1, g2, &h2, &a1, &a2]);
+        DleqProof {
+            challenge,
+            response: k - challenge * x,
+        }
+    }
+
+    /// a₁ = z·g₁ + c·h₁, a₂ = z·g₂ + c·h₂, then recompute c
+    pub fn verify(
+        &self,
+        g1: &RistrettoPoint,
+        h1: &RistrettoPoint,
+        g2: &RistrettoPoint,
+        h2: &RistrettoPoint,
+        context: &[u8],
+    ) -> bool {
+        let (z, c) = (self.response.scalar(), self.challenge.scalar());
+        let a1 = g1 * z + h1 * c;
+        let a2 = g2 * z + h2 * c;
+        dleq_challenge(context, [g1, h1, g2, h2, &a1, &a2]) == self.challenge
+    }
+}
+
+/// ElGamal ciphertext (C₁, C₂) = (r·G, m·G + r·Y)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ElGamalCiphertext {
+    pub c1: RistrettoPoint,
+    pub c2: RistrettoPoint,
+}
+
+impl ElGamalCiphertext {
+    /// C₁ ‖ C₂ compressed, 64 bytes
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(self.c1.compress().as_bytes());
+        bytes[32..].copy_from_slice(self.c2.compress().as_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() != 64 {
+            return Err(format!("ElGamal ciphertext must be 64 bytes, got {}", bytes.len()));
+        }
+        let point = |half: &[u8]| {
+            CompressedRistretto::from_slice(half)
+                .ok()
+                .and_then(|c| c.decompress())
+                .ok_or_else(|| "Ciphertext component is not a valid point".to_string())
+        };
+        Ok(ElGamalCiphertext {
+            c1: point(&bytes[..32])?,
+            c2: point(&bytes[32..])?,
+        })
+    }
+
+    /// Encrypt a small integer in the exponent; decrypting needs a
+    /// discrete log, so keep plaintexts within [`discrete_log`]'s bound
+    pub fn encrypt<R: RngCore + CryptoRng>(public_key: &RistrettoPoint, plaintext: u64, rng: &mut R) -> Self {
+        let r = Scalar25519::random(rng).scalar();
+        ElGamalCiphertext {
+            c1: RISTRETTO_BASEPOINT_POINT * r,
+            c2: RISTRETTO_BASEPOINT_POINT * Scalar::from(plaintext) + public_key * r,
+        }
+    }
+
+    /// Homomorphic k·m: (k·C₁, k·C₂)
+    pub fn scale(&self, k: u64) -> Self {
+        let k = Scalar::from(k);
+        ElGamalCiphertext {
+            c1: self.c1 * k,
+            c2: self.c2 * k,
+        }
+    }
+}
+
+/// Homomorphic m₁ + m₂: (C₁ + C₁', C₂ + C₂')
+impl Add for ElGamalCiphertext {
+    type Output = ElGamalCiphertext;
+
+    fn add(self, other: ElGamalCiphertext) -> ElGamalCiphertext {
+        ElGamalCiphertext {
+            c1: self.c1 + other.c1,
+            c2: self.c2 + other.c2,
+        }
+    }
+}
+
+/// Decryptor i's share Dᵢ = xᵢ·C₁ with its proof
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerifiablePartialDecryption {
+    pub id: u32,
+    pub share: RistrettoPoint,
+    pub proof: DleqProof,
+}
+
+impl VerifiablePartialDecryption {
+    /// id (big-endian) ‖ Dᵢ ‖ challenge ‖ response, 100 bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(100);
+        bytes.extend_from_slice(&self.id.to_be_bytes());
+        bytes.extend_from_slice(self.share.compress().as_bytes());
+        bytes.extend_from_slice(&self.proof.challenge.to_bytes());
+        bytes.extend_from_slice(&self.proof.response.to_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() != 100 {
+            return Err(format!("Partial decryption must be 100 bytes, got {}", bytes.len()));
+        }
+        let share = CompressedRistretto::from_slice(&bytes[4..36])
+            .ok()
+            .and_then(|c| c.decompress())
+            .ok_or("Partial decryption share is not a valid point")?;
+        let scalar = |range: std::ops::Range<usize>| {
+            let array: [u8; 32] = bytes[range].try_into().unwrap();
+            Option::<Scalar>::from(Scalar::from_canonical_bytes(array))
+                .map(Scalar25519::new)
+                .ok_or_else(|| "Proof scalar is not canonical".to_string())
+        };
+        Ok(VerifiablePartialDecryption {
+            id: u32::from_be_bytes(bytes[..4].try_into().unwrap()),
+            share,
+            proof: DleqProof {
+                challenge: scalar(36..68)?,
+                response: scalar(68..100)?,
+            },
+        })
+    }
+
+    /// Check log_G Yᵢ = log_C₁ Dᵢ against the published verification share
+    pub fn verify(&self, ct: &ElGamalCiphertext, verification_share: &RistrettoPoint) -> bool {
+        self.proof.verify(
+            &RISTRETTO_BASEPOINT_POINT,
+            verification_share,
+            &ct.c1,
+            &self.share,
+            &self.id.to_le_bytes(),
+        )
+    }
+}
+
+/// Partial decryption by the holder of a DKG key share
+pub fn partial_decrypt<R: RngCore + CryptoRng>(
+    output: &DkgOutput,
+    ct: &ElGamalCiphertext,
+    rng: &mut R,
+) -> VerifiablePartialDecryption {
+    partial_decrypt_with_share(output.id, &output.key_share, ct, rng)
+}
+
+/// Partial decryption by decryptor `id` holding `key_share` xᵢ
+pub fn partial_decrypt_with_share<R: RngCore + CryptoRng>(
+    id: u32,
+    key_share: &Secret<Scalar25519>,
+    ct: &ElGamalCiphertext,
+    rng: &mut R,
+) -> VerifiablePartialDecryption {
+    VerifiablePartialDecryption {
+        id,
+        share: ct.c1 * key_share.expose().scalar(),
+        proof: DleqProof::prove(*key_share.expose(), &RISTRETTO_BASEPOINT_POINT, &ct.c1, &id.to_le_bytes(), rng),
+    }
+}
+
+/// Combined decryption with the decryptors whose contributions were dropped
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifiedDecryption {
+    /// m·G; see [`discrete_log`] to recover m
+    pub plaintext_point: RistrettoPoint,
+    /// Ids with an invalid proof, an unknown verification share or a
+    /// duplicate submission (sorted)
+    pub rejected: Vec<u32>,
+}
+
+/// Verify every partial, then interpolate from the first `threshold` valid ones
+pub fn combine_verified(
+    ct: &ElGamalCiphertext,
+    partials: &[VerifiablePartialDecryption],
+    verification_shares: &HashMap<u32, RistrettoPoint>,
+    threshold: u32,
+) -> Result<VerifiedDecryption, String> {
+    let mut seen = HashSet::new();
+    let mut rejected = Vec::new();
+    let mut valid = Vec::new();
+    for partial in partials {
+        if !seen.insert(partial.id) {
+            rejected.push(partial.id);
+            continue;
+        }
+        match verification_shares.get(&partial.id) {
+            Some(y) if partial.verify(ct, y) => valid.push(partial),
+            _ => rejected.push(partial.id),
+        }
+    }
+    rejected.sort_unstable();
+    rejected.dedup();
+    valid.retain(|p| !rejected.contains(&p.id));
+
+    if valid.len() < threshold as usize {
+        return Err(format!(
+            "Need {} valid partial decryptions, have {} (rejected: {:?})",
+            threshold,
+            valid.len(),
+            rejected
+        ));
+    }
+    valid.truncate(threshold as usize);
+
+    let xs: Vec<Scalar25519> = valid.iter().map(|p| Scalar25519::from(p.id)).collect();
+    let lambdas = lagrange_coefficients(&xs, Scalar25519::ZERO)?;
+    let masked: RistrettoPoint = valid
+        .iter()
+        .zip(lambdas)
+        .map(|(p, lambda)| p.share * lambda.scalar())
+        .sum();
+
+    Ok(VerifiedDecryption {
+        plaintext_point: ct.c2 - masked,
+        rejected,
+    })
+}
+
+/// Baby-step giant-step search for m < bound with m·G = `point`
+pub fn discrete_log(point: &RistrettoPoint, bound: u64) -> Option<u64> {
+    let step = (bound as f64).sqrt().ceil().max(1.0) as u64;
+    let mut baby = HashMap::with_capacity(step as usize);
+    let mut current = RistrettoPoint::identity();
+    for j in 0..step {
+        baby.insert(current.compress().to_bytes(), j);
+        current += RISTRETTO_BASEPOINT_POINT;
+    }
+
+    let giant = -(RISTRETTO_BASEPOINT_POINT * Scalar::from(step));
+    let mut gamma = *point;
+    for i in 0..step {
+        if let Some(&j) = baby.get(gamma.compress().as_bytes()) {
+            let m = i * step + j;
+            return (m < bound).then_some(m);
+        }
+        gamma += giant;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dkg::run_dkg;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    fn setup(seed: u64) -> (HashMap<u32, DkgOutput>, HashMap<u32, RistrettoPoint>, ChaCha20Rng) {
+        let mut rng = ChaCha20Rng::seed_from_u64(seed);
+        let outputs = run_dkg(&[1, 2, 3, 4, 5], 3, &HashMap::new(), &mut rng).unwrap();
+        let shares = outputs
+            .iter()
+            .map(|(&id, output)| (id, output.verification_share()))
+            .collect();
+        (outputs, shares, rng)
+    }
+
+    #[test]
+    fn test_dleq_proof_round_trip() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let x = Scalar25519::random(&mut rng);
+        let g2 = RISTRETTO_BASEPOINT_POINT * Scalar25519::random(&mut rng).scalar();
+        let (h1, h2) = (RISTRETTO_BASEPOINT_POINT * x.scalar(), g2 * x.scalar());
+        let proof = DleqProof::prove(x, &RISTRETTO_BASEPOINT_POINT, &g2, b"ctx", &mut rng);
+
+        assert!(proof.verify(&RISTRETTO_BASEPOINT_POINT, &h1, &g2, &h2, b"ctx"));
+        assert!(!proof.verify(&RISTRETTO_BASEPOINT_POINT, &h1, &g2, &h2, b"other"));
+        assert!(!proof.verify(&RISTRETTO_BASEPOINT_POINT, &h1, &g2, &(h2 + g2), b"ctx"));
+    }
+
+    #[test]
+    fn test_threshold_decryption_with_valid_proofs() {
+        let (outputs, shares, mut rng) = setup(2);
+        let ct = ElGamalCiphertext::encrypt(&outputs[&1].public_key, 6357, &mut rng);
+
+        let partials: Vec<_> = [5, 2, 4]
+            .iter()
+            .map(|id| partial_decrypt(&outputs[id], &ct, &mut rng))
+            .collect();
+        let result = combine_verified(&ct, &partials, &shares, 3).unwrap();
+
+        assert!(result.rejected.is_empty());
+        assert_eq!(discrete_log(&result.plaintext_point, 10_000), Some(6357));
+    }
+
+    #[test]
+    fn test_combiner_rejects_and_names_bad_decryptors() {
+        let (outputs, shares, mut rng) = setup(3);
+        let ct = ElGamalCiphertext::encrypt(&outputs[&1].public_key, 42, &mut rng);
+        let mut partials: Vec<_> = [1, 2, 3, 4, 5]
+            .iter()
+            .map(|id| partial_decrypt(&outputs[id], &ct, &mut rng))
+            .collect();
+
+        // Decryptor 2 returns a wrong share with its honest proof
+        partials[1].share += RISTRETTO_BASEPOINT_POINT;
+        // Decryptor 4 replays decryptor 3's share and proof under its own id
+        partials[3] = VerifiablePartialDecryption { id: 4, ..partials[2] };
+
+        let result = combine_verified(&ct, &partials, &shares, 3).unwrap();
+        assert_eq!(result.rejected, vec![2, 4]);
+        assert_eq!(discrete_log(&result.plaintext_point, 100), Some(42));
+
+        // Without enough honest decryptors the combiner refuses
+        let err = combine_verified(&ct, &partials[..4], &shares, 3).unwrap_err();
+        assert!(err.contains("[2, 4]"));
+    }
+
+    #[test]
+    fn test_proof_is_bound_to_the_ciphertext() {
+        let (outputs, shares, mut rng) = setup(4);
+        let ct = ElGamalCiphertext::encrypt(&outputs[&1].public_key, 1, &mut rng);
+        let other = ElGamalCiphertext::encrypt(&outputs[&1].public_key, 1, &mut rng);
+
+        let partial = partial_decrypt(&outputs[&3], &ct, &mut rng);
+        assert!(partial.verify(&ct, &shares[&3]));
+        assert!(!partial.verify(&other, &shares[&3]));
+        assert!(!partial.verify(&ct, &shares[&2]));
+    }
+
+    #[test]
+    fn test_duplicate_and_unknown_ids_are_rejected() {
+        let (outputs, mut shares, mut rng) = setup(5);
+        let ct = ElGamalCiphertext::encrypt(&outputs[&1].public_key, 7, &mut rng);
+        let one = partial_decrypt(&outputs[&1], &ct, &mut rng);
+        let two = partial_decrypt(&outputs[&2], &ct, &mut rng);
+        let three = partial_decrypt(&outputs[&3], &ct, &mut rng);
+        shares.remove(&3);
+
+        let result = combine_verified(&ct, &[one, two, one, three], &shares, 2);
+        // 1 sent twice and 3 has no verification share; only 2 remains
+        assert!(result.unwrap_err().contains("[1, 3]"));
+    }
+
+    #[test]
+    fn test_ciphertexts_add_and_scale_homomorphically() {
+        let (outputs, shares, mut rng) = setup(7);
+        let y = outputs[&1].public_key;
+        let sum = ElGamalCiphertext::encrypt(&y, 30, &mut rng).scale(3) + ElGamalCiphertext::encrypt(&y, 8, &mut rng);
+        let partials: Vec<_> = [1, 2, 3].iter().map(|id| partial_decrypt(&outputs[id], &sum, &mut rng)).collect();
+        let decrypted = combine_verified(&sum, &partials, &shares, 3).unwrap();
+        assert_eq!(discrete_log(&decrypted.plaintext_point, 1000), Some(98));
+    }
+
+    #[test]
+    fn test_ciphertext_and_partial_encodings_round_trip() {
+        let (outputs, _, mut rng) = setup(6);
+        let ct = ElGamalCiphertext::encrypt(&outputs[&1].public_key, 77, &mut rng);
+        assert_eq!(ElGamalCiphertext::from_bytes(&ct.to_bytes()).unwrap(), ct);
+        assert!(ElGamalCiphertext::from_bytes(&[0xff; 64]).is_err());
+
+        let partial = partial_decrypt(&outputs[&2], &ct, &mut rng);
+        assert_eq!(VerifiablePartialDecryption::from_bytes(&partial.to_bytes()).unwrap(), partial);
+        assert!(VerifiablePartialDecryption::from_bytes(&partial.to_bytes()[..99]).is_err());
+    }
+
+    #[test]
+    fn test_discrete_log_bound() {
+        let point = RISTRETTO_BASEPOINT_POINT * Scalar::from(99u64);
+        assert_eq!(discrete_log(&point, 100), Some(99));
+        assert_eq!(discrete_log(&point, 99), None);
+        assert_eq!(discrete_log(&RistrettoPoint::identity(), 1), Some(0));
+    }
+}
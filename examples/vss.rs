@@ -0,0 +1,441 @@
+//! Verifiable Secret Sharing over Ristretto255
+//!
+//! Feldman VSS: the dealer publishes C_j = a_j·G for every coefficient of
+//! P(x) = a_0 + a_1·x + … + a_{k−1}·x^{k−1}. A holder of share s_i = P(i)
+//! checks s_i·G = ∑ C_j·i^j without learning any coefficient.
+//!
+//! Pedersen VSS: a second random polynomial R(x) blinds every coefficient,
+//! C_j = a_j·G + b_j·H, and each holder also receives r_i = R(i). The
+//! commitments are perfectly hiding, so publishing them on-chain reveals
+//! nothing about the secret even to an unbounded adversary.
+
+use crate::field::{PrimeField, Scalar25519};
+use crate::secret::REDACTED;
+use crate::shamir::Polynomial;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::traits::Identity;
+use rand::{CryptoRng, RngCore};
+use sha2::Sha512;
+use std::collections::HashMap;
+use std::fmt;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Domain label hashed to the Pedersen generator H
+const PEDERSEN_H_LABEL: &[u8] = b"blocksense-fhe-oracle/vss/pedersen-generator-H";
+
+/// Second generator H with no known discrete log relative to G
+pub fn pedersen_generator() -> RistrettoPoint {
+    RistrettoPoint::hash_from_bytes::<Sha512>(PEDERSEN_H_LABEL)
+}
+
+/// ∑ C_j·x^j over commitment points, by Horner's rule in the group
+fn evaluate_commitment(points: &[RistrettoPoint], id: u32) -> RistrettoPoint {
+    let x = Scalar25519::from(id).scalar();
+    points
+        .iter()
+        .rev()
+        .fold(RistrettoPoint::identity(), |acc, c| acc * x + c)
+}
+
+fn check_threshold(threshold: u32, total_shares: u32) -> Result<(), String> {
+    if threshold == 0 || threshold > total_shares {
+        return Err(format!(
+            "Invalid threshold {} for {} shares",
+            threshold, total_shares
+        ));
+    }
+    Ok(())
+}
+
+/// Feldman commitment to a dealer polynomial: C_j = a_j·G
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeldmanCommitment {
+    pub points: Vec<RistrettoPoint>,
+}
+
+impl FeldmanCommitment {
+    pub fn from_polynomial(polynomial: &Polynomial<Scalar25519>) -> Self {
+        FeldmanCommitment {
+            points: polynomial
+                .coefficients()
+                .iter()
+                .map(|a| RISTRETTO_BASEPOINT_POINT * a.scalar())
+                .collect(),
+        }
+    }
+
+    /// Number of shares needed to reconstruct (polynomial degree + 1)
+    pub fn threshold(&self) -> usize {
+        self.points.len()
+    }
+
+    /// C_0 = secret·G, the public key matching the shared secret
+    pub fn public_secret(&self) -> RistrettoPoint {
+        self.points
+            .first()
+            .copied()
+            .unwrap_or_else(RistrettoPoint::identity)
+    }
+
+    /// ∑ C_j·x^j = P(x)·G
+    pub fn share_commitment(&self, id: u32) -> RistrettoPoint {
+        evaluate_commitment(&self.points, id)
+    }
+
+    /// Check s_i·G = ∑ C_j·i^j
+    pub fn verify_share(&self, id: u32, share: Scalar25519) -> bool {
+        (RISTRETTO_BASEPOINT_POINT * share.scalar())
+            .ct_eq(&self.share_commitment(id))
+            .into()
+    }
+
+    /// Compressed 32-byte encodings, e.g. for publishing on-chain
+    pub fn to_bytes(&self) -> Vec<[u8; 32]> {
+        self.points.iter().map(|p| p.compress().to_bytes()).collect()
+    }
+}
+
+/// Deal k-of-n Feldman shares of `secret`
+///
+/// The dealer polynomial is zeroized before returning; only the shares and
+/// the public commitment leave this function.
+pub fn feldman_deal<R: RngCore + CryptoRng>(
+    secret: Scalar25519,
+    threshold: u32,
+    total_shares: u32,
+    rng: &mut R,
+) -> Result<(FeldmanCommitment, HashMap<u32, Scalar25519>), String> {
+    check_threshold(threshold, total_shares)?;
+
+    let polynomial = Polynomial::random(secret, threshold as usize - 1, rng);
+    let commitment = FeldmanCommitment::from_polynomial(&polynomial);
+    Ok((commitment, polynomial.shares(total_shares)))
+}
+
+/// Pedersen commitment to a dealer polynomial: C_j = a_j·G + b_j·H
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PedersenCommitment {
+    pub points: Vec<RistrettoPoint>,
+}
+
+impl PedersenCommitment {
+    /// Commit to P (secret polynomial) blinded by R; both must have equal degree
+    pub fn from_polynomials(
+        polynomial: &Polynomial<Scalar25519>,
+        blinding: &Polynomial<Scalar25519>,
+    ) -> Result<Self, String> {
+        if polynomial.degree() != blinding.degree() {
+            return Err(format!(
+                "Blinding polynomial degree {} does not match {}",
+                blinding.degree(),
+                polynomial.degree()
+            ));
+        }
+        let h = pedersen_generator();
+        Ok(PedersenCommitment {
+            points: polynomial
+                .coefficients()
+                .iter()
+                .zip(blinding.coefficients())
+                .map(|(a, b)| RISTRETTO_BASEPOINT_POINT * a.scalar() + h * b.scalar())
+                .collect(),
+        })
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.points.len()
+    }
+
+    /// ∑ C_j·x^j = P(x)·G + R(x)·H
+    pub fn share_commitment(&self, id: u32) -> RistrettoPoint {
+        evaluate_commitment(&self.points, id)
+    }
+
+    /// Check s_i·G + r_i·H = ∑ C_j·i^j
+    pub fn verify_share(&self, id: u32, share: Scalar25519, blinding: Scalar25519) -> bool {
+        (RISTRETTO_BASEPOINT_POINT * share.scalar() + pedersen_generator() * blinding.scalar())
+            .ct_eq(&self.share_commitment(id))
+            .into()
+    }
+
+    pub fn to_bytes(&self) -> Vec<[u8; 32]> {
+        self.points.iter().map(|p| p.compress().to_bytes()).collect()
+    }
+}
+
+/// Deal k-of-n Pedersen shares of `secret`; each share carries its blinding
+pub fn pedersen_deal<R: RngCore + CryptoRng>(
+    secret: Scalar25519,
+    threshold: u32,
+    total_shares: u32,
+    rng: &mut R,
+) -> Result<(PedersenCommitment, HashMap<u32, VssShare>), String> {
+    check_threshold(threshold, total_shares)?;
+
+    let degree = threshold as usize - 1;
+    let polynomial = Polynomial::random(secret, degree, rng);
+    let blinding = Polynomial::random(Scalar25519::random(rng), degree, rng);
+    let commitment = PedersenCommitment::from_polynomials(&polynomial, &blinding)?;
+
+    let shares = (1..=total_shares)
+        .map(|id| {
+            let x = Scalar25519::from(id);
+            (id, VssShare::pedersen(polynomial.evaluate(x), blinding.evaluate(x)))
+        })
+        .collect();
+    Ok((commitment, shares))
+}
+
+/// Which VSS flavour a dealing uses
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VssMode {
+    /// Computationally hiding, C_j = a_j·G
+    Feldman,
+    /// Perfectly hiding, C_j = a_j·G + b_j·H
+    Pedersen,
+}
+
+/// A share as received by its holder
+///
+/// Wiped on drop and redacted in `Debug`; copy it with `clone_secret`.
+#[derive(PartialEq, Eq)]
+pub struct VssShare {
+    /// s_i = P(i), the value used for reconstruction
+    pub value: Scalar25519,
+    /// r_i = R(i), present only in Pedersen mode
+    pub blinding: Option<Scalar25519>,
+}
+
+impl VssShare {
+    pub fn feldman(value: Scalar25519) -> Self {
+        VssShare { value, blinding: None }
+    }
+
+    pub fn pedersen(value: Scalar25519, blinding: Scalar25519) -> Self {
+        VssShare {
+            value,
+            blinding: Some(blinding),
+        }
+    }
+
+    /// Deliberate copy of the share
+    pub fn clone_secret(&self) -> Self {
+        VssShare {
+            value: self.value,
+            blinding: self.blinding,
+        }
+    }
+}
+
+impl fmt::Debug for VssShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VssShare")
+            .field("value", &REDACTED)
+            .field("blinded", &self.blinding.is_some())
+            .finish()
+    }
+}
+
+impl Zeroize for VssShare {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+        if let Some(blinding) = self.blinding.as_mut() {
+            blinding.zeroize();
+        }
+    }
+}
+
+impl Drop for VssShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for VssShare {}
+
+impl From<Scalar25519> for VssShare {
+    fn from(value: Scalar25519) -> Self {
+        VssShare::feldman(value)
+    }
+}
+
+/// Public commitment of either VSS flavour
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VssCommitment {
+    Feldman(FeldmanCommitment),
+    Pedersen(PedersenCommitment),
+}
+
+impl VssCommitment {
+    pub fn mode(&self) -> VssMode {
+        match self {
+            VssCommitment::Feldman(_) => VssMode::Feldman,
+            VssCommitment::Pedersen(_) => VssMode::Pedersen,
+        }
+    }
+
+    pub fn threshold(&self) -> usize {
+        match self {
+            VssCommitment::Feldman(c) => c.threshold(),
+            VssCommitment::Pedersen(c) => c.threshold(),
+        }
+    }
+
+    /// Verify a share; a share whose blinding does not match the mode fails
+    pub fn verify_share(&self, id: u32, share: &VssShare) -> bool {
+        match (self, share.blinding) {
+            (VssCommitment::Feldman(c), None) => c.verify_share(id, share.value),
+            (VssCommitment::Pedersen(c), Some(blinding)) => c.verify_share(id, share.value, blinding),
+            _ => false,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<[u8; 32]> {
+        match self {
+            VssCommitment::Feldman(c) => c.to_bytes(),
+            VssCommitment::Pedersen(c) => c.to_bytes(),
+        }
+    }
+}
+
+/// Deal k-of-n shares of `secret` in the given mode
+pub fn vss_deal<R: RngCore + CryptoRng>(
+    mode: VssMode,
+    secret: Scalar25519,
+    threshold: u32,
+    total_shares: u32,
+    rng: &mut R,
+) -> Result<(VssCommitment, HashMap<u32, VssShare>), String> {
+    match mode {
+        VssMode::Feldman => {
+            let (commitment, shares) = feldman_deal(secret, threshold, total_shares, rng)?;
+            let shares = shares
+                .into_iter()
+                .map(|(id, value)| (id, VssShare::feldman(value)))
+                .collect();
+            Ok((VssCommitment::Feldman(commitment), shares))
+        }
+        VssMode::Pedersen => {
+            let (commitment, shares) = pedersen_deal(secret, threshold, total_shares, rng)?;
+            Ok((VssCommitment::Pedersen(commitment), shares))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shamir::threshold_decrypt_modular;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    fn s(value: u64) -> Scalar25519 {
+        Scalar25519::from(value)
+    }
+
+    #[test]
+    fn test_feldman_verifies_linear_polynomial() {
+        // P(x) = 42 + 10x
+        let polynomial = Polynomial::from_coefficients(vec![s(42), s(10)]);
+        let commitment = FeldmanCommitment::from_polynomial(&polynomial);
+
+        assert!(commitment.verify_share(1, s(52)));
+        assert!(commitment.verify_share(3, s(72)));
+        assert!(!commitment.verify_share(1, s(50)));
+        assert!(!commitment.verify_share(2, s(52)));
+        assert_eq!(commitment.public_secret(), RISTRETTO_BASEPOINT_POINT * s(42).scalar());
+    }
+
+    #[test]
+    fn test_feldman_deal_shares_verify_and_reconstruct() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let secret = Scalar25519::random(&mut rng);
+        let (commitment, shares) = feldman_deal(secret, 3, 5, &mut rng).unwrap();
+
+        assert_eq!(commitment.threshold(), 3);
+        for (&id, &share) in &shares {
+            assert!(commitment.verify_share(id, share), "share {} rejected", id);
+            assert!(!commitment.verify_share(id, share + Scalar25519::ONE));
+        }
+        assert_eq!(threshold_decrypt_modular(&shares, 3), Some(secret));
+    }
+
+    #[test]
+    fn test_feldman_commitment_does_not_expose_coefficients() {
+        let mut rng = ChaCha20Rng::seed_from_u64(2);
+        let (commitment, _) = feldman_deal(s(42), 3, 5, &mut rng).unwrap();
+
+        // Only compressed group elements are published, not the scalar 42
+        let secret_bytes = s(42).to_bytes();
+        assert!(commitment.to_bytes().iter().all(|b| *b != secret_bytes));
+    }
+
+    #[test]
+    fn test_feldman_deal_rejects_invalid_threshold() {
+        let mut rng = ChaCha20Rng::seed_from_u64(3);
+        assert!(feldman_deal(s(1), 0, 5, &mut rng).is_err());
+        assert!(feldman_deal(s(1), 6, 5, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_pedersen_deal_shares_verify_and_reconstruct() {
+        let mut rng = ChaCha20Rng::seed_from_u64(4);
+        let secret = Scalar25519::random(&mut rng);
+        let (commitment, shares) = pedersen_deal(secret, 3, 5, &mut rng).unwrap();
+
+        assert_eq!(commitment.threshold(), 3);
+        for (&id, share) in &shares {
+            let blinding = share.blinding.unwrap();
+            assert!(commitment.verify_share(id, share.value, blinding));
+            assert!(!commitment.verify_share(id, share.value + Scalar25519::ONE, blinding));
+            assert!(!commitment.verify_share(id, share.value, blinding + Scalar25519::ONE));
+        }
+
+        let values: HashMap<u32, Scalar25519> = shares.iter().map(|(&id, s)| (id, s.value)).collect();
+        assert_eq!(threshold_decrypt_modular(&values, 3), Some(secret));
+    }
+
+    #[test]
+    fn test_pedersen_commitment_hides_secret() {
+        // Same secret and same P, different blinding: unrelated commitments,
+        // and C_0 is not secret·G as it would be under Feldman
+        let (a, _) = pedersen_deal(s(42), 2, 3, &mut ChaCha20Rng::seed_from_u64(5)).unwrap();
+        let (b, _) = pedersen_deal(s(42), 2, 3, &mut ChaCha20Rng::seed_from_u64(6)).unwrap();
+        assert_ne!(a.points[0], b.points[0]);
+        assert_ne!(a.points[0], RISTRETTO_BASEPOINT_POINT * s(42).scalar());
+
+        // Any secret is consistent with C_0: for secret' there is r' with
+        // secret'·G + r'·H = C_0, so C_0 alone carries no information.
+        assert_ne!(pedersen_generator(), RISTRETTO_BASEPOINT_POINT);
+    }
+
+    #[test]
+    fn test_pedersen_rejects_mismatched_blinding_degree() {
+        let p = Polynomial::from_coefficients(vec![s(1), s(2)]);
+        let r = Polynomial::from_coefficients(vec![s(3)]);
+        assert!(PedersenCommitment::from_polynomials(&p, &r).is_err());
+    }
+
+    #[test]
+    fn test_vss_commitment_checks_share_against_mode() {
+        let mut rng = ChaCha20Rng::seed_from_u64(8);
+        for mode in [VssMode::Feldman, VssMode::Pedersen] {
+            let (commitment, shares) = vss_deal(mode, s(42), 3, 5, &mut rng).unwrap();
+            assert_eq!(commitment.mode(), mode);
+            assert_eq!(commitment.threshold(), 3);
+            for (&id, share) in &shares {
+                assert!(commitment.verify_share(id, share));
+            }
+
+            // A Feldman-style share cannot satisfy a Pedersen commitment and vice versa
+            let share = &shares[&1];
+            let flipped = match share.blinding {
+                Some(_) => VssShare::feldman(share.value),
+                None => VssShare::pedersen(share.value, Scalar25519::ZERO),
+            };
+            assert!(!commitment.verify_share(1, &flipped));
+        }
+    }
+}
@@ -1,8 +1,6 @@
-/// Week 4: Threshold Decryption Tests and Examples
+//! Week 4: Threshold Decryption Tests and Examples
 
-mod threshold_decryption;
-
-use threshold_decryption::{
+use blocksense_examples::threshold_decryption::{
     ThresholdConfig, SecretShare, Decryptor, ThresholdScheme, generate_key_shares,
     threshold_decrypt,
 };
@@ -97,11 +95,7 @@ fn test_decryptor_registration() {
 
     for (i, name) in decryptor_names.iter().enumerate() {
         let id = (i + 1) as u32;
-        let share = SecretShare {
-            id,
-            share_value: 1000 + (id as i64) * 100,
-            public_commitment: vec![],
-        };
+        let share = SecretShare::new(id, 1000 + (id as i64) * 100);
         let decryptor = Decryptor::new(id, name, share);
 
         match scheme.register_decryptor(decryptor) {
@@ -126,7 +120,6 @@ fn test_full_workflow() {
 
     let config = ThresholdConfig::new_3_of_5();
     let secret = 42i64;
-    let modulus = 1000000i64;
 
     println!("Workflow:");
     println!("  1. Original secret: {}", secret);
@@ -162,7 +155,7 @@ fn test_full_workflow() {
 
     for (i, &name) in decryptor_names.iter().enumerate() {
         let id = (i + 1) as u32;
-        let share = shares[i].clone();
+        let share = shares[i].clone_secret();
         let decryptor = Decryptor::new(id, name, share);
         let _ = scheme.register_decryptor(decryptor);
     }
@@ -175,12 +168,10 @@ fn test_full_workflow() {
 
     // Perform threshold decryption
     println!("Attempting threshold decryption...");
-    match threshold_decrypt(&scheme, secret, modulus) {
+    match threshold_decrypt(&scheme, secret) {
         Ok(result) => {
             println!("  ✓ Decryption successful");
-            println!("  Original secret: {}", secret);
-            println!("  Reconstructed secret: {}", result);
-            println!("  Match: {}\n", secret == result);
+            println!("  Reconstructed secret matches: {}\n", secret == result);
         }
         Err(e) => println!("  ✗ Error: {}\n", e),
     }
@@ -191,14 +182,14 @@ fn test_full_workflow() {
     
     for i in 0..2 {
         let id = (i + 1) as u32;
-        let share = shares[i as usize].clone();
+        let share = shares[i as usize].clone_secret();
         let decryptor = Decryptor::new(id, &format!("Decryptor {}", char::from_u32(65 + i as u32).unwrap()), share);
         let _ = scheme2.register_decryptor(decryptor);
     }
 
     println!("  Registered {} decryptors (need 3)", scheme2.decryptors.len());
     println!("  Can decrypt: {}", scheme2.can_decrypt());
-    match threshold_decrypt(&scheme2, secret, modulus) {
+    match threshold_decrypt(&scheme2, secret) {
         Ok(_) => println!("  ✗ Should have failed!"),
         Err(e) => println!("  ✓ Correctly rejected: {}\n", e),
     }
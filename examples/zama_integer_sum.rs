@@ -1,21 +1,28 @@
-/// Homomorphic integer aggregation POC
-/// 
-/// This is a placeholder demonstrating the workflow for Zama Concrete or similar FHE libraries.
-/// 
-/// Workflow:
-/// 1. Load provider ciphertexts (submissions)
-/// 2. Perform homomorphic weighted sum: Σ weight_i * CT_value_i
-/// 3. Subtract threshold: CT_diff = CT_aggregate - CT_threshold
-/// 4. Extract sign bit or use comparison circuit
-/// 5. Decrypt result via threshold scheme (MPC)
-///
-/// Actual Zama/Concrete API may differ; refer to official documentation.
-
+//! Homomorphic integer aggregation POC
+//! 
+//! This is a placeholder demonstrating the workflow for Zama Concrete or similar FHE libraries.
+//! 
+//! Workflow:
+//! 1. Load provider ciphertexts (submissions)
+//! 2. Perform homomorphic weighted sum: Σ weight_i * CT_value_i
+//! 3. Subtract threshold: CT_diff = CT_aggregate - CT_threshold
+//! 4. Extract sign bit or use comparison circuit
+//! 5. Decrypt result via threshold scheme (MPC)
+//!
+//! Actual Zama/Concrete API may differ; refer to official documentation.
+
+use blocksense_examples::threshold_decryption::ThresholdConfig;
+use blocksense_examples::threshold_signature::{
+    verify_oracle_result, OracleResult, Signature, SigningCommittee,
+};
+use curve25519_dalek::ristretto::RistrettoPoint;
+use rand::rngs::OsRng;
 use std::collections::HashMap;
 use std::fmt;
 
 /// Mock ciphertext (in practice, binary blob from Zama)
 #[derive(Clone, Debug)]
+#[allow(dead_code)]
 pub struct Ciphertext {
     id: String,
     encrypted_value: Vec<u8>,
@@ -23,6 +30,7 @@ pub struct Ciphertext {
 
 /// Provider submission structure
 #[derive(Clone, Debug)]
+#[allow(dead_code)]
 pub struct ProviderSubmission {
     provider_id: String,
     quantized_value: i32,
@@ -39,6 +47,7 @@ pub struct AggregationResult {
 }
 
 #[derive(Clone, Debug)]
+#[allow(dead_code)]
 struct FHEPublicKey {
     modulus: u64,
 }
@@ -53,18 +62,23 @@ struct FHEDecryptResult {
     plaintext: i64,
 }
 
+/// Oracle result with the committee's threshold signature
 #[derive(Debug)]
-struct OracleResult {
-    event_id: String,
-    aggregate_value: i64,
-    threshold: i64,
-    result: String,
-    decryptor_set: Vec<String>,
-    signature: String,
+struct SignedOracleResult {
+    result: OracleResult,
+    group_public_key: RistrettoPoint,
+    signature: Signature,
 }
 
-impl fmt::Display for OracleResult {
+impl fmt::Display for SignedOracleResult {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key: String = self
+            .group_public_key
+            .compress()
+            .as_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
         write!(
             f,
             r#"OracleResult {{
@@ -73,9 +87,16 @@ impl fmt::Display for OracleResult {
   threshold: {},
   result: "{}",
   decryptorSet: {:?},
+  groupPublicKey: "0x{}",
   signature: "{}"
 }}"#,
-            self.event_id, self.aggregate_value, self.threshold, self.result, self.decryptor_set, self.signature
+            self.result.event_id,
+            self.result.aggregate_value,
+            self.result.threshold,
+            self.result.result,
+            self.result.decryptor_set,
+            key,
+            self.signature.to_hex()
         )
     }
 }
@@ -171,8 +192,8 @@ fn homomorphic_subtract_threshold(
     // Placeholder: extract plaintext for demo, normally stays encrypted
     let agg_str = String::from_utf8_lossy(&aggregate_ct.encrypted_value);
     let agg_val: i32 = agg_str
-        .split(':')
-        .last()
+        .rsplit(':')
+        .next()
         .and_then(|s| s.parse().ok())
         .unwrap_or(0);
 
@@ -192,8 +213,8 @@ fn extract_result(diff_ct: &Ciphertext) -> bool {
     // Placeholder: in real MPC/FHE, this would be done via threshold decryption
     let diff_str = String::from_utf8_lossy(&diff_ct.encrypted_value);
     let diff_val: i32 = diff_str
-        .split(':')
-        .last()
+        .rsplit(':')
+        .next()
         .and_then(|s| s.parse().ok())
         .unwrap_or(0);
 
@@ -303,18 +324,26 @@ fn main() {
     println!("  Result: {} (aggregate={} > threshold={})", final_result, result.plaintext, threshold_value);
     println!();
 
-    // 7) Output structure
-    let output = OracleResult {
+    // 7) Committee signs the canonical result (FROST, 3-of-5 DKG key)
+    let config = ThresholdConfig::new_3_of_5();
+    let committee = SigningCommittee::generate(&config, &mut OsRng).expect("committee DKG failed");
+    let signers = vec![1, 2, 3];
+    let result = OracleResult {
         event_id: "0xabc123".to_string(),
         aggregate_value: result.plaintext,
         threshold: threshold_value,
         result: final_result.to_string(),
-        decryptor_set: vec![
-            "0x1111".to_string(),
-            "0x2222".to_string(),
-            "0x3333".to_string(),
-        ],
-        signature: "0xsig...".to_string(),
+        decryptor_set: signers.clone(),
+    };
+    let signature = committee
+        .sign_oracle_result(&signers, &result, &mut OsRng)
+        .expect("threshold signing failed");
+    assert!(verify_oracle_result(&committee.public_key, &result, &signature));
+
+    let output = SignedOracleResult {
+        result,
+        group_public_key: committee.public_key,
+        signature,
     };
 
     println!("Final on-chain output:");
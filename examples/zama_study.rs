@@ -1,13 +1,13 @@
-/// Zama/TFHE Study - Learning FHE Operations
-///
-/// This file demonstrates:
-/// 1. Key generation (client key, server key)
-/// 2. Encryption of integers
-/// 3. Homomorphic operations (add, multiply, compare)
-/// 4. Decryption
-///
-/// Note: Full FHE operations available in TFHE with specific configuration
-/// For now, we'll demonstrate the workflow with basic operations
+//! Zama/TFHE Study - Learning FHE Operations
+//!
+//! This file demonstrates:
+//! 1. Key generation (client key, server key)
+//! 2. Encryption of integers
+//! 3. Homomorphic operations (add, multiply, compare)
+//! 4. Decryption
+//!
+//! Note: Full FHE operations available in TFHE with specific configuration
+//! For now, we'll demonstrate the workflow with basic operations
 
 use tfhe::prelude::*;
 use tfhe::{generate_keys, set_server_key, ConfigBuilder};
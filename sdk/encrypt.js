@@ -11,7 +11,8 @@ const fs = require('fs');
 const path = require('path');
 
 function quantizePercent(percentValue, scale = 10000) {
-  if (typeof percentValue !== 'number' || percentValue < 0 || percentValue > 100) {
+  if (typeof percentValue !== 'number' || !Number.isFinite(percentValue) ||
+      percentValue < 0 || percentValue > 100) {
     throw new Error(`Invalid percentage: ${percentValue}`);
   }
   if (percentValue === 0 || percentValue === 100) {
@@ -24,10 +25,15 @@ function quantizePercent(percentValue, scale = 10000) {
   return quantized;
 }
 
+const MAX_PRICE = 92233720.36;
+
 function quantizePrice(priceValue, decimals = 8) {
-  if (typeof priceValue !== 'number' || priceValue < 0) {
+  if (typeof priceValue !== 'number' || !Number.isFinite(priceValue) || priceValue < 0) {
     throw new Error(`Invalid price: ${priceValue}`);
   }
+  if (priceValue > MAX_PRICE) {
+    throw new Error(`Price exceeds maximum [${MAX_PRICE}]: ${priceValue}`);
+  }
   const factor = Math.pow(10, decimals);
   const quantized = Math.round(priceValue * factor);
   const MAX_I64 = BigInt('9223372036854775807');
@@ -38,7 +44,8 @@ function quantizePrice(priceValue, decimals = 8) {
 }
 
 function quantizeRatio(ratioValue, scale = 1000000) {
-  if (typeof ratioValue !== 'number' || ratioValue < 0 || ratioValue > 1) {
+  if (typeof ratioValue !== 'number' || !Number.isFinite(ratioValue) ||
+      ratioValue < 0 || ratioValue > 1) {
     throw new Error(`Invalid ratio: ${ratioValue}`);
   }
   const quantized = Math.round(ratioValue * scale);
@@ -0,0 +1,9 @@
+//! Emit the quantization conformance vectors as JSON
+//!
+//! Usage: cargo run --bin quantization_vectors > tests/vectors/quantization.json
+
+use blocksense_fhe_oracle::vectors::{spec_vector_file, to_json};
+
+fn main() {
+    print!("{}", to_json(&spec_vector_file()));
+}
@@ -0,0 +1,8 @@
+//! Blocksense FHE Oracle
+//!
+//! Quantization of raw market values into the integer domain used by FHE,
+//! plus the shared conformance vectors that keep the Rust and JavaScript
+//! quantizers in agreement with docs/quantization-spec.md.
+
+pub mod quantization;
+pub mod vectors;
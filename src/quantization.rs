@@ -0,0 +1,107 @@
+//! Quantization Module
+//!
+//! Converts real-world continuous values into the integer scales defined in
+//! docs/quantization-spec.md section 1. Mirrors `quantizePercent`,
+//! `quantizePrice` and `quantizeRatio` in `sdk/encrypt.js`.
+
+use std::fmt;
+
+/// Largest price (USD) that still fits in i64 with 8 decimals
+pub const MAX_PRICE: f64 = 92233720.36;
+
+/// Default percentage scale: 100% → 10000 (1 bps precision)
+pub const PERCENT_SCALE: i32 = 10000;
+
+/// Default price precision: 8 decimals (satoshi equivalent)
+pub const PRICE_DECIMALS: u32 = 8;
+
+/// Default ratio scale: 1.0 → 1_000_000
+pub const RATIO_SCALE: i32 = 1000000;
+
+/// Quantization failure (spec section 2.1)
+#[derive(Clone, Debug, PartialEq)]
+pub enum QuantizationError {
+    /// Input is NaN or ±Infinity
+    NonFinite(f64),
+    /// Finite input outside the market's accepted range
+    OutOfRange { value: f64, min: f64, max: f64 },
+    /// Quantized value hits 0 or the full scale (unfalsifiable market)
+    Extreme(i64),
+}
+
+impl fmt::Display for QuantizationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuantizationError::NonFinite(value) => {
+                write!(f, "Value must be finite (not NaN or Infinity): {}", value)
+            }
+            QuantizationError::OutOfRange { value, min, max } => {
+                write!(f, "Value out of range [{}, {}]: {}", min, max, value)
+            }
+            QuantizationError::Extreme(quantized) => {
+                write!(f, "Quantized value is extreme (unfalsifiable): {}", quantized)
+            }
+        }
+    }
+}
+
+impl std::error::Error for QuantizationError {}
+
+fn check_range(value: f64, min: f64, max: f64) -> Result<(), QuantizationError> {
+    if !value.is_finite() {
+        return Err(QuantizationError::NonFinite(value));
+    }
+    if !(min..=max).contains(&value) {
+        return Err(QuantizationError::OutOfRange { value, min, max });
+    }
+    Ok(())
+}
+
+/// Quantize a percentage market value
+///
+/// Rejects 0% and 100% after rounding (unfalsifiable markets)
+pub fn quantize_percent(percent: f64, scale: i32) -> Result<i32, QuantizationError> {
+    check_range(percent, 0.0, 100.0)?;
+
+    let quantized = (percent * (scale as f64 / 100.0)).round() as i32;
+
+    if quantized == 0 || quantized == scale {
+        return Err(QuantizationError::Extreme(quantized as i64));
+    }
+
+    Ok(quantized)
+}
+
+/// Quantize a USD price with the given number of decimals
+pub fn quantize_price(price: f64, decimals: u32) -> Result<i64, QuantizationError> {
+    check_range(price, 0.0, MAX_PRICE)?;
+
+    let factor = 10_f64.powi(decimals as i32);
+    let quantized = (price * factor).round() as i64;
+
+    Ok(quantized)
+}
+
+/// Quantize a ratio market value in [0.0, 1.0]
+pub fn quantize_ratio(ratio: f64, scale: i32) -> Result<i32, QuantizationError> {
+    check_range(ratio, 0.0, 1.0)?;
+
+    let quantized = (ratio * (scale as f64)).round() as i32;
+
+    Ok(quantized)
+}
+
+/// Convert a quantized percentage back to percent (for display/verification)
+pub fn dequantize_percent(quantized: i32, scale: i32) -> f64 {
+    quantized as f64 * 100.0 / scale as f64
+}
+
+/// Convert a quantized price back to USD
+pub fn dequantize_price(quantized: i64, decimals: u32) -> f64 {
+    quantized as f64 / 10_f64.powi(decimals as i32)
+}
+
+/// Convert a quantized ratio back to [0.0, 1.0]
+pub fn dequantize_ratio(quantized: i32, scale: i32) -> f64 {
+    quantized as f64 / scale as f64
+}
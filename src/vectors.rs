@@ -0,0 +1,168 @@
+//! Quantization Conformance Vectors
+//!
+//! Machine-readable copy of the test vectors in docs/quantization-spec.md
+//! section 3. The same file (tests/vectors/quantization.json) is checked by
+//! the Rust harness and by the JavaScript SDK tests, so both quantizers are
+//! held to one set of expectations.
+//!
+//! Regenerate with: `cargo run --bin quantization_vectors > tests/vectors/quantization.json`
+
+use serde::{Deserialize, Serialize};
+
+use crate::quantization::{
+    quantize_percent, quantize_price, quantize_ratio, PERCENT_SCALE, PRICE_DECIMALS, RATIO_SCALE,
+};
+
+/// Sentinel for vectors that must be rejected (matches `ERROR` in the spec)
+pub const ERROR: &str = "ERROR";
+
+/// Vector file format version
+pub const VECTOR_FILE_VERSION: u32 = 1;
+
+/// Market type a vector applies to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Market {
+    Percent,
+    Price,
+    Ratio,
+}
+
+/// One conformance vector
+///
+/// `input` uses JavaScript number spelling ("NaN", "Infinity") so it can be
+/// fed to `Number()` as-is. `expected` is a decimal integer string (prices
+/// exceed 2^53) or `ERROR`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuantizationVector {
+    pub market: Market,
+    pub input: String,
+    pub expected: String,
+    pub description: String,
+}
+
+/// Top-level vector file
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VectorFile {
+    pub version: u32,
+    pub spec: String,
+    pub vectors: Vec<QuantizationVector>,
+}
+
+impl QuantizationVector {
+    fn new(market: Market, input: &str, expected: &str, description: &str) -> Self {
+        QuantizationVector {
+            market,
+            input: input.to_string(),
+            expected: expected.to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    /// Parse the input as an f64 (accepts "NaN", "Infinity", "-Infinity")
+    pub fn input_value(&self) -> Result<f64, String> {
+        self.input
+            .parse::<f64>()
+            .map_err(|e| format!("Invalid vector input '{}': {}", self.input, e))
+    }
+
+    /// Run the input through the Rust quantizer with the spec default scale
+    pub fn quantize(&self) -> Result<i64, String> {
+        let input = self.input_value()?;
+        let result = match self.market {
+            Market::Percent => quantize_percent(input, PERCENT_SCALE).map(i64::from),
+            Market::Price => quantize_price(input, PRICE_DECIMALS),
+            Market::Ratio => quantize_ratio(input, RATIO_SCALE).map(i64::from),
+        };
+        result.map_err(|e| e.to_string())
+    }
+
+    /// Check the Rust quantizer against this vector
+    pub fn check(&self) -> Result<(), String> {
+        let result = self.quantize();
+        match (self.expected.as_str(), result) {
+            (ERROR, Err(_)) => Ok(()),
+            (ERROR, Ok(value)) => Err(format!(
+                "{:?} '{}' ({}): expected ERROR, got {}",
+                self.market, self.input, self.description, value
+            )),
+            (expected, Ok(value)) if expected == value.to_string() => Ok(()),
+            (expected, outcome) => Err(format!(
+                "{:?} '{}' ({}): expected {}, got {:?}",
+                self.market, self.input, self.description, expected, outcome
+            )),
+        }
+    }
+}
+
+/// Test vectors from docs/quantization-spec.md section 3
+pub fn spec_vectors() -> Vec<QuantizationVector> {
+    use Market::*;
+    let v = QuantizationVector::new;
+
+    vec![
+        // 3.1 Percentage Market Tests
+        v(Percent, "50.5", "5050", "Balanced market"),
+        v(Percent, "75.25", "7525", "High confidence"),
+        v(Percent, "25.0", "2500", "Low confidence"),
+        v(Percent, "99.99", "9999", "Very high confidence (but not certain)"),
+        v(Percent, "0.01", "1", "Very low confidence (but possible)"),
+        v(Percent, "-1", ERROR, "Negative percentage"),
+        v(Percent, "101", ERROR, "Over 100%"),
+        v(Percent, "0", ERROR, "Certain no (unfalsifiable)"),
+        v(Percent, "100", ERROR, "Certain yes (unfalsifiable)"),
+        v(Percent, "NaN", ERROR, "NaN input"),
+        v(Percent, "Infinity", ERROR, "Infinity input"),
+        // 3.2 Price Market Tests
+        v(Price, "3250.50", "325050000000", "Ethereum-like price"),
+        v(Price, "1.00", "100000000", "Dollar price"),
+        v(Price, "0.01", "1000000", "Penny"),
+        v(Price, "0.00000001", "1", "Minimum unit (satoshi)"),
+        v(Price, "50000", "5000000000000", "High price (BTC-like)"),
+        v(Price, "92233720", "9223372000000000", "Near i64 max"),
+        v(Price, "-1", ERROR, "Negative price"),
+        v(Price, "92233720.37", ERROR, "Exceeds i64 max"),
+        v(Price, "NaN", ERROR, "NaN input"),
+        v(Price, "Infinity", ERROR, "Infinity input"),
+        // 3.3 Ratio Market Tests
+        v(Ratio, "0.527", "527000", "52.7% ratio"),
+        v(Ratio, "0.5", "500000", "50% (fair odds)"),
+        v(Ratio, "0.99", "990000", "99% (very likely)"),
+        v(Ratio, "0.01", "10000", "1% (unlikely)"),
+        v(Ratio, "0.000001", "1", "Minimum non-zero ratio"),
+        v(Ratio, "1.0", "1000000", "100% certainty"),
+        v(Ratio, "-0.1", ERROR, "Negative ratio"),
+        v(Ratio, "1.1", ERROR, "Ratio > 1.0"),
+        v(Ratio, "NaN", ERROR, "NaN input"),
+        v(Ratio, "Infinity", ERROR, "Infinity input"),
+    ]
+}
+
+/// Vector file as emitted by the `quantization_vectors` binary
+pub fn spec_vector_file() -> VectorFile {
+    VectorFile {
+        version: VECTOR_FILE_VERSION,
+        spec: "docs/quantization-spec.md#3-test-vectors".to_string(),
+        vectors: spec_vectors(),
+    }
+}
+
+/// Serialize a vector file (pretty JSON with trailing newline)
+pub fn to_json(file: &VectorFile) -> String {
+    let mut json = serde_json::to_string_pretty(file).expect("vector file is serializable");
+    json.push('\n');
+    json
+}
+
+/// Parse a vector file, rejecting unknown versions
+pub fn from_json(json: &str) -> Result<VectorFile, String> {
+    let file: VectorFile =
+        serde_json::from_str(json).map_err(|e| format!("Invalid vector file: {}", e))?;
+    if file.version != VECTOR_FILE_VERSION {
+        return Err(format!(
+            "Unsupported vector file version {} (expected {})",
+            file.version, VECTOR_FILE_VERSION
+        ));
+    }
+    Ok(file)
+}
@@ -0,0 +1,40 @@
+/**
+ * Quantization Conformance Vectors (JS SDK)
+ *
+ * Runs sdk/encrypt.js quantizers against the shared vector file emitted by
+ * the Rust side (tests/vectors/quantization.json).
+ *
+ * Regenerate vectors: cargo run --bin quantization_vectors > tests/vectors/quantization.json
+ */
+
+const assert = require('assert');
+const path = require('path');
+
+const { quantizePercent, quantizePrice, quantizeRatio } = require('../sdk/encrypt.js');
+const vectorFile = require(path.join(__dirname, 'vectors', 'quantization.json'));
+
+const QUANTIZERS = {
+  percent: quantizePercent,
+  price: quantizePrice,
+  ratio: quantizeRatio
+};
+
+describe('Quantization Conformance Vectors', () => {
+  it('should use vector file version 1', () => {
+    assert.strictEqual(vectorFile.version, 1);
+  });
+
+  vectorFile.vectors.forEach((vector) => {
+    const quantize = QUANTIZERS[vector.market];
+    const input = Number(vector.input);
+
+    it(`${vector.market}(${vector.input}) → ${vector.expected} (${vector.description})`, () => {
+      if (vector.expected === 'ERROR') {
+        assert.throws(() => quantize(input));
+      } else {
+        // Compare as BigInt: price outputs exceed Number.MAX_SAFE_INTEGER
+        assert.strictEqual(BigInt(quantize(input)), BigInt(vector.expected));
+      }
+    });
+  });
+});
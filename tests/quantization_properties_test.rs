@@ -0,0 +1,261 @@
+//! Quantization property tests
+//!
+//! Checks round-trip error, monotonicity and error variants over generated
+//! inputs. Every property runs under a fixed list of seeds; a failure
+//! message names the seed and case so it can be replayed exactly.
+
+use blocksense_fhe_oracle::quantization::{
+    dequantize_percent, dequantize_price, dequantize_ratio, quantize_percent, quantize_price,
+    quantize_ratio, QuantizationError, MAX_PRICE, PERCENT_SCALE, PRICE_DECIMALS, RATIO_SCALE,
+};
+
+/// Fixed seeds: failures are reproducible by re-running the suite
+const SEEDS: [u64; 4] = [0x5EED_0001, 0xB10C_5E45E, 0xFEE_D00D, 0xDEAD_BEEF_CAFE];
+
+/// Generated cases per seed and property
+const CASES: usize = 2000;
+
+/// SplitMix64: tiny deterministic generator for test inputs
+struct TestRng(u64);
+
+impl TestRng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Fair coin flip
+    fn coin(&mut self) -> bool {
+        self.next_u64() & 1 == 0
+    }
+
+    /// Uniform f64 in [0, 1)
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform f64 in [min, max], with ~1/8 of draws pinned to an endpoint
+    fn range(&mut self, min: f64, max: f64) -> f64 {
+        match self.next_u64() % 16 {
+            0 => min,
+            1 => max,
+            _ => min + (max - min) * self.unit(),
+        }
+    }
+}
+
+/// Run `property` for every seed and case, reporting the first failure
+fn for_all(name: &str, mut property: impl FnMut(&mut TestRng) -> Result<(), String>) {
+    for seed in SEEDS {
+        let mut rng = TestRng(seed);
+        for case in 0..CASES {
+            if let Err(msg) = property(&mut rng) {
+                panic!("property '{}' failed (seed={:#x}, case={}): {}", name, seed, case, msg);
+            }
+        }
+    }
+}
+
+/// Half a quantization unit, plus float slack for values near 2^53
+fn half_unit_tolerance(unit: f64, value: f64) -> f64 {
+    unit / 2.0 + value.abs() * 4.0 * f64::EPSILON
+}
+
+// ============================================================================
+// ROUND-TRIP: quantize → dequantize stays within half a unit
+// ============================================================================
+
+#[test]
+fn prop_percent_round_trip_within_half_unit() {
+    let unit = 100.0 / PERCENT_SCALE as f64;
+    for_all("percent round-trip", |rng| {
+        let input = rng.range(unit, 100.0 - unit);
+        let quantized = quantize_percent(input, PERCENT_SCALE)
+            .map_err(|e| format!("input {} rejected: {}", input, e))?;
+        let error = (dequantize_percent(quantized, PERCENT_SCALE) - input).abs();
+        if error > half_unit_tolerance(unit, input) {
+            return Err(format!("input {} → {} (error {})", input, quantized, error));
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn prop_price_round_trip_within_half_unit() {
+    let unit = 10_f64.powi(-(PRICE_DECIMALS as i32));
+    for_all("price round-trip", |rng| {
+        // Mix small prices with prices near MAX_PRICE
+        let input = if rng.coin() {
+            rng.range(0.0, 10_000.0)
+        } else {
+            rng.range(0.0, MAX_PRICE)
+        };
+        let quantized = quantize_price(input, PRICE_DECIMALS)
+            .map_err(|e| format!("input {} rejected: {}", input, e))?;
+        let error = (dequantize_price(quantized, PRICE_DECIMALS) - input).abs();
+        if error > half_unit_tolerance(unit, input) {
+            return Err(format!("input {} → {} (error {})", input, quantized, error));
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn prop_ratio_round_trip_within_half_unit() {
+    let unit = 1.0 / RATIO_SCALE as f64;
+    for_all("ratio round-trip", |rng| {
+        let input = rng.range(0.0, 1.0);
+        let quantized = quantize_ratio(input, RATIO_SCALE)
+            .map_err(|e| format!("input {} rejected: {}", input, e))?;
+        let error = (dequantize_ratio(quantized, RATIO_SCALE) - input).abs();
+        if error > half_unit_tolerance(unit, input) {
+            return Err(format!("input {} → {} (error {})", input, quantized, error));
+        }
+        Ok(())
+    });
+}
+
+// ============================================================================
+// MONOTONICITY: a <= b implies q(a) <= q(b)
+// ============================================================================
+
+fn ordered_pair(rng: &mut TestRng, min: f64, max: f64) -> (f64, f64) {
+    let a = rng.range(min, max);
+    // Half the time pick a close neighbour to probe rounding boundaries
+    let b = if rng.coin() {
+        (a + (max - min) * 1e-6 * rng.unit()).min(max)
+    } else {
+        rng.range(min, max)
+    };
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+#[test]
+fn prop_percent_monotonic() {
+    let unit = 100.0 / PERCENT_SCALE as f64;
+    for_all("percent monotonic", |rng| {
+        let (a, b) = ordered_pair(rng, unit, 100.0 - unit);
+        let (qa, qb) = (quantize_percent(a, PERCENT_SCALE), quantize_percent(b, PERCENT_SCALE));
+        match (qa, qb) {
+            (Ok(qa), Ok(qb)) if qa <= qb => Ok(()),
+            other => Err(format!("{} <= {} but got {:?}", a, b, other)),
+        }
+    });
+}
+
+#[test]
+fn prop_price_monotonic() {
+    for_all("price monotonic", |rng| {
+        let (a, b) = ordered_pair(rng, 0.0, MAX_PRICE);
+        let (qa, qb) = (quantize_price(a, PRICE_DECIMALS), quantize_price(b, PRICE_DECIMALS));
+        match (qa, qb) {
+            (Ok(qa), Ok(qb)) if qa <= qb => Ok(()),
+            other => Err(format!("{} <= {} but got {:?}", a, b, other)),
+        }
+    });
+}
+
+#[test]
+fn prop_ratio_monotonic() {
+    for_all("ratio monotonic", |rng| {
+        let (a, b) = ordered_pair(rng, 0.0, 1.0);
+        let (qa, qb) = (quantize_ratio(a, RATIO_SCALE), quantize_ratio(b, RATIO_SCALE));
+        match (qa, qb) {
+            (Ok(qa), Ok(qb)) if qa <= qb => Ok(()),
+            other => Err(format!("{} <= {} but got {:?}", a, b, other)),
+        }
+    });
+}
+
+// ============================================================================
+// ERROR VARIANTS
+// ============================================================================
+
+const NON_FINITE: [f64; 3] = [f64::NAN, f64::INFINITY, f64::NEG_INFINITY];
+
+/// Finite value strictly outside [min, max]
+fn out_of_range(rng: &mut TestRng, min: f64, max: f64) -> f64 {
+    let magnitude = match rng.next_u64() % 3 {
+        0 => rng.unit() * 1e-3,
+        1 => rng.unit() * (max - min),
+        _ => rng.unit() * f64::MAX / 2.0,
+    };
+    let value = if rng.coin() {
+        min - magnitude
+    } else {
+        max + magnitude
+    };
+    // Adding a tiny magnitude may round back onto the boundary
+    if (min..=max).contains(&value) {
+        max * 2.0 + 1.0
+    } else {
+        value
+    }
+}
+
+fn expect_out_of_range<T: std::fmt::Debug>(
+    input: f64,
+    result: Result<T, QuantizationError>,
+) -> Result<(), String> {
+    match result {
+        Err(QuantizationError::OutOfRange { value, .. }) if value == input => Ok(()),
+        other => Err(format!("input {} expected OutOfRange, got {:?}", input, other)),
+    }
+}
+
+#[test]
+fn prop_non_finite_inputs_rejected() {
+    for input in NON_FINITE {
+        assert!(matches!(quantize_percent(input, PERCENT_SCALE), Err(QuantizationError::NonFinite(_))));
+        assert!(matches!(quantize_price(input, PRICE_DECIMALS), Err(QuantizationError::NonFinite(_))));
+        assert!(matches!(quantize_ratio(input, RATIO_SCALE), Err(QuantizationError::NonFinite(_))));
+    }
+}
+
+#[test]
+fn prop_percent_out_of_range_rejected() {
+    for_all("percent out of range", |rng| {
+        let input = out_of_range(rng, 0.0, 100.0);
+        expect_out_of_range(input, quantize_percent(input, PERCENT_SCALE))
+    });
+}
+
+#[test]
+fn prop_price_out_of_range_rejected() {
+    for_all("price out of range", |rng| {
+        let input = out_of_range(rng, 0.0, MAX_PRICE);
+        expect_out_of_range(input, quantize_price(input, PRICE_DECIMALS))
+    });
+}
+
+#[test]
+fn prop_ratio_out_of_range_rejected() {
+    for_all("ratio out of range", |rng| {
+        let input = out_of_range(rng, 0.0, 1.0);
+        expect_out_of_range(input, quantize_ratio(input, RATIO_SCALE))
+    });
+}
+
+#[test]
+fn prop_percent_extremes_rejected() {
+    // Anything that rounds to 0 or to the full scale is unfalsifiable
+    let half_unit = 50.0 / PERCENT_SCALE as f64;
+    for_all("percent extremes", |rng| {
+        let input = if rng.coin() {
+            rng.unit() * half_unit * 0.999
+        } else {
+            100.0 - rng.unit() * half_unit * 0.999
+        };
+        match quantize_percent(input, PERCENT_SCALE) {
+            Err(QuantizationError::Extreme(q)) if q == 0 || q == PERCENT_SCALE as i64 => Ok(()),
+            other => Err(format!("input {} expected Extreme, got {:?}", input, other)),
+        }
+    });
+}
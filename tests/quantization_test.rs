@@ -1,7 +1,9 @@
-/// Quantization test suite
-/// 
-/// Validates all quantization functions against specification:
-/// docs/quantization-spec.md
+//! Quantization test suite
+//!
+//! Validates all quantization functions against specification:
+//! docs/quantization-spec.md
+
+use blocksense_fhe_oracle::quantization::{quantize_percent, quantize_price, quantize_ratio};
 
 #[cfg(test)]
 mod quantization_tests {
@@ -274,7 +276,7 @@ mod quantization_tests {
 
         // Dequantize for verification
         let dequantized = average as f64 / 1e8;
-        assert!((dequantized - 3250.83).abs() < 0.01, "Average should be ~$3250.83");
+        assert!((dequantized - 3250.50).abs() < 0.01, "Average should be ~$3250.50");
     }
 
     #[test]
@@ -286,52 +288,3 @@ mod quantization_tests {
         assert!(actual_price > threshold, "Price should exceed threshold");
     }
 }
-
-// Implementation (must be in main module)
-fn quantize_percent(percent: f64, scale: i32) -> Result<i32, String> {
-    if percent < 0.0 || percent > 100.0 {
-        return Err(format!("Percentage out of range [0, 100]: {}", percent));
-    }
-
-    let quantized = (percent * (scale as f64 / 100.0)).round() as i32;
-
-    if quantized == 0 || quantized == scale {
-        return Err("Quantized value is extreme (unfalsifiable)".to_string());
-    }
-
-    Ok(quantized)
-}
-
-fn quantize_price(price: f64, decimals: u32) -> Result<i64, String> {
-    if price < 0.0 {
-        return Err(format!("Price cannot be negative: {}", price));
-    }
-
-    if !price.is_finite() {
-        return Err("Price must be finite (not NaN or Infinity)".to_string());
-    }
-
-    const MAX_PRICE: f64 = 92233720.36;
-    if price > MAX_PRICE {
-        return Err(format!("Price exceeds maximum [{}]: {}", MAX_PRICE, price));
-    }
-
-    let factor = 10_f64.powi(decimals as i32);
-    let quantized = (price * factor).round() as i64;
-
-    Ok(quantized)
-}
-
-fn quantize_ratio(ratio: f64, scale: i32) -> Result<i32, String> {
-    if ratio < 0.0 || ratio > 1.0 {
-        return Err(format!("Ratio out of range [0.0, 1.0]: {}", ratio));
-    }
-
-    if !ratio.is_finite() {
-        return Err("Ratio must be finite (not NaN or Infinity)".to_string());
-    }
-
-    let quantized = (ratio * (scale as f64)).round() as i32;
-
-    Ok(quantized)
-}
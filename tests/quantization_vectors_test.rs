@@ -0,0 +1,97 @@
+//! Quantization conformance vector harness
+//!
+//! Runs every vector in tests/vectors/quantization.json through the Rust
+//! quantizers and checks the file is in sync with docs/quantization-spec.md.
+
+use blocksense_fhe_oracle::vectors::{from_json, spec_vector_file, to_json, Market, QuantizationVector};
+
+const VECTOR_FILE: &str = include_str!("vectors/quantization.json");
+const SPEC: &str = include_str!("../docs/quantization-spec.md");
+
+/// Vectors from the `(input, expected, "description"),` rows of the spec's
+/// section 3 tables, in document order
+fn spec_section_vectors() -> Vec<QuantizationVector> {
+    let start = SPEC.find("## 3. Test Vectors").expect("spec has section 3");
+    let end = start + SPEC[start..].find("\n## 4.").expect("spec has section 4");
+    let mut market = None;
+    let mut vectors = Vec::new();
+    for line in SPEC[start..end].lines().map(str::trim) {
+        if let Some(heading) = line.strip_prefix("### ") {
+            market = Some(match heading.split_whitespace().nth(1) {
+                Some("Percentage") => Market::Percent,
+                Some("Price") => Market::Price,
+                Some("Ratio") => Market::Ratio,
+                other => panic!("Unknown vector table {:?}", other),
+            });
+            continue;
+        }
+        let Some(row) = line.strip_prefix('(').and_then(|l| l.strip_suffix("),")) else {
+            continue;
+        };
+        let fields: Vec<&str> = row.splitn(3, ", ").collect();
+        let [input, expected, description] = fields[..] else {
+            panic!("Malformed vector row: {}", line);
+        };
+        vectors.push(QuantizationVector {
+            market: market.expect("vector row outside a table"),
+            input: input.to_string(),
+            expected: expected.trim_end_matches('n').to_string(),
+            description: description.trim_matches('"').to_string(),
+        });
+    }
+    vectors
+}
+
+#[test]
+fn test_spec_vectors_match_spec_document() {
+    assert_eq!(
+        spec_vector_file().vectors,
+        spec_section_vectors(),
+        "spec_vectors() differs from docs/quantization-spec.md section 3"
+    );
+}
+
+#[test]
+fn test_vector_file_matches_spec() {
+    let file = from_json(VECTOR_FILE).expect("vector file parses");
+    assert_eq!(
+        file,
+        spec_vector_file(),
+        "tests/vectors/quantization.json is stale; regenerate with `cargo run --bin quantization_vectors`"
+    );
+}
+
+#[test]
+fn test_vector_file_is_canonical() {
+    // Emitting the parsed file must reproduce it byte-for-byte
+    let file = from_json(VECTOR_FILE).unwrap();
+    assert_eq!(to_json(&file), VECTOR_FILE);
+}
+
+#[test]
+fn test_rust_quantizers_pass_all_vectors() {
+    let file = from_json(VECTOR_FILE).unwrap();
+    let failures: Vec<String> = file
+        .vectors
+        .iter()
+        .filter_map(|vector| vector.check().err())
+        .collect();
+    assert!(failures.is_empty(), "Vector failures:\n{}", failures.join("\n"));
+}
+
+#[test]
+fn test_vectors_cover_every_market() {
+    let file = from_json(VECTOR_FILE).unwrap();
+    for market in [Market::Percent, Market::Price, Market::Ratio] {
+        let vectors: Vec<_> = file.vectors.iter().filter(|v| v.market == market).collect();
+        assert!(vectors.iter().any(|v| v.expected == "ERROR"), "{:?} has no error vectors", market);
+        assert!(vectors.iter().any(|v| v.input == "NaN"), "{:?} has no NaN vector", market);
+        assert!(vectors.iter().any(|v| v.input == "Infinity"), "{:?} has no Infinity vector", market);
+    }
+}
+
+#[test]
+fn test_unknown_version_rejected() {
+    let json = VECTOR_FILE.replacen("\"version\": 1", "\"version\": 99", 1);
+    assert!(from_json(&json).is_err());
+}